@@ -0,0 +1,11 @@
+#![allow(non_upper_case_globals)]
+use core_foundation_sys::base::CFIndex;
+
+pub const kAXUIElementsKey: &str = "AXUIElementsKey";
+pub const kAXPriorityKey: &str = "AXPriorityKey";
+pub const kAXAnnouncementKey: &str = "AXAnnouncementKey";
+pub const kAXUIElementTitleKey: &str = "AXUIElementTitleKey";
+
+pub const kAXPriorityLow: CFIndex = 10;
+pub const kAXPriorityMedium: CFIndex = 50;
+pub const kAXPriorityHigh: CFIndex = 90;
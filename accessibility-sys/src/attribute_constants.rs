@@ -1,162 +1,311 @@
+// Generated by `cargo run -p codegen` from spec/accessibility.toml. Do not edit.
 #![allow(non_upper_case_globals)]
+
+/// The element's role, e.g. `AXButton`.
 pub const kAXRoleAttribute: &str = "AXRole";
+/// A more specific role, e.g. `AXCloseButton`.
 pub const kAXSubroleAttribute: &str = "AXSubrole";
+/// Localized, human-readable description of the role.
 pub const kAXRoleDescriptionAttribute: &str = "AXRoleDescription";
+/// Help text, usually shown as a tooltip.
 pub const kAXHelpAttribute: &str = "AXHelp";
+/// The visible title or label of the element.
 pub const kAXTitleAttribute: &str = "AXTitle";
+/// The element's value. Its type depends on the role.
 pub const kAXValueAttribute: &str = "AXValue";
+/// Human-readable description of the value.
 pub const kAXValueDescriptionAttribute: &str = "AXValueDescription";
+/// Smallest value the element accepts.
 pub const kAXMinValueAttribute: &str = "AXMinValue";
+/// Largest value the element accepts.
 pub const kAXMaxValueAttribute: &str = "AXMaxValue";
+/// Amount the value changes by for one step.
 pub const kAXValueIncrementAttribute: &str = "AXValueIncrement";
+/// The only values the element accepts.
 pub const kAXAllowedValuesAttribute: &str = "AXAllowedValues";
+/// Text shown while the field is empty.
 pub const kAXPlaceholderValueAttribute: &str = "AXPlaceholderValue";
+/// Whether the element responds to user input.
 pub const kAXEnabledAttribute: &str = "AXEnabled";
+/// Whether the element is busy, e.g. loading content.
 pub const kAXElementBusyAttribute: &str = "AXElementBusy";
+/// Whether the element has keyboard focus.
 pub const kAXFocusedAttribute: &str = "AXFocused";
+/// The element that contains this one.
 pub const kAXParentAttribute: &str = "AXParent";
+/// Elements contained by this one, in visual order.
 pub const kAXChildrenAttribute: &str = "AXChildren";
+/// Children that are selected.
 pub const kAXSelectedChildrenAttribute: &str = "AXSelectedChildren";
+/// Children that are currently visible.
 pub const kAXVisibleChildrenAttribute: &str = "AXVisibleChildren";
+/// The window containing the element.
 pub const kAXWindowAttribute: &str = "AXWindow";
+/// The window, sheet or drawer containing the element.
 pub const kAXTopLevelUIElementAttribute: &str = "AXTopLevelUIElement";
+/// Position and size in screen coordinates.
 pub const kAXFrameAttribute: &str = "AXFrame";
+/// Top-left corner in screen coordinates.
 pub const kAXPositionAttribute: &str = "AXPosition";
+/// Width and height in points.
 pub const kAXSizeAttribute: &str = "AXSize";
+/// `AXHorizontalOrientation` or `AXVerticalOrientation`.
 pub const kAXOrientationAttribute: &str = "AXOrientation";
+/// Describes the element's purpose when it has no title.
 pub const kAXDescriptionAttribute: &str = "AXDescription";
-pub const kAXDescription: &str = "AXDescription"; // old name
+/// Alternate name of [`kAXDescriptionAttribute`].
+pub const kAXDescription: &str = "AXDescription";
+/// The currently selected text.
 pub const kAXSelectedTextAttribute: &str = "AXSelectedText";
+/// Range of the selected text.
 pub const kAXSelectedTextRangeAttribute: &str = "AXSelectedTextRange";
+/// Ranges of all selected text, for discontiguous selections.
 pub const kAXSelectedTextRangesAttribute: &str = "AXSelectedTextRanges";
+/// Range of the characters that are scrolled into view.
 pub const kAXVisibleCharacterRangeAttribute: &str = "AXVisibleCharacterRange";
+/// Number of characters in the text.
 pub const kAXNumberOfCharactersAttribute: &str = "AXNumberOfCharacters";
+/// Text elements that share this element's text storage.
 pub const kAXSharedTextUIElementsAttribute: &str = "AXSharedTextUIElements";
+/// Range of the shared text storage this element displays.
 pub const kAXSharedCharacterRangeAttribute: &str = "AXSharedCharacterRange";
+/// Elements that share keyboard focus with this one.
 pub const kAXSharedFocusElementsAttribute: &str = "AXSharedFocusElements";
+/// Line number of the insertion point.
 pub const kAXInsertionPointLineNumberAttribute: &str = "AXInsertionPointLineNumber";
+/// Whether this is the main window.
 pub const kAXMainAttribute: &str = "AXMain";
+/// Whether the window is minimized to the Dock.
 pub const kAXMinimizedAttribute: &str = "AXMinimized";
+/// The window's close button.
 pub const kAXCloseButtonAttribute: &str = "AXCloseButton";
+/// The window's zoom button.
 pub const kAXZoomButtonAttribute: &str = "AXZoomButton";
+/// The window's minimize button.
 pub const kAXMinimizeButtonAttribute: &str = "AXMinimizeButton";
+/// The window's toolbar button.
 pub const kAXToolbarButtonAttribute: &str = "AXToolbarButton";
+/// The window's full screen button.
 pub const kAXFullScreenButtonAttribute: &str = "AXFullScreenButton";
+/// The document proxy icon in the title bar.
 pub const kAXProxyAttribute: &str = "AXProxy";
+/// The window's resize area.
 pub const kAXGrowAreaAttribute: &str = "AXGrowArea";
+/// Whether the window is modal.
 pub const kAXModalAttribute: &str = "AXModal";
+/// The button pressed by Return.
 pub const kAXDefaultButtonAttribute: &str = "AXDefaultButton";
+/// The button pressed by Escape.
 pub const kAXCancelButtonAttribute: &str = "AXCancelButton";
+/// Character of the item's keyboard shortcut.
 pub const kAXMenuItemCmdCharAttribute: &str = "AXMenuItemCmdChar";
+/// Virtual key code of the item's keyboard shortcut.
 pub const kAXMenuItemCmdVirtualKeyAttribute: &str = "AXMenuItemCmdVirtualKey";
+/// Glyph code of the item's keyboard shortcut.
 pub const kAXMenuItemCmdGlyphAttribute: &str = "AXMenuItemCmdGlyph";
+/// Modifier flags of the item's keyboard shortcut.
 pub const kAXMenuItemCmdModifiersAttribute: &str = "AXMenuItemCmdModifiers";
+/// The check mark or other mark shown next to the item.
 pub const kAXMenuItemMarkCharAttribute: &str = "AXMenuItemMarkChar";
+/// The primary item of an alternate menu item.
 pub const kAXMenuItemPrimaryUIElementAttribute: &str = "AXMenuItemPrimaryUIElement";
-
-pub const kAXMenuItemModifierNone: u32 = 0;
-pub const kAXMenuItemModifierShift: u32 = 1 << 0;
-pub const kAXMenuItemModifierOption: u32 = 1 << 1;
-pub const kAXMenuItemModifierControl: u32 = 1 << 2;
-pub const kAXMenuItemModifierNoCommand: u32 = 1 << 3;
-
+/// The application's menu bar.
 pub const kAXMenuBarAttribute: &str = "AXMenuBar";
+/// The application's windows.
 pub const kAXWindowsAttribute: &str = "AXWindows";
+/// Whether the application is frontmost.
 pub const kAXFrontmostAttribute: &str = "AXFrontmost";
+/// Whether the application is hidden.
 pub const kAXHiddenAttribute: &str = "AXHidden";
+/// The application's main window.
 pub const kAXMainWindowAttribute: &str = "AXMainWindow";
+/// The application's focused window.
 pub const kAXFocusedWindowAttribute: &str = "AXFocusedWindow";
+/// The element with keyboard focus.
 pub const kAXFocusedUIElementAttribute: &str = "AXFocusedUIElement";
+/// The menu bar extras owned by the application.
 pub const kAXExtrasMenuBarAttribute: &str = "AXExtrasMenuBar";
+/// The header element of a column or table.
 pub const kAXHeaderAttribute: &str = "AXHeader";
+/// Whether the element has unsaved changes.
 pub const kAXEditedAttribute: &str = "AXEdited";
+/// Whether the value wraps around at its limits.
 pub const kAXValueWrapsAttribute: &str = "AXValueWraps";
+/// The tabs of a tab group.
 pub const kAXTabsAttribute: &str = "AXTabs";
+/// The static text that serves as the element's title.
 pub const kAXTitleUIElementAttribute: &str = "AXTitleUIElement";
+/// The horizontal scroll bar.
 pub const kAXHorizontalScrollBarAttribute: &str = "AXHorizontalScrollBar";
+/// The vertical scroll bar.
 pub const kAXVerticalScrollBarAttribute: &str = "AXVerticalScrollBar";
+/// Button that shows items that don't fit.
 pub const kAXOverflowButtonAttribute: &str = "AXOverflowButton";
+/// Name of the file the element represents.
 pub const kAXFilenameAttribute: &str = "AXFilename";
+/// Whether the element is expanded.
 pub const kAXExpandedAttribute: &str = "AXExpanded";
+/// Whether the element is selected.
 pub const kAXSelectedAttribute: &str = "AXSelected";
+/// The splitters of a split group.
 pub const kAXSplittersAttribute: &str = "AXSplitters";
+/// Elements after the splitter.
 pub const kAXNextContentsAttribute: &str = "AXNextContents";
+/// URL of the document the window shows, as a string.
 pub const kAXDocumentAttribute: &str = "AXDocument";
+/// The decrement button.
 pub const kAXDecrementButtonAttribute: &str = "AXDecrementButton";
+/// The increment button.
 pub const kAXIncrementButtonAttribute: &str = "AXIncrementButton";
+/// Elements before the splitter.
 pub const kAXPreviousContentsAttribute: &str = "AXPreviousContents";
+/// The content element, e.g. of a scroll area or tab group.
 pub const kAXContentsAttribute: &str = "AXContents";
+/// The incrementor attached to the element.
 pub const kAXIncrementorAttribute: &str = "AXIncrementor";
+/// The hour field.
 pub const kAXHourFieldAttribute: &str = "AXHourField";
+/// The minute field.
 pub const kAXMinuteFieldAttribute: &str = "AXMinuteField";
+/// The second field.
 pub const kAXSecondFieldAttribute: &str = "AXSecondField";
+/// The AM/PM field.
 pub const kAXAMPMFieldAttribute: &str = "AXAMPMField";
+/// The day field.
 pub const kAXDayFieldAttribute: &str = "AXDayField";
+/// The month field.
 pub const kAXMonthFieldAttribute: &str = "AXMonthField";
+/// The year field.
 pub const kAXYearFieldAttribute: &str = "AXYearField";
-pub const kAXColumnTitleAttribute: &str = "AXColumnTitles";
+/// URL of the resource the element represents.
 pub const kAXURLAttribute: &str = "AXURL";
+/// Labels of a slider or level indicator.
 pub const kAXLabelUIElementsAttribute: &str = "AXLabelUIElements";
+/// Value a label corresponds to.
 pub const kAXLabelValueAttribute: &str = "AXLabelValue";
+/// The menu currently shown by the element.
 pub const kAXShownMenuUIElementAttribute: &str = "AXShownMenuUIElement";
+/// Elements this static text is the title of.
 pub const kAXServesAsTitleForUIElementsAttribute: &str = "AXServesAsTitleForUIElements";
+/// Elements related to this one, such as a source list and its detail view.
 pub const kAXLinkedUIElementsAttribute: &str = "AXLinkedUIElements";
+/// All rows.
 pub const kAXRowsAttribute: &str = "AXRows";
+/// Rows scrolled into view.
 pub const kAXVisibleRowsAttribute: &str = "AXVisibleRows";
+/// Selected rows.
 pub const kAXSelectedRowsAttribute: &str = "AXSelectedRows";
+/// All columns.
 pub const kAXColumnsAttribute: &str = "AXColumns";
+/// Columns scrolled into view.
 pub const kAXVisibleColumnsAttribute: &str = "AXVisibleColumns";
+/// Selected columns.
 pub const kAXSelectedColumnsAttribute: &str = "AXSelectedColumns";
+/// `AXAscendingSortDirection`, `AXDescendingSortDirection` or `AXUnknownSortDirection`.
 pub const kAXSortDirectionAttribute: &str = "AXSortDirection";
+/// Position of the row or column in its table.
 pub const kAXIndexAttribute: &str = "AXIndex";
+/// Whether the row's children are shown.
 pub const kAXDisclosingAttribute: &str = "AXDisclosing";
+/// Rows shown below this row when it is expanded.
 pub const kAXDisclosedRowsAttribute: &str = "AXDisclosedRows";
+/// The row this row is shown below.
 pub const kAXDisclosedByRowAttribute: &str = "AXDisclosedByRow";
+/// Nesting depth of the row, starting at 0.
 pub const kAXDisclosureLevelAttribute: &str = "AXDisclosureLevel";
+/// Area of the matte that is not obscured.
 pub const kAXMatteHoleAttribute: &str = "AXMatteHole";
+/// The element the matte clips.
 pub const kAXMatteContentUIElementAttribute: &str = "AXMatteContentUIElement";
+/// Markers of a ruler.
 pub const kAXMarkerUIElementsAttribute: &str = "AXMarkerUIElements";
+/// Units of a ruler.
 pub const kAXUnitsAttribute: &str = "AXUnits";
+/// Localized description of a ruler's units.
 pub const kAXUnitDescriptionAttribute: &str = "AXUnitDescription";
+/// Kind of ruler marker, e.g. `AXLeftTabStopMarker`.
 pub const kAXMarkerTypeAttribute: &str = "AXMarkerType";
+/// Localized description of the marker type.
 pub const kAXMarkerTypeDescriptionAttribute: &str = "AXMarkerTypeDescription";
+/// Whether the application a Dock item represents is running.
 pub const kAXIsApplicationRunningAttribute: &str = "AXIsApplicationRunning";
+/// The search button of a search field.
 pub const kAXSearchButtonAttribute: &str = "AXSearchButton";
+/// The clear button of a search field.
 pub const kAXClearButtonAttribute: &str = "AXClearButton";
+/// The application with keyboard focus.
 pub const kAXFocusedApplicationAttribute: &str = "AXFocusedApplication";
+/// Number of rows.
 pub const kAXRowCountAttribute: &str = "AXRowCount";
+/// Number of columns.
 pub const kAXColumnCountAttribute: &str = "AXColumnCount";
+/// Whether the grid is laid out row by row.
 pub const kAXOrderedByRowAttribute: &str = "AXOrderedByRow";
+/// Value at which the indicator shows a warning.
 pub const kAXWarningValueAttribute: &str = "AXWarningValue";
+/// Value at which the indicator shows a critical state.
 pub const kAXCriticalValueAttribute: &str = "AXCriticalValue";
+/// Selected cells.
 pub const kAXSelectedCellsAttribute: &str = "AXSelectedCells";
+/// Cells scrolled into view.
 pub const kAXVisibleCellsAttribute: &str = "AXVisibleCells";
+/// The row header elements.
 pub const kAXRowHeaderUIElementsAttribute: &str = "AXRowHeaderUIElements";
+/// The column header elements.
 pub const kAXColumnHeaderUIElementsAttribute: &str = "AXColumnHeaderUIElements";
+/// Rows the cell spans.
 pub const kAXRowIndexRangeAttribute: &str = "AXRowIndexRange";
+/// Columns the cell spans.
 pub const kAXColumnIndexRangeAttribute: &str = "AXColumnIndexRange";
+/// Horizontal units of a layout area.
 pub const kAXHorizontalUnitsAttribute: &str = "AXHorizontalUnits";
+/// Vertical units of a layout area.
 pub const kAXVerticalUnitsAttribute: &str = "AXVerticalUnits";
+/// Localized description of the horizontal units.
 pub const kAXHorizontalUnitDescriptionAttribute: &str = "AXHorizontalUnitDescription";
+/// Localized description of the vertical units.
 pub const kAXVerticalUnitDescriptionAttribute: &str = "AXVerticalUnitDescription";
+/// Resize handles of a layout item.
 pub const kAXHandlesAttribute: &str = "AXHandles";
+/// The element's text.
 pub const kAXTextAttribute: &str = "AXText";
+/// The part of the text that is visible.
 pub const kAXVisibleTextAttribute: &str = "AXVisibleText";
+/// Whether the text can be edited.
 pub const kAXIsEditableAttribute: &str = "AXIsEditable";
+/// The column header elements.
 pub const kAXColumnTitlesAttribute: &str = "AXColumnTitles";
+/// Alternate name of [`kAXColumnTitlesAttribute`].
+pub const kAXColumnTitleAttribute: &str = "AXColumnTitles";
+/// Developer-assigned identifier, stable across launches.
 pub const kAXIdentifierAttribute: &str = "AXIdentifier";
+/// Whether alternate UI, such as hover controls, is shown.
 pub const kAXAlternateUIVisibleAttribute: &str = "AXAlternateUIVisible";
+/// Line number containing the character index.
 pub const kAXLineForIndexParameterizedAttribute: &str = "AXLineForIndex";
+/// Character range of a line.
 pub const kAXRangeForLineParameterizedAttribute: &str = "AXRangeForLine";
+/// Text in a character range.
 pub const kAXStringForRangeParameterizedAttribute: &str = "AXStringForRange";
+/// Range of the character at a screen point.
 pub const kAXRangeForPositionParameterizedAttribute: &str = "AXRangeForPosition";
+/// Range of the composed character at an index.
 pub const kAXRangeForIndexParameterizedAttribute: &str = "AXRangeForIndex";
+/// Screen bounds of a character range.
 pub const kAXBoundsForRangeParameterizedAttribute: &str = "AXBoundsForRange";
+/// RTF data for a character range.
 pub const kAXRTFForRangeParameterizedAttribute: &str = "AXRTFForRange";
+/// Attributed string for a character range.
 pub const kAXAttributedStringForRangeParameterizedAttribute: &str = "AXAttributedStringForRange";
+/// Range of the style run containing an index.
 pub const kAXStyleRangeForIndexParameterizedAttribute: &str = "AXStyleRangeForIndex";
+/// Cell at a column and row index.
 pub const kAXCellForColumnAndRowParameterizedAttribute: &str = "AXCellForColumnAndRow";
+/// Converts a screen point to layout coordinates.
 pub const kAXLayoutPointForScreenPointParameterizedAttribute: &str = "AXLayoutPointForScreenPoint";
+/// Converts a screen size to layout units.
 pub const kAXLayoutSizeForScreenSizeParameterizedAttribute: &str = "AXLayoutSizeForScreenSize";
+/// Converts a layout point to screen coordinates.
 pub const kAXScreenPointForLayoutPointParameterizedAttribute: &str = "AXScreenPointForLayoutPoint";
+/// Converts a layout size to screen units.
 pub const kAXScreenSizeForLayoutSizeParameterizedAttribute: &str = "AXScreenSizeForLayoutSize";
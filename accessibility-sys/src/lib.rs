@@ -1,6 +1,8 @@
 mod action_constants;
+mod announcement_constants;
 mod attribute_constants;
 mod error;
+mod menu_constants;
 mod notification_constants;
 mod role_constants;
 mod text_attributed_string;
@@ -9,8 +11,10 @@ mod value;
 mod value_constants;
 
 pub use action_constants::*;
+pub use announcement_constants::*;
 pub use attribute_constants::*;
 pub use error::*;
+pub use menu_constants::*;
 pub use notification_constants::*;
 pub use role_constants::*;
 pub use text_attributed_string::*;
@@ -0,0 +1,7 @@
+#![allow(non_upper_case_globals)]
+
+pub const kAXMenuItemModifierNone: u32 = 0;
+pub const kAXMenuItemModifierShift: u32 = 1 << 0;
+pub const kAXMenuItemModifierOption: u32 = 1 << 1;
+pub const kAXMenuItemModifierControl: u32 = 1 << 2;
+pub const kAXMenuItemModifierNoCommand: u32 = 1 << 3;
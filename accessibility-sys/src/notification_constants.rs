@@ -1,5 +1,5 @@
+// Generated by `cargo run -p codegen` from spec/accessibility.toml. Do not edit.
 #![allow(non_upper_case_globals)]
-use core_foundation_sys::base::CFIndex;
 
 pub const kAXMainWindowChangedNotification: &str = "AXMainWindowChanged";
 pub const kAXFocusedWindowChangedNotification: &str = "AXFocusedWindowChanged";
@@ -38,11 +38,3 @@ pub const kAXSelectedTextChangedNotification: &str = "AXSelectedTextChanged";
 pub const kAXTitleChangedNotification: &str = "AXTitleChanged";
 pub const kAXLayoutChangedNotification: &str = "AXLayoutChanged";
 pub const kAXAnnouncementRequestedNotification: &str = "AXAnnouncementRequested";
-pub const kAXUIElementsKey: &str = "AXUIElementsKey";
-pub const kAXPriorityKey: &str = "AXPriorityKey";
-pub const kAXAnnouncementKey: &str = "AXAnnouncementKey";
-pub const kAXUIElementTitleKey: &str = "AXUIElementTitleKey";
-
-pub const kAXPriorityLow: CFIndex = 10;
-pub const kAXPriorityMedium: CFIndex = 50;
-pub const kAXPriorityHigh: CFIndex = 90;
@@ -1,3 +1,4 @@
+// Generated by `cargo run -p codegen` from spec/accessibility.toml. Do not edit.
 #![allow(non_upper_case_globals)]
 
 pub const kAXApplicationRole: &str = "AXApplication";
@@ -0,0 +1,722 @@
+use std::{collections::BTreeMap, fmt, ptr};
+
+use accessibility_sys::{error_string, AXError};
+use core_foundation::{
+    array::CFArray,
+    attributed_string::{CFAttributedString, CFAttributedStringGetString},
+    base::{CFCopyTypeIDDescription, CFRange, CFType, TCFType},
+    boolean::CFBoolean,
+    dictionary::CFDictionary,
+    number::{CFNumber, CFNumberIsFloatType},
+    string::CFString,
+    url::{CFURLCreateWithString, CFURL},
+};
+use core_graphics_types::geometry::{CGPoint, CGRect, CGSize};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    value::{AXErrorValue, AXValue},
+    AXUIElement, AXUIElementAttributes, Frame,
+};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl From<CGPoint> for Point {
+    fn from(point: CGPoint) -> Self {
+        Self {
+            x: point.x,
+            y: point.y,
+        }
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Size {
+    pub width: f64,
+    pub height: f64,
+}
+
+impl From<CGSize> for Size {
+    fn from(size: CGSize) -> Self {
+        Self {
+            width: size.width,
+            height: size.height,
+        }
+    }
+}
+
+impl fmt::Display for Size {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Range {
+    pub location: i64,
+    pub length: i64,
+}
+
+impl From<CFRange> for Range {
+    fn from(range: CFRange) -> Self {
+        Self {
+            location: range.location as i64,
+            length: range.length as i64,
+        }
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.location, self.location + self.length)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Number {
+    Integer(i64),
+    Float(f64),
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::Integer(n) => write!(f, "{}", n),
+            Number::Float(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+/// A decoded attribute value.
+///
+/// Unlike [`CFType`], this is plain data: elements are reduced to a label
+/// such as `AXButton "OK"`, so values can be compared, printed and
+/// serialized without a live application.
+///
+/// Strings, booleans, numbers and arrays serialize as plain JSON values;
+/// everything else is wrapped in a single-key object naming its kind, e.g.
+/// `{"point": {"x": 1.0, "y": 2.0}}`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(from = "Repr", into = "Repr")]
+pub enum AXAny {
+    String(String),
+    Bool(bool),
+    Number(Number),
+    Point(Point),
+    Size(Size),
+    Rect(Frame),
+    Range(Range),
+    Url(String),
+    Element(String),
+    Array(Vec<AXAny>),
+    Dictionary(BTreeMap<String, AXAny>),
+    AttributedString(String),
+    Error(AXError),
+    /// A value of a type with no conversion, holding the type's name.
+    Unknown(String),
+}
+
+impl AXAny {
+    /// Converts a value returned by the accessibility API.
+    pub fn from_cftype(value: &CFType) -> Self {
+        if let Some(s) = value.downcast::<CFString>() {
+            AXAny::String(s.to_string())
+        } else if let Some(b) = value.downcast::<CFBoolean>() {
+            AXAny::Bool(b.into())
+        } else if let Some(n) = value.downcast::<CFNumber>() {
+            if unsafe { CFNumberIsFloatType(n.as_concrete_TypeRef()) } != 0 {
+                AXAny::Number(Number::Float(n.to_f64().unwrap_or_default()))
+            } else {
+                AXAny::Number(Number::Integer(n.to_i64().unwrap_or_default()))
+            }
+        } else if let Some(value) = value.downcast::<AXValue<AXErrorValue>>() {
+            value.any()
+        } else if let Some(element) = value.downcast::<AXUIElement>() {
+            AXAny::Element(element_label(&element))
+        } else if let Some(url) = value.downcast::<CFURL>() {
+            AXAny::Url(url.get_string().to_string())
+        } else if let Some(array) = value.downcast::<CFArray>() {
+            let array: CFArray<CFType> =
+                unsafe { CFArray::wrap_under_get_rule(array.as_concrete_TypeRef()) };
+
+            AXAny::Array(array.iter().map(|v| AXAny::from_cftype(&v)).collect())
+        } else if let Some(dictionary) = value.downcast::<CFDictionary>() {
+            let (keys, values) = dictionary.get_keys_and_values();
+            let entries = keys.into_iter().zip(values).map(|(k, v)| unsafe {
+                let key = AXAny::from_cftype(&CFType::wrap_under_get_rule(k));
+                let value = AXAny::from_cftype(&CFType::wrap_under_get_rule(v));
+
+                (key.to_string(), value)
+            });
+
+            AXAny::Dictionary(entries.collect())
+        } else if let Some(string) = value.downcast::<CFAttributedString>() {
+            let text = unsafe {
+                CFString::wrap_under_get_rule(CFAttributedStringGetString(
+                    string.as_concrete_TypeRef(),
+                ))
+            };
+
+            AXAny::AttributedString(text.to_string())
+        } else {
+            AXAny::Unknown(type_name(value))
+        }
+    }
+
+    /// The text of a string, URL, element label or attributed string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AXAny::String(s) | AXAny::Url(s) | AXAny::Element(s) | AXAny::AttributedString(s) => {
+                Some(s)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            AXAny::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            AXAny::Number(Number::Integer(n)) => Some(*n),
+            AXAny::Number(Number::Float(n)) if n.fract() == 0.0 => Some(*n as i64),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            AXAny::Number(Number::Integer(n)) => Some(*n as f64),
+            AXAny::Number(Number::Float(n)) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Converts back to a value the accessibility API accepts. Elements,
+    /// dictionaries, attributed strings, errors and unknown values can't be
+    /// converted.
+    pub fn to_cftype(&self) -> Option<CFType> {
+        let value = match self {
+            AXAny::String(s) => CFString::new(s).into_CFType(),
+            AXAny::Bool(b) => CFBoolean::from(*b).into_CFType(),
+            AXAny::Number(Number::Integer(n)) => CFNumber::from(*n).into_CFType(),
+            AXAny::Number(Number::Float(n)) => CFNumber::from(*n).into_CFType(),
+            AXAny::Point(p) => AXValue::new(&CGPoint::new(p.x, p.y)).ok()?.into_CFType(),
+            AXAny::Size(s) => AXValue::new(&CGSize::new(s.width, s.height))
+                .ok()?
+                .into_CFType(),
+            AXAny::Rect(r) => AXValue::new(&CGRect::from(*r)).ok()?.into_CFType(),
+            AXAny::Range(r) => AXValue::new(&CFRange::init(r.location as _, r.length as _))
+                .ok()?
+                .into_CFType(),
+            AXAny::Url(s) => url(s)?.into_CFType(),
+            AXAny::Array(items) => {
+                let items = items
+                    .iter()
+                    .map(|item| item.to_cftype())
+                    .collect::<Option<Vec<_>>>()?;
+
+                CFArray::from_CFTypes(&items).into_CFType()
+            }
+            AXAny::Element(_)
+            | AXAny::Dictionary(_)
+            | AXAny::AttributedString(_)
+            | AXAny::Error(_)
+            | AXAny::Unknown(_) => return None,
+        };
+
+        Some(value)
+    }
+
+    /// Parses `text` as a value of the same kind as this one, e.g. to turn a
+    /// command line argument into a new value for an attribute.
+    ///
+    /// Points, sizes and rectangles take their numbers in order, separated by
+    /// commas, spaces or parentheses, so both `10,20` and their display form
+    /// `(10, 20)` work; sizes can also be written `100x50`. Ranges take
+    /// `location,length` or `start..end` and can't be negative.
+    pub fn parse_as(&self, text: &str) -> Result<AXAny, String> {
+        let err = || format!("can't parse {:?} as {}", text, self.kind());
+        let numbers = |count: usize| match numbers(text) {
+            Some(numbers) if numbers.len() == count => Ok(numbers),
+            _ => Err(err()),
+        };
+
+        match self {
+            AXAny::String(_) => Ok(AXAny::String(text.to_owned())),
+            AXAny::AttributedString(_) => Ok(AXAny::String(text.to_owned())),
+            // Checked here so a bad URL is a parse error rather than a
+            // value that can't be converted later.
+            AXAny::Url(_) => url(text)
+                .map(|_| AXAny::Url(text.to_owned()))
+                .ok_or_else(err),
+            AXAny::Bool(_) => match text {
+                "true" | "1" | "yes" => Ok(AXAny::Bool(true)),
+                "false" | "0" | "no" => Ok(AXAny::Bool(false)),
+                _ => Err(err()),
+            },
+            AXAny::Number(Number::Integer(_)) => text
+                .trim()
+                .parse()
+                .map(|n| AXAny::Number(Number::Integer(n)))
+                .map_err(|_| err()),
+            AXAny::Number(Number::Float(_)) => text
+                .trim()
+                .parse()
+                .map(|n| AXAny::Number(Number::Float(n)))
+                .map_err(|_| err()),
+            AXAny::Point(_) => {
+                let n = numbers(2)?;
+
+                Ok(AXAny::Point(Point { x: n[0], y: n[1] }))
+            }
+            AXAny::Size(_) => {
+                let n = numbers(2)?;
+
+                Ok(AXAny::Size(Size {
+                    width: n[0],
+                    height: n[1],
+                }))
+            }
+            AXAny::Rect(_) => {
+                let n = numbers(4)?;
+
+                Ok(AXAny::Rect(Frame {
+                    x: n[0],
+                    y: n[1],
+                    width: n[2],
+                    height: n[3],
+                }))
+            }
+            AXAny::Range(_) => {
+                let range = match text.split_once("..") {
+                    Some((start, end)) => {
+                        let start: i64 = start.trim().parse().map_err(|_| err())?;
+                        let end: i64 = end.trim().parse().map_err(|_| err())?;
+
+                        Range {
+                            location: start,
+                            length: end - start,
+                        }
+                    }
+                    None => {
+                        let n = numbers(2)?;
+
+                        if n.iter().any(|n| n.fract() != 0.0) {
+                            return Err(err());
+                        }
+
+                        Range {
+                            location: n[0] as i64,
+                            length: n[1] as i64,
+                        }
+                    }
+                };
+
+                if range.location < 0 || range.length < 0 {
+                    return Err(err());
+                }
+
+                Ok(AXAny::Range(range))
+            }
+            AXAny::Element(_)
+            | AXAny::Array(_)
+            | AXAny::Dictionary(_)
+            | AXAny::Error(_)
+            | AXAny::Unknown(_) => Err(format!("values of type {} can't be set", self.kind())),
+        }
+    }
+
+    /// The name of this value's kind, e.g. `point`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AXAny::String(_) => "string",
+            AXAny::Bool(_) => "bool",
+            AXAny::Number(_) => "number",
+            AXAny::Point(_) => "point",
+            AXAny::Size(_) => "size",
+            AXAny::Rect(_) => "rect",
+            AXAny::Range(_) => "range",
+            AXAny::Url(_) => "url",
+            AXAny::Element(_) => "element",
+            AXAny::Array(_) => "array",
+            AXAny::Dictionary(_) => "dictionary",
+            AXAny::AttributedString(_) => "attributed_string",
+            AXAny::Error(_) => "error",
+            AXAny::Unknown(_) => "unknown",
+        }
+    }
+}
+
+impl From<&CFType> for AXAny {
+    fn from(value: &CFType) -> Self {
+        AXAny::from_cftype(value)
+    }
+}
+
+impl From<String> for AXAny {
+    fn from(value: String) -> Self {
+        AXAny::String(value)
+    }
+}
+
+impl From<&str> for AXAny {
+    fn from(value: &str) -> Self {
+        AXAny::String(value.to_owned())
+    }
+}
+
+impl fmt::Display for AXAny {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AXAny::String(s) | AXAny::Url(s) | AXAny::Element(s) | AXAny::AttributedString(s) => {
+                f.write_str(s)
+            }
+            AXAny::Bool(b) => write!(f, "{}", b),
+            AXAny::Number(n) => write!(f, "{}", n),
+            AXAny::Point(p) => write!(f, "{}", p),
+            AXAny::Size(s) => write!(f, "{}", s),
+            AXAny::Rect(r) => write!(f, "{}", r),
+            AXAny::Range(r) => write!(f, "{}", r),
+            AXAny::Array(items) => {
+                f.write_str("[")?;
+
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+
+                    write!(f, "{}", item)?;
+                }
+
+                f.write_str("]")
+            }
+            AXAny::Dictionary(entries) => {
+                f.write_str("{")?;
+
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+
+                    write!(f, "{}: {}", key, value)?;
+                }
+
+                f.write_str("}")
+            }
+            AXAny::Error(code) => write!(f, "<{}>", error_string(*code)),
+            AXAny::Unknown(name) => write!(f, "<{}>", name),
+        }
+    }
+}
+
+fn url(s: &str) -> Option<CFURL> {
+    let s = CFString::new(s);
+    let url = unsafe { CFURLCreateWithString(ptr::null(), s.as_concrete_TypeRef(), ptr::null()) };
+
+    if url.is_null() {
+        None
+    } else {
+        Some(unsafe { CFURL::wrap_under_create_rule(url) })
+    }
+}
+
+/// The numbers in `text`, separated by whitespace, commas and parentheses,
+/// or by the `x` between a width and a height. `None` if any part of `text`
+/// isn't a finite number.
+fn numbers(text: &str) -> Option<Vec<f64>> {
+    text.split(|c: char| c.is_whitespace() || matches!(c, ',' | '(' | ')'))
+        .filter(|token| !token.is_empty())
+        .flat_map(|token| token.split('x'))
+        .map(|n| n.parse().ok().filter(|n: &f64| n.is_finite()))
+        .collect()
+}
+
+fn type_name(value: &CFType) -> String {
+    unsafe { CFString::wrap_under_create_rule(CFCopyTypeIDDescription(value.type_of())) }
+        .to_string()
+}
+
+/// `AXButton "OK"`, or just the role when the element has no title.
+fn element_label(element: &AXUIElement) -> String {
+    let role = element.role().map(|r| r.to_string()).unwrap_or_default();
+
+    match element.title() {
+        Ok(title) if title.char_len() > 0 => format!("{} {:?}", role, title.to_string()),
+        _ => role,
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum Repr {
+    Bool(bool),
+    Number(Number),
+    String(String),
+    Array(Vec<AXAny>),
+    Tagged(Tagged),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Tagged {
+    Point(Point),
+    Size(Size),
+    Rect(Frame),
+    Range(Range),
+    Url(String),
+    Element(String),
+    Dictionary(BTreeMap<String, AXAny>),
+    AttributedString(String),
+    Error(AXError),
+    Unknown(String),
+}
+
+impl From<AXAny> for Repr {
+    fn from(value: AXAny) -> Self {
+        match value {
+            AXAny::String(s) => Repr::String(s),
+            AXAny::Bool(b) => Repr::Bool(b),
+            AXAny::Number(n) => Repr::Number(n),
+            AXAny::Array(items) => Repr::Array(items),
+            AXAny::Point(p) => Repr::Tagged(Tagged::Point(p)),
+            AXAny::Size(s) => Repr::Tagged(Tagged::Size(s)),
+            AXAny::Rect(r) => Repr::Tagged(Tagged::Rect(r)),
+            AXAny::Range(r) => Repr::Tagged(Tagged::Range(r)),
+            AXAny::Url(s) => Repr::Tagged(Tagged::Url(s)),
+            AXAny::Element(s) => Repr::Tagged(Tagged::Element(s)),
+            AXAny::Dictionary(d) => Repr::Tagged(Tagged::Dictionary(d)),
+            AXAny::AttributedString(s) => Repr::Tagged(Tagged::AttributedString(s)),
+            AXAny::Error(e) => Repr::Tagged(Tagged::Error(e)),
+            AXAny::Unknown(s) => Repr::Tagged(Tagged::Unknown(s)),
+        }
+    }
+}
+
+impl From<Repr> for AXAny {
+    fn from(repr: Repr) -> Self {
+        match repr {
+            Repr::String(s) => AXAny::String(s),
+            Repr::Bool(b) => AXAny::Bool(b),
+            Repr::Number(n) => AXAny::Number(n),
+            Repr::Array(items) => AXAny::Array(items),
+            Repr::Tagged(Tagged::Point(p)) => AXAny::Point(p),
+            Repr::Tagged(Tagged::Size(s)) => AXAny::Size(s),
+            Repr::Tagged(Tagged::Rect(r)) => AXAny::Rect(r),
+            Repr::Tagged(Tagged::Range(r)) => AXAny::Range(r),
+            Repr::Tagged(Tagged::Url(s)) => AXAny::Url(s),
+            Repr::Tagged(Tagged::Element(s)) => AXAny::Element(s),
+            Repr::Tagged(Tagged::Dictionary(d)) => AXAny::Dictionary(d),
+            Repr::Tagged(Tagged::AttributedString(s)) => AXAny::AttributedString(s),
+            Repr::Tagged(Tagged::Error(e)) => AXAny::Error(e),
+            Repr::Tagged(Tagged::Unknown(s)) => AXAny::Unknown(s),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values() -> Vec<AXAny> {
+        let mut dictionary = BTreeMap::new();
+
+        dictionary.insert("key".to_owned(), AXAny::from("value"));
+
+        vec![
+            AXAny::from("OK"),
+            AXAny::Bool(true),
+            AXAny::Number(Number::Integer(-3)),
+            AXAny::Number(Number::Float(1.5)),
+            AXAny::Number(Number::Float(2.0)),
+            AXAny::Array(vec![AXAny::Bool(false), AXAny::from("a")]),
+            AXAny::Point(Point { x: 1.0, y: -2.5 }),
+            AXAny::Size(Size {
+                width: 3.0,
+                height: 4.0,
+            }),
+            AXAny::Rect(Frame::new(1.0, 2.0, 3.0, 4.0)),
+            AXAny::Range(Range {
+                location: 1,
+                length: 2,
+            }),
+            AXAny::Url("https://example.com/".to_owned()),
+            AXAny::Element("AXButton \"OK\"".to_owned()),
+            AXAny::Dictionary(dictionary),
+            AXAny::AttributedString("styled".to_owned()),
+            AXAny::Error(-25205),
+            AXAny::Unknown("AXTextMarker".to_owned()),
+        ]
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        for value in values() {
+            let json = serde_json::to_string(&value).unwrap();
+
+            assert_eq!(
+                serde_json::from_str::<AXAny>(&json).unwrap(),
+                value,
+                "{}",
+                json
+            );
+        }
+    }
+
+    #[test]
+    fn serde_forms() {
+        let json = |value: AXAny| serde_json::to_string(&value).unwrap();
+
+        assert_eq!(json(AXAny::from("OK")), r#""OK""#);
+        assert_eq!(json(AXAny::Bool(true)), "true");
+        assert_eq!(json(AXAny::Number(Number::Integer(3))), "3");
+        assert_eq!(json(AXAny::Number(Number::Float(2.0))), "2.0");
+        assert_eq!(
+            json(AXAny::Array(vec![AXAny::from("a"), AXAny::Bool(false)])),
+            r#"["a",false]"#
+        );
+        assert_eq!(
+            json(AXAny::Point(Point { x: 1.0, y: 2.0 })),
+            r#"{"point":{"x":1.0,"y":2.0}}"#
+        );
+        assert_eq!(
+            json(AXAny::Range(Range {
+                location: 1,
+                length: 2
+            })),
+            r#"{"range":{"location":1,"length":2}}"#
+        );
+        assert_eq!(
+            json(AXAny::AttributedString("a".to_owned())),
+            r#"{"attributed_string":"a"}"#
+        );
+        // Plain strings stay strings even when they look like a kind.
+        assert_eq!(
+            serde_json::from_str::<AXAny>(r#""point""#).unwrap(),
+            AXAny::from("point")
+        );
+    }
+
+    #[test]
+    fn display() {
+        let shown: Vec<String> = values().iter().map(AXAny::to_string).collect();
+
+        assert_eq!(
+            shown[..14],
+            [
+                "OK",
+                "true",
+                "-3",
+                "1.5",
+                "2",
+                "[false, a]",
+                "(1, -2.5)",
+                "3x4",
+                "(1, 2) 3x4",
+                "1..3",
+                "https://example.com/",
+                "AXButton \"OK\"",
+                "{key: value}",
+                "styled",
+            ]
+        );
+        assert_eq!(shown[15], "<AXTextMarker>");
+    }
+
+    #[test]
+    fn parse_as() {
+        let parse = |kind: AXAny, text: &str| kind.parse_as(text);
+        let point = AXAny::Point(Point::default());
+        let size = AXAny::Size(Size::default());
+        let rect = AXAny::Rect(Frame::default());
+        let range = AXAny::Range(Range::default());
+
+        assert_eq!(parse(AXAny::from(""), " a b "), Ok(AXAny::from(" a b ")));
+        assert_eq!(parse(AXAny::Bool(false), "yes"), Ok(AXAny::Bool(true)));
+        assert_eq!(parse(AXAny::Bool(true), "0"), Ok(AXAny::Bool(false)));
+        assert!(parse(AXAny::Bool(true), "maybe").is_err());
+        assert_eq!(
+            parse(AXAny::Number(Number::Integer(0)), " 42 "),
+            Ok(AXAny::Number(Number::Integer(42)))
+        );
+        assert!(parse(AXAny::Number(Number::Integer(0)), "4.2").is_err());
+        assert_eq!(
+            parse(AXAny::Number(Number::Float(0.0)), "4.2"),
+            Ok(AXAny::Number(Number::Float(4.2)))
+        );
+        assert_eq!(
+            parse(point.clone(), "-10,20.5"),
+            Ok(AXAny::Point(Point { x: -10.0, y: 20.5 }))
+        );
+        assert_eq!(
+            parse(point.clone(), "1e3 2"),
+            Ok(AXAny::Point(Point { x: 1000.0, y: 2.0 }))
+        );
+        assert_eq!(
+            parse(size.clone(), "100x50"),
+            Ok(AXAny::Size(Size {
+                width: 100.0,
+                height: 50.0
+            }))
+        );
+        assert_eq!(
+            parse(rect, "1, 2, 3, 4"),
+            Ok(AXAny::Rect(Frame::new(1.0, 2.0, 3.0, 4.0)))
+        );
+        assert_eq!(
+            parse(range.clone(), "3..5"),
+            Ok(AXAny::Range(Range {
+                location: 3,
+                length: 2
+            }))
+        );
+        assert_eq!(
+            parse(range.clone(), "3, 2"),
+            Ok(AXAny::Range(Range {
+                location: 3,
+                length: 2
+            }))
+        );
+        assert!(parse(AXAny::Element(String::new()), "x").is_err());
+    }
+
+    #[test]
+    fn parse_as_rejects_garbage() {
+        let point = AXAny::Point(Point::default());
+        let range = AXAny::Range(Range::default());
+
+        for text in [
+            "10,x,20", "10-20", "10,20,30", "10", "", "inf,1", "10,20a", "x",
+        ]
+        .iter()
+        {
+            assert!(point.parse_as(text).is_err(), "{:?}", text);
+        }
+
+        for text in ["3..1", "1.5, 2", "1, -2", "-1, 2", "a..b"].iter() {
+            assert!(range.parse_as(text).is_err(), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn parse_as_reads_display_forms() {
+        for value in values().into_iter().skip(6).take(4) {
+            assert_eq!(value.parse_as(&value.to_string()), Ok(value.clone()));
+        }
+    }
+}
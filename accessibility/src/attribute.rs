@@ -1,25 +1,17 @@
-use accessibility_sys::{
-    kAXAllowedValuesAttribute, kAXChildrenAttribute, kAXContentsAttribute, kAXDescriptionAttribute,
-    kAXElementBusyAttribute, kAXEnabledAttribute, kAXFocusedAttribute, kAXFocusedWindowAttribute,
-    kAXFrameAttribute, kAXFrontmostAttribute, kAXHelpAttribute, kAXIdentifierAttribute,
-    kAXLabelValueAttribute, kAXMainAttribute, kAXMainWindowAttribute, kAXMaxValueAttribute,
-    kAXMinValueAttribute, kAXMinimizedAttribute, kAXParentAttribute, kAXPlaceholderValueAttribute,
-    kAXPositionAttribute, kAXRoleAttribute, kAXRoleDescriptionAttribute,
-    kAXSelectedChildrenAttribute, kAXSizeAttribute, kAXSubroleAttribute, kAXTitleAttribute,
-    kAXTitleUIElementAttribute, kAXTopLevelUIElementAttribute, kAXValueAttribute,
-    kAXValueDescriptionAttribute, kAXValueIncrementAttribute, kAXVisibleChildrenAttribute,
-    kAXWindowAttribute, kAXWindowsAttribute,
-};
 use core_foundation::{
     array::CFArray,
-    base::{CFType, TCFType},
+    attributed_string::CFAttributedString,
+    base::{CFRange, CFType, TCFType},
     boolean::CFBoolean,
+    data::CFData,
+    number::CFNumber,
     string::CFString,
+    url::CFURL,
 };
 use core_graphics_types::geometry::{CGPoint, CGRect, CGSize};
 use std::marker::PhantomData;
 
-use crate::{value::AXValue, AXUIElement, ElementFinder, Error};
+use crate::{value::AXValue, AXUIElement, ElementFinder, Error, Role, Subrole};
 
 pub trait TAXAttribute {
     type Value: TCFType;
@@ -40,26 +32,43 @@ impl<T> AXAttribute<T> {
 }
 
 macro_rules! constructor {
-    ($name:ident, $typ:ty, $const:ident $(,$setter:ident)?) => {
+    ([$($doc:tt)*] $name:ident, $typ:ty, $const:ident $(,$setter:ident)?) => {
+        $($doc)*
         pub fn $name() -> AXAttribute<$typ> {
             AXAttribute(CFString::from_static_string($const), PhantomData)
         }
     };
 }
 
+macro_rules! setter_doc {
+    ($name:ident) => {
+        concat!(
+            "Sets the value read by [`",
+            stringify!($name),
+            "`](Self::",
+            stringify!($name),
+            ")."
+        )
+    };
+}
+
 macro_rules! accessor {
-    (@decl $name:ident, AXValue<$typ:ty>, $const:ident, $setter:ident) => {
-        accessor!(@decl $name, AXValue<$typ>, $const);
+    (@decl [$($doc:tt)*] $name:ident, AXValue<$typ:ty>, $const:ident, $setter:ident) => {
+        accessor!(@decl [$($doc)*] $name, AXValue<$typ>, $const);
+        #[doc = setter_doc!($name)]
         fn $setter(&self, value: impl Into<$typ>) -> Result<(), Error>;
     };
-    (@decl $name:ident, $typ:ty, $const:ident, $setter:ident) => {
-        accessor!(@decl $name, $typ, $const);
+    (@decl [$($doc:tt)*] $name:ident, $typ:ty, $const:ident, $setter:ident) => {
+        accessor!(@decl [$($doc)*] $name, $typ, $const);
+        #[doc = setter_doc!($name)]
         fn $setter(&self, value: impl Into<$typ>) -> Result<(), Error>;
     };
-    (@decl $name:ident, AXValue<$typ:ty>, $const:ident) => {
+    (@decl [$($doc:tt)*] $name:ident, AXValue<$typ:ty>, $const:ident) => {
+        $($doc)*
         fn $name(&self) -> Result<$typ, Error>;
     };
-    (@decl $name:ident, $typ:ty, $const:ident) => {
+    (@decl [$($doc:tt)*] $name:ident, $typ:ty, $const:ident) => {
+        $($doc)*
         fn $name(&self) -> Result<$typ, Error>;
     };
     (@impl $name:ident, AXValue<$typ:ty>, $const:ident, $setter:ident) => {
@@ -87,13 +96,21 @@ macro_rules! accessor {
 }
 
 macro_rules! define_attributes {
-    ($(($($args:tt)*)),*,) => {
+    ($($(#[$meta:meta])* ($($args:tt)*)),*,) => {
         impl AXAttribute<()> {
-            $(constructor!($($args)*);)*
+            $(constructor!([$(#[$meta])*] $($args)*);)*
         }
 
         pub trait AXUIElementAttributes {
-            $(accessor!(@decl $($args)*);)*
+            $(accessor!(@decl [$(#[$meta])*] $($args)*);)*
+
+            fn role_typed(&self) -> Result<Role, Error> {
+                Ok(Role::from(&*self.role()?.to_string()))
+            }
+
+            fn subrole_typed(&self) -> Result<Subrole, Error> {
+                Ok(Subrole::from(&*self.subrole()?.to_string()))
+            }
         }
 
         impl AXUIElementAttributes for AXUIElement {
@@ -106,63 +123,93 @@ macro_rules! define_attributes {
     }
 }
 
+macro_rules! parameterized_accessor {
+    (@decl [$($doc:tt)*] $name:ident, AXValue<$typ:ty>, $param:ty, $const:ident) => {
+        $($doc)*
+        fn $name(&self, parameter: &$param) -> Result<$typ, Error>;
+    };
+    (@decl [$($doc:tt)*] $name:ident, $typ:ty, $param:ty, $const:ident) => {
+        $($doc)*
+        fn $name(&self, parameter: &$param) -> Result<$typ, Error>;
+    };
+    (@impl $name:ident, AXValue<$typ:ty>, $param:ty, $const:ident) => {
+        fn $name(&self, parameter: &$param) -> Result<$typ, Error> {
+            self.parameterized_attribute(&AXAttribute::$name(), parameter)
+                .and_then(|v| v.value())
+        }
+    };
+    (@impl $name:ident, $typ:ty, $param:ty, $const:ident) => {
+        fn $name(&self, parameter: &$param) -> Result<$typ, Error> {
+            self.parameterized_attribute(&AXAttribute::$name(), parameter)
+        }
+    };
+}
+
+macro_rules! parameterized_constructor {
+    ([$($doc:tt)*] $name:ident, $typ:ty, $param:ty, $const:ident) => {
+        constructor!([$($doc)*] $name, $typ, $const);
+    };
+}
+
+macro_rules! define_parameterized_attributes {
+    ($($(#[$meta:meta])* ($($args:tt)*)),*,) => {
+        impl AXAttribute<()> {
+            $(parameterized_constructor!([$(#[$meta])*] $($args)*);)*
+        }
+
+        pub trait AXUIElementParameterizedAttributes {
+            $(parameterized_accessor!(@decl [$(#[$meta])*] $($args)*);)*
+        }
+
+        impl AXUIElementParameterizedAttributes for AXUIElement {
+            $(parameterized_accessor!(@impl $($args)*);)*
+        }
+
+        impl AXUIElementParameterizedAttributes for ElementFinder {
+            $(parameterized_accessor!(@impl $($args)*);)*
+        }
+    }
+}
+
 impl AXAttribute<CFType> {
     pub fn new(name: &CFString) -> Self {
         AXAttribute(name.to_owned(), PhantomData)
     }
 }
 
-define_attributes![
-    (allowed_values, CFArray<CFType>, kAXAllowedValuesAttribute),
-    (children, CFArray<AXUIElement>, kAXChildrenAttribute),
-    (contents, AXUIElement, kAXContentsAttribute),
-    (description, CFString, kAXDescriptionAttribute),
-    (element_busy, CFBoolean, kAXElementBusyAttribute),
-    (enabled, CFBoolean, kAXEnabledAttribute),
-    (focused, CFBoolean, kAXFocusedAttribute),
-    (focused_window, AXUIElement, kAXFocusedWindowAttribute),
-    (frontmost, CFBoolean, kAXFrontmostAttribute, set_frontmost),
-    (frame, AXValue<CGRect>, kAXFrameAttribute),
-    (help, CFString, kAXHelpAttribute),
-    (identifier, CFString, kAXIdentifierAttribute),
-    (label_value, CFString, kAXLabelValueAttribute),
-    (main, CFBoolean, kAXMainAttribute, set_main),
-    (main_window, AXUIElement, kAXMainWindowAttribute),
-    (max_value, CFType, kAXMaxValueAttribute),
-    (min_value, CFType, kAXMinValueAttribute),
-    (minimized, CFBoolean, kAXMinimizedAttribute),
-    (parent, AXUIElement, kAXParentAttribute),
-    (placeholder_value, CFString, kAXPlaceholderValueAttribute),
-    (
-        position,
-        AXValue<CGPoint>,
-        kAXPositionAttribute,
-        set_position
-    ),
-    (role, CFString, kAXRoleAttribute),
-    (role_description, CFString, kAXRoleDescriptionAttribute),
-    (
-        selected_children,
-        CFArray<AXUIElement>,
-        kAXSelectedChildrenAttribute
-    ),
-    (size, AXValue<CGSize>, kAXSizeAttribute, set_size),
-    (subrole, CFString, kAXSubroleAttribute),
-    (title, CFString, kAXTitleAttribute),
-    (title_ui_element, AXUIElement, kAXTitleUIElementAttribute),
-    (
-        top_level_ui_element,
-        AXUIElement,
-        kAXTopLevelUIElementAttribute
-    ),
-    (value, CFType, kAXValueAttribute, set_value),
-    (value_description, CFString, kAXValueDescriptionAttribute),
-    (value_increment, CFType, kAXValueIncrementAttribute),
-    (
-        visible_children,
-        CFArray<AXUIElement>,
-        kAXVisibleChildrenAttribute
-    ),
-    (window, AXUIElement, kAXWindowAttribute),
-    (windows, CFArray<AXUIElement>, kAXWindowsAttribute),
-];
+include!("generated/attributes.rs");
+
+#[cfg(test)]
+mod tests {
+    /// Aliases that name the same attribute as another constant.
+    const ALIASES: &[&str] = &["kAXColumnTitleAttribute", "kAXDescription"];
+
+    #[test]
+    fn every_attribute_constant_has_an_accessor() {
+        let constants: Vec<&str> =
+            include_str!("../../accessibility-sys/src/attribute_constants.rs")
+                .lines()
+                .filter_map(|line| line.trim().strip_prefix("pub const "))
+                .filter_map(|rest| rest.split(':').next())
+                .filter(|name| name.starts_with("kAX"))
+                .collect();
+
+        // Entries are `(accessor, types..., kAX..., [setter])`.
+        let accessors: Vec<&str> = include_str!("generated/attributes.rs")
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with('('))
+            .flat_map(|line| line.trim_end_matches("),").split(','))
+            .map(str::trim)
+            .filter(|field| field.starts_with("kAX"))
+            .collect();
+        let missing: Vec<&str> = constants
+            .iter()
+            .filter(|name| !accessors.contains(name) && !ALIASES.contains(name))
+            .copied()
+            .collect();
+
+        assert!(constants.len() > 100, "found only {:?}", constants);
+        assert!(missing.is_empty(), "no accessor for {:?}", missing);
+    }
+}
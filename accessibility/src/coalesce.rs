@@ -0,0 +1,463 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+use crate::ObserverEvent;
+
+/// How bursts of one notification type are collapsed.
+///
+/// Events are keyed by `(element, notification)`. While a key has an event
+/// pending, newer events for the same key replace it ("latest wins").
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CoalesceRule {
+    /// Quiet period that must elapse after the most recent event before the
+    /// pending event is delivered.
+    pub debounce: Duration,
+    /// Upper bound on how long an event may be held back by `debounce`,
+    /// measured from the first event of the burst.
+    pub max_delay: Option<Duration>,
+    /// Minimum spacing between two deliveries for the same key.
+    pub min_interval: Duration,
+}
+
+impl Default for CoalesceRule {
+    fn default() -> Self {
+        Self::passthrough()
+    }
+}
+
+impl CoalesceRule {
+    /// Delivers every event as soon as it is polled.
+    pub fn passthrough() -> Self {
+        Self {
+            debounce: Duration::ZERO,
+            max_delay: None,
+            min_interval: Duration::ZERO,
+        }
+    }
+
+    pub fn debounce(debounce: Duration) -> Self {
+        Self {
+            debounce,
+            ..Self::passthrough()
+        }
+    }
+
+    pub fn throttle(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            ..Self::passthrough()
+        }
+    }
+
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = Some(max_delay);
+        self
+    }
+
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    fn is_passthrough(&self) -> bool {
+        self.debounce.is_zero() && self.min_interval.is_zero()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CoalesceConfig {
+    default_rule: CoalesceRule,
+    rules: HashMap<String, CoalesceRule>,
+    max_pending: usize,
+}
+
+impl Default for CoalesceConfig {
+    fn default() -> Self {
+        Self {
+            default_rule: CoalesceRule::passthrough(),
+            rules: HashMap::new(),
+            max_pending: 4096,
+        }
+    }
+}
+
+impl CoalesceConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the rule used for notifications without a rule of their own.
+    pub fn default_rule(mut self, rule: CoalesceRule) -> Self {
+        self.default_rule = rule;
+        self
+    }
+
+    pub fn rule(mut self, notification: &str, rule: CoalesceRule) -> Self {
+        self.rules.insert(notification.to_owned(), rule);
+        self
+    }
+
+    /// Limits the number of events held by the coalescer. Events arriving
+    /// while the limit is reached are dropped and counted.
+    pub fn max_pending(mut self, max_pending: usize) -> Self {
+        self.max_pending = max_pending;
+        self
+    }
+
+    pub fn rule_for(&self, notification: &str) -> &CoalesceRule {
+        self.rules.get(notification).unwrap_or(&self.default_rule)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CoalesceStats {
+    /// Events passed to [`Coalescer::push`].
+    pub received: usize,
+    /// Events returned from [`Coalescer::poll`] or [`Coalescer::flush`].
+    pub delivered: usize,
+    /// Events replaced by a newer event for the same key.
+    pub collapsed: usize,
+    /// Events discarded because `max_pending` was reached.
+    pub dropped: usize,
+}
+
+struct Pending<E> {
+    event: ObserverEvent<E>,
+    first_seen: Instant,
+    seq: u64,
+}
+
+/// Debounces and throttles an observer event stream.
+///
+/// The coalescer never looks at the clock itself: events carry their own
+/// timestamps and callers pass the current time to [`Coalescer::poll`], so a
+/// sequence of synthetic events always produces the same output.
+pub struct Coalescer<E = crate::AXUIElement> {
+    config: CoalesceConfig,
+    pending: HashMap<(E, String), Pending<E>>,
+    ready: VecDeque<(u64, ObserverEvent<E>)>,
+    last_delivered: HashMap<(E, String), Instant>,
+    seq: u64,
+    stats: CoalesceStats,
+    notification_stats: HashMap<String, CoalesceStats>,
+}
+
+impl<E: Clone + Eq + Hash> Coalescer<E> {
+    pub fn new(config: CoalesceConfig) -> Self {
+        Self {
+            config,
+            pending: HashMap::new(),
+            ready: VecDeque::new(),
+            last_delivered: HashMap::new(),
+            seq: 0,
+            stats: CoalesceStats::default(),
+            notification_stats: HashMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> &CoalesceConfig {
+        &self.config
+    }
+
+    pub fn push(&mut self, event: ObserverEvent<E>) {
+        self.count(&event.notification, |s| s.received += 1);
+
+        let key = (event.element.clone(), event.notification.clone());
+
+        if let Some(pending) = self.pending.get_mut(&key) {
+            pending.event = event;
+            self.count(&key.1, |s| s.collapsed += 1);
+            return;
+        }
+
+        if self.pending.len() + self.ready.len() >= self.config.max_pending {
+            self.count(&key.1, |s| s.dropped += 1);
+            return;
+        }
+
+        self.seq += 1;
+
+        if self.config.rule_for(&key.1).is_passthrough() {
+            self.ready.push_back((self.seq, event));
+        } else {
+            let first_seen = event.timestamp;
+
+            self.pending.insert(
+                key,
+                Pending {
+                    event,
+                    first_seen,
+                    seq: self.seq,
+                },
+            );
+        }
+    }
+
+    /// Returns every event that is due at `now`, in the order they became due.
+    pub fn poll(&mut self, now: Instant) -> Vec<ObserverEvent<E>> {
+        let mut due: Vec<(Instant, u64, ObserverEvent<E>)> = self
+            .ready
+            .drain(..)
+            .map(|(seq, event)| (event.timestamp, seq, event))
+            .collect();
+        let due_keys: Vec<_> = self
+            .pending
+            .iter()
+            .filter(|(key, pending)| self.due_at(key, pending) <= now)
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        for key in due_keys {
+            let pending = self.pending.remove(&key).unwrap();
+            let at = self.due_at(&key, &pending);
+
+            // Throttling is measured from when the event was due rather than
+            // when it was polled, so late polls don't stretch the spacing.
+            due.push((at, pending.seq, pending.event));
+            self.last_delivered.insert(key, at);
+        }
+
+        let config = &self.config;
+        self.last_delivered
+            .retain(|(_, notification), at| now < *at + config.rule_for(notification).min_interval);
+
+        due.sort_by_key(|(at, seq, _)| (*at, *seq));
+        due.into_iter()
+            .map(|(_, _, event)| {
+                self.count(&event.notification, |s| s.delivered += 1);
+                event
+            })
+            .collect()
+    }
+
+    /// Returns every held event immediately, ignoring debounce and throttle
+    /// windows.
+    pub fn flush(&mut self) -> Vec<ObserverEvent<E>> {
+        let mut events: Vec<(u64, ObserverEvent<E>)> = self.ready.drain(..).collect();
+
+        events.extend(
+            self.pending
+                .drain()
+                .map(|(_, pending)| (pending.seq, pending.event)),
+        );
+        self.last_delivered.clear();
+
+        events.sort_by_key(|(seq, _)| *seq);
+        events
+            .into_iter()
+            .map(|(_, event)| {
+                self.count(&event.notification, |s| s.delivered += 1);
+                event
+            })
+            .collect()
+    }
+
+    /// The earliest time at which [`Coalescer::poll`] will return an event,
+    /// if any are held.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        let ready = self.ready.iter().map(|(_, event)| event.timestamp);
+        let pending = self
+            .pending
+            .iter()
+            .map(|(key, pending)| self.due_at(key, pending));
+
+        ready.chain(pending).min()
+    }
+
+    pub fn pending_len(&self) -> usize {
+        self.pending.len() + self.ready.len()
+    }
+
+    pub fn stats(&self) -> CoalesceStats {
+        self.stats
+    }
+
+    pub fn notification_stats(&self, notification: &str) -> CoalesceStats {
+        self.notification_stats
+            .get(notification)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn due_at(&self, key: &(E, String), pending: &Pending<E>) -> Instant {
+        let rule = self.config.rule_for(&key.1);
+        let mut due = pending.event.timestamp + rule.debounce;
+
+        if let Some(max_delay) = rule.max_delay {
+            due = std::cmp::min(due, pending.first_seen + max_delay);
+        }
+
+        if let Some(last) = self.last_delivered.get(key) {
+            due = std::cmp::max(due, *last + rule.min_interval);
+        }
+
+        due
+    }
+
+    fn count(&mut self, notification: &str, f: impl Fn(&mut CoalesceStats)) {
+        f(&mut self.stats);
+        f(self
+            .notification_stats
+            .entry(notification.to_owned())
+            .or_default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALUE: &str = "AXValueChanged";
+    const FOCUS: &str = "AXFocusedUIElementChanged";
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    fn event(element: u32, notification: &str, at: Instant) -> ObserverEvent<u32> {
+        ObserverEvent::new(element, notification, at)
+    }
+
+    fn times(events: &[ObserverEvent<u32>], t0: Instant) -> Vec<(u32, u64)> {
+        events
+            .iter()
+            .map(|e| (e.element, (e.timestamp - t0).as_millis() as u64))
+            .collect()
+    }
+
+    #[test]
+    fn passthrough_delivers_on_the_next_poll() {
+        let t0 = Instant::now();
+        let mut coalescer = Coalescer::new(CoalesceConfig::new());
+
+        coalescer.push(event(1, VALUE, t0));
+        coalescer.push(event(1, VALUE, t0 + ms(1)));
+
+        assert_eq!(coalescer.next_deadline(), Some(t0));
+        assert_eq!(times(&coalescer.poll(t0 + ms(1)), t0), [(1, 0), (1, 1)]);
+        assert_eq!(coalescer.pending_len(), 0);
+    }
+
+    #[test]
+    fn debounce_merges_events_within_the_window() {
+        let t0 = Instant::now();
+        let config = CoalesceConfig::new().default_rule(CoalesceRule::debounce(ms(50)));
+        let mut coalescer = Coalescer::new(config);
+
+        coalescer.push(event(1, VALUE, t0));
+        coalescer.push(event(1, VALUE, t0 + ms(10)));
+        coalescer.push(event(2, VALUE, t0 + ms(20)));
+
+        assert!(coalescer.poll(t0 + ms(40)).is_empty());
+        assert_eq!(coalescer.next_deadline(), Some(t0 + ms(60)));
+        assert_eq!(times(&coalescer.poll(t0 + ms(60)), t0), [(1, 10)]);
+        assert_eq!(times(&coalescer.poll(t0 + ms(70)), t0), [(2, 20)]);
+        assert_eq!(
+            coalescer.stats(),
+            CoalesceStats {
+                received: 3,
+                delivered: 2,
+                collapsed: 1,
+                dropped: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn max_delay_delivers_the_trailing_event_of_a_burst() {
+        let t0 = Instant::now();
+        let rule = CoalesceRule::debounce(ms(50)).with_max_delay(ms(100));
+        let mut coalescer = Coalescer::new(CoalesceConfig::new().default_rule(rule));
+
+        for i in 0..4 {
+            coalescer.push(event(1, VALUE, t0 + ms(30 * i)));
+        }
+
+        assert!(coalescer.poll(t0 + ms(99)).is_empty());
+        assert_eq!(times(&coalescer.poll(t0 + ms(100)), t0), [(1, 90)]);
+        assert_eq!(coalescer.pending_len(), 0);
+    }
+
+    #[test]
+    fn throttle_spacing_is_measured_from_the_due_time() {
+        let t0 = Instant::now();
+        let config = CoalesceConfig::new().default_rule(CoalesceRule::throttle(ms(100)));
+        let mut coalescer = Coalescer::new(config);
+
+        coalescer.push(event(1, VALUE, t0));
+        // A late poll must not push the next delivery back.
+        assert_eq!(times(&coalescer.poll(t0 + ms(30)), t0), [(1, 0)]);
+
+        coalescer.push(event(1, VALUE, t0 + ms(40)));
+        assert!(coalescer.poll(t0 + ms(99)).is_empty());
+        assert_eq!(coalescer.next_deadline(), Some(t0 + ms(100)));
+        assert_eq!(times(&coalescer.poll(t0 + ms(100)), t0), [(1, 40)]);
+    }
+
+    #[test]
+    fn rules_apply_per_notification() {
+        let t0 = Instant::now();
+        let config = CoalesceConfig::new().rule(VALUE, CoalesceRule::debounce(ms(50)));
+        let mut coalescer = Coalescer::new(config);
+
+        coalescer.push(event(1, VALUE, t0));
+        coalescer.push(event(1, FOCUS, t0));
+
+        let now = coalescer.poll(t0);
+
+        assert_eq!(now.len(), 1);
+        assert_eq!(now[0].notification, FOCUS);
+
+        let later = coalescer.poll(t0 + ms(50));
+
+        assert_eq!(later.len(), 1);
+        assert_eq!(later[0].notification, VALUE);
+        assert_eq!(coalescer.notification_stats(VALUE).delivered, 1);
+        assert_eq!(coalescer.notification_stats(FOCUS).delivered, 1);
+    }
+
+    #[test]
+    fn flush_returns_held_events_in_arrival_order() {
+        let t0 = Instant::now();
+        let config = CoalesceConfig::new().rule(VALUE, CoalesceRule::debounce(ms(50)));
+        let mut coalescer = Coalescer::new(config);
+
+        coalescer.push(event(1, VALUE, t0));
+        coalescer.push(event(2, FOCUS, t0 + ms(1)));
+        coalescer.push(event(3, VALUE, t0 + ms(2)));
+
+        assert_eq!(times(&coalescer.flush(), t0), [(1, 0), (2, 1), (3, 2)]);
+        assert_eq!(coalescer.next_deadline(), None);
+        assert_eq!(coalescer.stats().delivered, 3);
+    }
+
+    #[test]
+    fn events_past_max_pending_are_dropped() {
+        let t0 = Instant::now();
+        let config = CoalesceConfig::new()
+            .default_rule(CoalesceRule::debounce(ms(50)))
+            .max_pending(2);
+        let mut coalescer = Coalescer::new(config);
+
+        for element in 0..4 {
+            coalescer.push(event(element, VALUE, t0));
+        }
+
+        // Updates to an event already held still collapse.
+        coalescer.push(event(0, VALUE, t0 + ms(1)));
+
+        assert_eq!(coalescer.pending_len(), 2);
+        assert_eq!(
+            coalescer.notification_stats(VALUE),
+            CoalesceStats {
+                received: 5,
+                delivered: 0,
+                collapsed: 1,
+                dropped: 2,
+            }
+        );
+    }
+}
@@ -0,0 +1,487 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt,
+};
+
+use serde::Serialize;
+
+use crate::{AXAny, SnapshotNode};
+
+/// One difference between two snapshots.
+///
+/// Paths are the labels of the nodes from the root down to the node in
+/// question (see [`SnapshotNode::label`]). A label is suffixed with its index
+/// when siblings share it.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Change {
+    Added {
+        path: Vec<String>,
+        node: SnapshotNode,
+    },
+    Removed {
+        path: Vec<String>,
+        node: SnapshotNode,
+    },
+    Moved {
+        from: Vec<String>,
+        to: Vec<String>,
+    },
+    Changed {
+        path: Vec<String>,
+        attribute: String,
+        old: Option<AXAny>,
+        new: Option<AXAny>,
+    },
+}
+
+/// Structural difference between two snapshots.
+///
+/// Children are matched by `AXIdentifier` first, then by role and title, and
+/// finally by position among siblings of the same role. Matched nodes whose
+/// sibling order changed, or that appear under a different parent with the
+/// same identifier, are reported as moved.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct TreeDiff {
+    pub changes: Vec<Change>,
+}
+
+impl TreeDiff {
+    pub fn new(old: &SnapshotNode, new: &SnapshotNode) -> Self {
+        let mut diff = Self::default();
+        let old_path = vec![old.label()];
+        let new_path = vec![new.label()];
+
+        diff.diff_node(old, new, &old_path, &new_path);
+        diff.pair_moves();
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    fn diff_node(
+        &mut self,
+        old: &SnapshotNode,
+        new: &SnapshotNode,
+        old_path: &[String],
+        new_path: &[String],
+    ) {
+        let old_attributes = old.all_attributes();
+        let new_attributes = new.all_attributes();
+        let names: BTreeSet<&String> = old_attributes.keys().chain(new_attributes.keys()).collect();
+
+        for name in names {
+            let (a, b) = (old_attributes.get(name), new_attributes.get(name));
+
+            if a != b {
+                self.changes.push(Change::Changed {
+                    path: new_path.to_vec(),
+                    attribute: name.clone(),
+                    old: a.cloned(),
+                    new: b.cloned(),
+                });
+            }
+        }
+
+        let pairs = match_children(&old.children, &new.children);
+        let in_order = longest_increasing(&pairs);
+        let old_segments = segments(&old.children);
+        let new_segments = segments(&new.children);
+        let child_path = |path: &[String], segment: &String| {
+            let mut path = path.to_vec();
+            path.push(segment.clone());
+            path
+        };
+
+        for (k, (i, j)) in pairs.iter().enumerate() {
+            let from = child_path(old_path, &old_segments[*i]);
+            let to = child_path(new_path, &new_segments[*j]);
+
+            if !in_order.contains(&k) {
+                self.changes.push(Change::Moved {
+                    from: from.clone(),
+                    to: to.clone(),
+                });
+            }
+
+            self.diff_node(&old.children[*i], &new.children[*j], &from, &to);
+        }
+
+        for (i, child) in old.children.iter().enumerate() {
+            if !pairs.iter().any(|(a, _)| *a == i) {
+                self.changes.push(Change::Removed {
+                    path: child_path(old_path, &old_segments[i]),
+                    node: child.clone(),
+                });
+            }
+        }
+
+        for (j, child) in new.children.iter().enumerate() {
+            if !pairs.iter().any(|(_, b)| *b == j) {
+                self.changes.push(Change::Added {
+                    path: child_path(new_path, &new_segments[j]),
+                    node: child.clone(),
+                });
+            }
+        }
+    }
+
+    /// Turns a removal and an addition of nodes with the same identifier into
+    /// a move, and diffs the two nodes against each other.
+    fn pair_moves(&mut self) {
+        let mut changes = std::mem::take(&mut self.changes);
+
+        while let Some((i, j)) = find_move(&changes) {
+            let later = changes.remove(std::cmp::max(i, j));
+            let earlier = changes.remove(std::cmp::min(i, j));
+            let (removed, added) = if i < j {
+                (earlier, later)
+            } else {
+                (later, earlier)
+            };
+            let (from, old) = into_path_and_node(removed);
+            let (to, new) = into_path_and_node(added);
+
+            self.changes.push(Change::Moved {
+                from: from.clone(),
+                to: to.clone(),
+            });
+            self.diff_node(&old, &new, &from, &to);
+        }
+
+        changes.append(&mut self.changes);
+        self.changes = changes;
+    }
+}
+
+/// Finds a removed node and an added node sharing an identifier.
+fn find_move(changes: &[Change]) -> Option<(usize, usize)> {
+    changes
+        .iter()
+        .enumerate()
+        .find_map(|(i, change)| match change {
+            Change::Removed { node, .. } if node.identifier.is_some() => changes
+                .iter()
+                .position(|other| match other {
+                    Change::Added { node: added, .. } => added.identifier == node.identifier,
+                    _ => false,
+                })
+                .map(|j| (i, j)),
+            _ => None,
+        })
+}
+
+fn into_path_and_node(change: Change) -> (Vec<String>, SnapshotNode) {
+    match change {
+        Change::Added { path, node } | Change::Removed { path, node } => (path, node),
+        _ => unreachable!(),
+    }
+}
+
+type Matcher = dyn Fn(&SnapshotNode, &SnapshotNode) -> bool;
+
+fn match_children(old: &[SnapshotNode], new: &[SnapshotNode]) -> Vec<(usize, usize)> {
+    let mut old_used = vec![false; old.len()];
+    let mut new_used = vec![false; new.len()];
+    let mut pairs = Vec::new();
+    let passes: [&Matcher; 3] = [
+        &|a, b| a.identifier.is_some() && a.identifier == b.identifier,
+        &|a, b| {
+            a.identifier.is_none()
+                && b.identifier.is_none()
+                && a.title.is_some()
+                && a.role == b.role
+                && a.title == b.title
+        },
+        // Whatever is left is matched by position among siblings of the same
+        // role, so an inserted sibling doesn't turn every later sibling into
+        // a remove/add pair.
+        &|a, b| a.identifier.is_none() && b.identifier.is_none() && a.role == b.role,
+    ];
+
+    for matches in passes.iter() {
+        for (i, a) in old.iter().enumerate() {
+            if old_used[i] {
+                continue;
+            }
+
+            let found = new
+                .iter()
+                .enumerate()
+                .find(|(j, b)| !new_used[*j] && matches(a, b));
+
+            if let Some((j, _)) = found {
+                old_used[i] = true;
+                new_used[j] = true;
+                pairs.push((i, j));
+            }
+        }
+    }
+
+    pairs.sort_unstable();
+    pairs
+}
+
+/// Indices into `pairs` (sorted by old position) that form the longest run
+/// whose new positions are increasing. Everything else has moved.
+fn longest_increasing(pairs: &[(usize, usize)]) -> BTreeSet<usize> {
+    let mut length = vec![1; pairs.len()];
+    let mut previous = vec![None; pairs.len()];
+
+    for k in 0..pairs.len() {
+        for m in 0..k {
+            if pairs[m].1 < pairs[k].1 && length[m] + 1 > length[k] {
+                length[k] = length[m] + 1;
+                previous[k] = Some(m);
+            }
+        }
+    }
+
+    let mut result = BTreeSet::new();
+    let mut current = (0..pairs.len()).max_by_key(|k| (length[*k], std::cmp::Reverse(*k)));
+
+    while let Some(k) = current {
+        result.insert(k);
+        current = previous[k];
+    }
+
+    result
+}
+
+/// The path segment of each sibling: its label, suffixed with its index when
+/// another sibling shares the label.
+fn segments(siblings: &[SnapshotNode]) -> Vec<String> {
+    let labels: Vec<String> = siblings.iter().map(SnapshotNode::label).collect();
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+
+    for label in &labels {
+        *counts.entry(label).or_default() += 1;
+    }
+
+    labels
+        .iter()
+        .enumerate()
+        .map(|(index, label)| {
+            if counts[label.as_str()] > 1 {
+                format!("{}[{}]", label, index)
+            } else {
+                label.clone()
+            }
+        })
+        .collect()
+}
+
+fn display_path(path: &[String]) -> String {
+    path.join(" > ")
+}
+
+fn display_value(value: &Option<AXAny>) -> String {
+    match value {
+        Some(AXAny::String(value)) => format!("{:?}", value),
+        Some(value) => value.to_string(),
+        None => "<none>".to_owned(),
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Added { path, node } => {
+                write!(f, "+ {} ({} nodes)", display_path(path), node.node_count())
+            }
+            Change::Removed { path, node } => {
+                write!(f, "- {} ({} nodes)", display_path(path), node.node_count())
+            }
+            Change::Moved { from, to } => {
+                write!(f, "> {} -> {}", display_path(from), display_path(to))
+            }
+            Change::Changed {
+                path,
+                attribute,
+                old,
+                new,
+            } => write!(
+                f,
+                "~ {}: {} {} -> {}",
+                display_path(path),
+                attribute,
+                display_value(old),
+                display_value(new)
+            ),
+        }
+    }
+}
+
+impl fmt::Display for TreeDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(role: &str, title: Option<&str>, children: Vec<SnapshotNode>) -> SnapshotNode {
+        SnapshotNode {
+            role: role.to_owned(),
+            title: title.map(str::to_owned),
+            children,
+            ..Default::default()
+        }
+    }
+
+    fn button(title: &str) -> SnapshotNode {
+        node("AXButton", Some(title), vec![])
+    }
+
+    fn window(children: Vec<SnapshotNode>) -> SnapshotNode {
+        node("AXWindow", Some("Main"), children)
+    }
+
+    fn path(segments: &[&str]) -> Vec<String> {
+        segments.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn identical_trees() {
+        let tree = window(vec![button("OK"), button("Cancel")]);
+
+        assert!(TreeDiff::new(&tree, &tree).is_empty());
+    }
+
+    #[test]
+    fn insert() {
+        let old = window(vec![button("OK"), button("Cancel")]);
+        let new = window(vec![button("OK"), button("Help"), button("Cancel")]);
+
+        assert_eq!(
+            TreeDiff::new(&old, &new).changes,
+            [Change::Added {
+                path: path(&["AXWindow \"Main\"", "AXButton \"Help\""]),
+                node: button("Help"),
+            }]
+        );
+    }
+
+    #[test]
+    fn remove() {
+        let old = window(vec![button("OK"), button("Help"), button("Cancel")]);
+        let new = window(vec![button("OK"), button("Cancel")]);
+
+        assert_eq!(
+            TreeDiff::new(&old, &new).changes,
+            [Change::Removed {
+                path: path(&["AXWindow \"Main\"", "AXButton \"Help\""]),
+                node: button("Help"),
+            }]
+        );
+    }
+
+    #[test]
+    fn reorder() {
+        let old = window(vec![button("OK"), button("Cancel"), button("Help")]);
+        let new = window(vec![button("Help"), button("OK"), button("Cancel")]);
+        let help = path(&["AXWindow \"Main\"", "AXButton \"Help\""]);
+
+        // OK and Cancel keep their order, so only Help moved.
+        assert_eq!(
+            TreeDiff::new(&old, &new).changes,
+            [Change::Moved {
+                from: help.clone(),
+                to: help,
+            }]
+        );
+    }
+
+    #[test]
+    fn attribute_change() {
+        let mut old = button("Count");
+        let mut new = button("Count");
+
+        old.value = Some(AXAny::from("1"));
+        new.value = Some(AXAny::from("2"));
+
+        assert_eq!(
+            TreeDiff::new(&window(vec![old]), &window(vec![new])).changes,
+            [Change::Changed {
+                path: path(&["AXWindow \"Main\"", "AXButton \"Count\""]),
+                attribute: "AXValue".to_owned(),
+                old: Some(AXAny::from("1")),
+                new: Some(AXAny::from("2")),
+            }]
+        );
+    }
+
+    #[test]
+    fn duplicate_sibling_labels() {
+        let untitled = || node("AXButton", None, vec![]);
+        let old = window(vec![untitled(), untitled()]);
+        let new = window(vec![untitled(), untitled(), untitled()]);
+        let diff = TreeDiff::new(&old, &new);
+
+        assert_eq!(
+            diff.changes,
+            [Change::Added {
+                path: path(&["AXWindow \"Main\"", "AXButton[2]"]),
+                node: untitled(),
+            }]
+        );
+        assert_eq!(
+            TreeDiff::new(&new, &old).changes,
+            [Change::Removed {
+                path: path(&["AXWindow \"Main\"", "AXButton[2]"]),
+                node: untitled(),
+            }]
+        );
+    }
+
+    #[test]
+    fn nested_move() {
+        let save = |title: &str| SnapshotNode {
+            identifier: Some("save".to_owned()),
+            ..button(title)
+        };
+        let old = window(vec![
+            node("AXGroup", Some("Left"), vec![save("Save")]),
+            node("AXGroup", Some("Right"), vec![]),
+        ]);
+        let new = window(vec![
+            node("AXGroup", Some("Left"), vec![]),
+            node("AXGroup", Some("Right"), vec![save("Save All")]),
+        ]);
+        let to = path(&["AXWindow \"Main\"", "AXGroup \"Right\"", "AXButton #save"]);
+
+        assert_eq!(
+            TreeDiff::new(&old, &new).changes,
+            [
+                Change::Moved {
+                    from: path(&["AXWindow \"Main\"", "AXGroup \"Left\"", "AXButton #save"]),
+                    to: to.clone(),
+                },
+                Change::Changed {
+                    path: to,
+                    attribute: "AXTitle".to_owned(),
+                    old: Some(AXAny::from("Save")),
+                    new: Some(AXAny::from("Save All")),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn longest_increasing_keeps_the_longest_run() {
+        let kept: Vec<usize> = longest_increasing(&[(0, 3), (1, 0), (2, 1), (3, 2)])
+            .into_iter()
+            .collect();
+
+        assert_eq!(kept, [1, 2, 3]);
+        assert!(longest_increasing(&[]).is_empty());
+    }
+}
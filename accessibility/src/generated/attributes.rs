@@ -0,0 +1,708 @@
+// Generated by `cargo run -p codegen` from spec/accessibility.toml. Do not edit.
+
+use accessibility_sys::{
+    kAXAMPMFieldAttribute,
+    kAXAllowedValuesAttribute,
+    kAXAlternateUIVisibleAttribute,
+    kAXAttributedStringForRangeParameterizedAttribute,
+    kAXBoundsForRangeParameterizedAttribute,
+    kAXCancelButtonAttribute,
+    kAXCellForColumnAndRowParameterizedAttribute,
+    kAXChildrenAttribute,
+    kAXClearButtonAttribute,
+    kAXCloseButtonAttribute,
+    kAXColumnCountAttribute,
+    kAXColumnHeaderUIElementsAttribute,
+    kAXColumnIndexRangeAttribute,
+    kAXColumnTitlesAttribute,
+    kAXColumnsAttribute,
+    kAXContentsAttribute,
+    kAXCriticalValueAttribute,
+    kAXDayFieldAttribute,
+    kAXDecrementButtonAttribute,
+    kAXDefaultButtonAttribute,
+    kAXDescriptionAttribute,
+    kAXDisclosedByRowAttribute,
+    kAXDisclosedRowsAttribute,
+    kAXDisclosingAttribute,
+    kAXDisclosureLevelAttribute,
+    kAXDocumentAttribute,
+    kAXEditedAttribute,
+    kAXElementBusyAttribute,
+    kAXEnabledAttribute,
+    kAXExpandedAttribute,
+    kAXExtrasMenuBarAttribute,
+    kAXFilenameAttribute,
+    kAXFocusedApplicationAttribute,
+    kAXFocusedAttribute,
+    kAXFocusedUIElementAttribute,
+    kAXFocusedWindowAttribute,
+    kAXFrameAttribute,
+    kAXFrontmostAttribute,
+    kAXFullScreenButtonAttribute,
+    kAXGrowAreaAttribute,
+    kAXHandlesAttribute,
+    kAXHeaderAttribute,
+    kAXHelpAttribute,
+    kAXHiddenAttribute,
+    kAXHorizontalScrollBarAttribute,
+    kAXHorizontalUnitDescriptionAttribute,
+    kAXHorizontalUnitsAttribute,
+    kAXHourFieldAttribute,
+    kAXIdentifierAttribute,
+    kAXIncrementButtonAttribute,
+    kAXIncrementorAttribute,
+    kAXIndexAttribute,
+    kAXInsertionPointLineNumberAttribute,
+    kAXIsApplicationRunningAttribute,
+    kAXIsEditableAttribute,
+    kAXLabelUIElementsAttribute,
+    kAXLabelValueAttribute,
+    kAXLayoutPointForScreenPointParameterizedAttribute,
+    kAXLayoutSizeForScreenSizeParameterizedAttribute,
+    kAXLineForIndexParameterizedAttribute,
+    kAXLinkedUIElementsAttribute,
+    kAXMainAttribute,
+    kAXMainWindowAttribute,
+    kAXMarkerTypeAttribute,
+    kAXMarkerTypeDescriptionAttribute,
+    kAXMarkerUIElementsAttribute,
+    kAXMatteContentUIElementAttribute,
+    kAXMatteHoleAttribute,
+    kAXMaxValueAttribute,
+    kAXMenuBarAttribute,
+    kAXMenuItemCmdCharAttribute,
+    kAXMenuItemCmdGlyphAttribute,
+    kAXMenuItemCmdModifiersAttribute,
+    kAXMenuItemCmdVirtualKeyAttribute,
+    kAXMenuItemMarkCharAttribute,
+    kAXMenuItemPrimaryUIElementAttribute,
+    kAXMinValueAttribute,
+    kAXMinimizeButtonAttribute,
+    kAXMinimizedAttribute,
+    kAXMinuteFieldAttribute,
+    kAXModalAttribute,
+    kAXMonthFieldAttribute,
+    kAXNextContentsAttribute,
+    kAXNumberOfCharactersAttribute,
+    kAXOrderedByRowAttribute,
+    kAXOrientationAttribute,
+    kAXOverflowButtonAttribute,
+    kAXParentAttribute,
+    kAXPlaceholderValueAttribute,
+    kAXPositionAttribute,
+    kAXPreviousContentsAttribute,
+    kAXProxyAttribute,
+    kAXRTFForRangeParameterizedAttribute,
+    kAXRangeForIndexParameterizedAttribute,
+    kAXRangeForLineParameterizedAttribute,
+    kAXRangeForPositionParameterizedAttribute,
+    kAXRoleAttribute,
+    kAXRoleDescriptionAttribute,
+    kAXRowCountAttribute,
+    kAXRowHeaderUIElementsAttribute,
+    kAXRowIndexRangeAttribute,
+    kAXRowsAttribute,
+    kAXScreenPointForLayoutPointParameterizedAttribute,
+    kAXScreenSizeForLayoutSizeParameterizedAttribute,
+    kAXSearchButtonAttribute,
+    kAXSecondFieldAttribute,
+    kAXSelectedAttribute,
+    kAXSelectedCellsAttribute,
+    kAXSelectedChildrenAttribute,
+    kAXSelectedColumnsAttribute,
+    kAXSelectedRowsAttribute,
+    kAXSelectedTextAttribute,
+    kAXSelectedTextRangeAttribute,
+    kAXSelectedTextRangesAttribute,
+    kAXServesAsTitleForUIElementsAttribute,
+    kAXSharedCharacterRangeAttribute,
+    kAXSharedFocusElementsAttribute,
+    kAXSharedTextUIElementsAttribute,
+    kAXShownMenuUIElementAttribute,
+    kAXSizeAttribute,
+    kAXSortDirectionAttribute,
+    kAXSplittersAttribute,
+    kAXStringForRangeParameterizedAttribute,
+    kAXStyleRangeForIndexParameterizedAttribute,
+    kAXSubroleAttribute,
+    kAXTabsAttribute,
+    kAXTextAttribute,
+    kAXTitleAttribute,
+    kAXTitleUIElementAttribute,
+    kAXToolbarButtonAttribute,
+    kAXTopLevelUIElementAttribute,
+    kAXURLAttribute,
+    kAXUnitDescriptionAttribute,
+    kAXUnitsAttribute,
+    kAXValueAttribute,
+    kAXValueDescriptionAttribute,
+    kAXValueIncrementAttribute,
+    kAXValueWrapsAttribute,
+    kAXVerticalScrollBarAttribute,
+    kAXVerticalUnitDescriptionAttribute,
+    kAXVerticalUnitsAttribute,
+    kAXVisibleCellsAttribute,
+    kAXVisibleCharacterRangeAttribute,
+    kAXVisibleChildrenAttribute,
+    kAXVisibleColumnsAttribute,
+    kAXVisibleRowsAttribute,
+    kAXVisibleTextAttribute,
+    kAXWarningValueAttribute,
+    kAXWindowAttribute,
+    kAXWindowsAttribute,
+    kAXYearFieldAttribute,
+    kAXZoomButtonAttribute,
+};
+
+define_attributes![
+    /// The only values the element accepts.
+    ///
+    /// Applies to `AXSlider`.
+    (allowed_values, CFArray<CFType>, kAXAllowedValuesAttribute),
+    /// Whether alternate UI, such as hover controls, is shown.
+    (alternate_ui_visible, CFBoolean, kAXAlternateUIVisibleAttribute),
+    /// The AM/PM field.
+    ///
+    /// Applies to `AXTimeField`.
+    (ampm_field, AXUIElement, kAXAMPMFieldAttribute),
+    /// The button pressed by Escape.
+    ///
+    /// Applies to `AXWindow`, `AXSheet`.
+    (cancel_button, AXUIElement, kAXCancelButtonAttribute),
+    /// Elements contained by this one, in visual order.
+    (children, CFArray<AXUIElement>, kAXChildrenAttribute),
+    /// The clear button of a search field.
+    ///
+    /// Applies to `AXTextField`.
+    (clear_button, AXUIElement, kAXClearButtonAttribute),
+    /// The window's close button.
+    ///
+    /// Applies to `AXWindow`.
+    (close_button, AXUIElement, kAXCloseButtonAttribute),
+    /// Number of columns.
+    ///
+    /// Applies to `AXTable`, `AXOutline`, `AXBrowser`, `AXGrid`.
+    (column_count, CFNumber, kAXColumnCountAttribute),
+    /// The column header elements.
+    ///
+    /// Applies to `AXTable`, `AXGrid`.
+    (column_header_ui_elements, CFArray<AXUIElement>, kAXColumnHeaderUIElementsAttribute),
+    /// Columns the cell spans.
+    ///
+    /// Applies to `AXCell`.
+    (column_index_range, AXValue<CFRange>, kAXColumnIndexRangeAttribute),
+    /// The column header elements.
+    ///
+    /// Applies to `AXTable`, `AXOutline`, `AXBrowser`.
+    (column_titles, CFArray<AXUIElement>, kAXColumnTitlesAttribute),
+    /// All columns.
+    ///
+    /// Applies to `AXTable`, `AXOutline`, `AXBrowser`.
+    (columns, CFArray<AXUIElement>, kAXColumnsAttribute),
+    /// The content element, e.g. of a scroll area or tab group.
+    ///
+    /// Applies to `AXScrollArea`, `AXTabGroup`.
+    (contents, AXUIElement, kAXContentsAttribute),
+    /// Value at which the indicator shows a critical state.
+    ///
+    /// Applies to `AXLevelIndicator`.
+    (critical_value, CFType, kAXCriticalValueAttribute),
+    /// The day field.
+    ///
+    /// Applies to `AXDateField`.
+    (day_field, AXUIElement, kAXDayFieldAttribute),
+    /// The decrement button.
+    ///
+    /// Applies to `AXIncrementor`, `AXScrollBar`.
+    (decrement_button, AXUIElement, kAXDecrementButtonAttribute),
+    /// The button pressed by Return.
+    ///
+    /// Applies to `AXWindow`, `AXSheet`.
+    (default_button, AXUIElement, kAXDefaultButtonAttribute),
+    /// Describes the element's purpose when it has no title.
+    (description, CFString, kAXDescriptionAttribute),
+    /// The row this row is shown below.
+    ///
+    /// Applies to `AXRow`.
+    (disclosed_by_row, AXUIElement, kAXDisclosedByRowAttribute),
+    /// Rows shown below this row when it is expanded.
+    ///
+    /// Applies to `AXRow`.
+    (disclosed_rows, CFArray<AXUIElement>, kAXDisclosedRowsAttribute),
+    /// Whether the row's children are shown.
+    ///
+    /// Applies to `AXRow`.
+    (disclosing, CFBoolean, kAXDisclosingAttribute, set_disclosing),
+    /// Nesting depth of the row, starting at 0.
+    ///
+    /// Applies to `AXRow`.
+    (disclosure_level, CFNumber, kAXDisclosureLevelAttribute),
+    /// URL of the document the window shows, as a string.
+    ///
+    /// Applies to `AXWindow`.
+    (document, CFString, kAXDocumentAttribute),
+    /// Whether the element has unsaved changes.
+    (edited, CFBoolean, kAXEditedAttribute),
+    /// Whether the element is busy, e.g. loading content.
+    (element_busy, CFBoolean, kAXElementBusyAttribute),
+    /// Whether the element responds to user input.
+    (enabled, CFBoolean, kAXEnabledAttribute),
+    /// Whether the element is expanded.
+    ///
+    /// Applies to `AXComboBox`, `AXDisclosureTriangle`, `AXRow`.
+    (expanded, CFBoolean, kAXExpandedAttribute, set_expanded),
+    /// The menu bar extras owned by the application.
+    ///
+    /// Applies to `AXApplication`.
+    (extras_menu_bar, AXUIElement, kAXExtrasMenuBarAttribute),
+    /// Name of the file the element represents.
+    (filename, CFString, kAXFilenameAttribute),
+    /// Whether the element has keyboard focus.
+    (focused, CFBoolean, kAXFocusedAttribute, set_focused),
+    /// The application with keyboard focus.
+    ///
+    /// Applies to `AXSystemWide`.
+    (focused_application, AXUIElement, kAXFocusedApplicationAttribute),
+    /// The element with keyboard focus.
+    ///
+    /// Applies to `AXApplication`, `AXSystemWide`.
+    (focused_ui_element, AXUIElement, kAXFocusedUIElementAttribute),
+    /// The application's focused window.
+    ///
+    /// Applies to `AXApplication`.
+    (focused_window, AXUIElement, kAXFocusedWindowAttribute),
+    /// Position and size in screen coordinates.
+    (frame, AXValue<CGRect>, kAXFrameAttribute),
+    /// Whether the application is frontmost.
+    ///
+    /// Applies to `AXApplication`.
+    (frontmost, CFBoolean, kAXFrontmostAttribute, set_frontmost),
+    /// The window's full screen button.
+    ///
+    /// Applies to `AXWindow`.
+    (full_screen_button, AXUIElement, kAXFullScreenButtonAttribute),
+    /// The window's resize area.
+    ///
+    /// Applies to `AXWindow`.
+    (grow_area, AXUIElement, kAXGrowAreaAttribute),
+    /// Resize handles of a layout item.
+    ///
+    /// Applies to `AXLayoutItem`.
+    (handles, CFArray<AXUIElement>, kAXHandlesAttribute),
+    /// The header element of a column or table.
+    ///
+    /// Applies to `AXColumn`, `AXTable`, `AXOutline`.
+    (header, AXUIElement, kAXHeaderAttribute),
+    /// Help text, usually shown as a tooltip.
+    (help, CFString, kAXHelpAttribute),
+    /// Whether the application is hidden.
+    ///
+    /// Applies to `AXApplication`.
+    (hidden, CFBoolean, kAXHiddenAttribute, set_hidden),
+    /// The horizontal scroll bar.
+    ///
+    /// Applies to `AXScrollArea`.
+    (horizontal_scroll_bar, AXUIElement, kAXHorizontalScrollBarAttribute),
+    /// Localized description of the horizontal units.
+    ///
+    /// Applies to `AXLayoutArea`.
+    (horizontal_unit_description, CFString, kAXHorizontalUnitDescriptionAttribute),
+    /// Horizontal units of a layout area.
+    ///
+    /// Applies to `AXLayoutArea`.
+    (horizontal_units, CFString, kAXHorizontalUnitsAttribute),
+    /// The hour field.
+    ///
+    /// Applies to `AXTimeField`.
+    (hour_field, AXUIElement, kAXHourFieldAttribute),
+    /// Developer-assigned identifier, stable across launches.
+    (identifier, CFString, kAXIdentifierAttribute),
+    /// The increment button.
+    ///
+    /// Applies to `AXIncrementor`, `AXScrollBar`.
+    (increment_button, AXUIElement, kAXIncrementButtonAttribute),
+    /// The incrementor attached to the element.
+    ///
+    /// Applies to `AXTimeField`, `AXDateField`.
+    (incrementor, AXUIElement, kAXIncrementorAttribute),
+    /// Position of the row or column in its table.
+    ///
+    /// Applies to `AXRow`, `AXColumn`.
+    (index, CFNumber, kAXIndexAttribute),
+    /// Line number of the insertion point.
+    ///
+    /// Applies to `AXTextField`, `AXTextArea`.
+    (insertion_point_line_number, CFNumber, kAXInsertionPointLineNumberAttribute),
+    /// Whether the application a Dock item represents is running.
+    ///
+    /// Applies to `AXDockItem`.
+    (is_application_running, CFBoolean, kAXIsApplicationRunningAttribute),
+    /// Whether the text can be edited.
+    (is_editable, CFBoolean, kAXIsEditableAttribute),
+    /// Labels of a slider or level indicator.
+    ///
+    /// Applies to `AXSlider`, `AXLevelIndicator`.
+    (label_ui_elements, CFArray<AXUIElement>, kAXLabelUIElementsAttribute),
+    /// Value a label corresponds to.
+    (label_value, CFString, kAXLabelValueAttribute),
+    /// Elements related to this one, such as a source list and its detail view.
+    (linked_ui_elements, CFArray<AXUIElement>, kAXLinkedUIElementsAttribute, set_linked_ui_elements),
+    /// Whether this is the main window.
+    ///
+    /// Applies to `AXWindow`.
+    (main, CFBoolean, kAXMainAttribute, set_main),
+    /// The application's main window.
+    ///
+    /// Applies to `AXApplication`.
+    (main_window, AXUIElement, kAXMainWindowAttribute),
+    /// Kind of ruler marker, e.g. `AXLeftTabStopMarker`.
+    ///
+    /// Applies to `AXRulerMarker`.
+    (marker_type, CFString, kAXMarkerTypeAttribute),
+    /// Localized description of the marker type.
+    ///
+    /// Applies to `AXRulerMarker`.
+    (marker_type_description, CFString, kAXMarkerTypeDescriptionAttribute),
+    /// Markers of a ruler.
+    ///
+    /// Applies to `AXRuler`.
+    (marker_ui_elements, CFArray<AXUIElement>, kAXMarkerUIElementsAttribute),
+    /// The element the matte clips.
+    ///
+    /// Applies to `AXMatte`.
+    (matte_content_ui_element, AXUIElement, kAXMatteContentUIElementAttribute),
+    /// Area of the matte that is not obscured.
+    ///
+    /// Applies to `AXMatte`.
+    (matte_hole, AXValue<CGRect>, kAXMatteHoleAttribute),
+    /// Largest value the element accepts.
+    ///
+    /// Applies to `AXSlider`, `AXIncrementor`, `AXScrollBar`, `AXLevelIndicator`.
+    (max_value, CFType, kAXMaxValueAttribute),
+    /// The application's menu bar.
+    ///
+    /// Applies to `AXApplication`.
+    (menu_bar, AXUIElement, kAXMenuBarAttribute),
+    /// Character of the item's keyboard shortcut.
+    ///
+    /// Applies to `AXMenuItem`.
+    (menu_item_cmd_char, CFString, kAXMenuItemCmdCharAttribute),
+    /// Glyph code of the item's keyboard shortcut.
+    ///
+    /// Applies to `AXMenuItem`.
+    (menu_item_cmd_glyph, CFNumber, kAXMenuItemCmdGlyphAttribute),
+    /// Modifier flags of the item's keyboard shortcut.
+    ///
+    /// Applies to `AXMenuItem`.
+    (menu_item_cmd_modifiers, CFNumber, kAXMenuItemCmdModifiersAttribute),
+    /// Virtual key code of the item's keyboard shortcut.
+    ///
+    /// Applies to `AXMenuItem`.
+    (menu_item_cmd_virtual_key, CFNumber, kAXMenuItemCmdVirtualKeyAttribute),
+    /// The check mark or other mark shown next to the item.
+    ///
+    /// Applies to `AXMenuItem`.
+    (menu_item_mark_char, CFString, kAXMenuItemMarkCharAttribute),
+    /// The primary item of an alternate menu item.
+    ///
+    /// Applies to `AXMenuItem`.
+    (menu_item_primary_ui_element, AXUIElement, kAXMenuItemPrimaryUIElementAttribute),
+    /// Smallest value the element accepts.
+    ///
+    /// Applies to `AXSlider`, `AXIncrementor`, `AXScrollBar`, `AXLevelIndicator`.
+    (min_value, CFType, kAXMinValueAttribute),
+    /// The window's minimize button.
+    ///
+    /// Applies to `AXWindow`.
+    (minimize_button, AXUIElement, kAXMinimizeButtonAttribute),
+    /// Whether the window is minimized to the Dock.
+    ///
+    /// Applies to `AXWindow`.
+    (minimized, CFBoolean, kAXMinimizedAttribute, set_minimized),
+    /// The minute field.
+    ///
+    /// Applies to `AXTimeField`.
+    (minute_field, AXUIElement, kAXMinuteFieldAttribute),
+    /// Whether the window is modal.
+    ///
+    /// Applies to `AXWindow`.
+    (modal, CFBoolean, kAXModalAttribute),
+    /// The month field.
+    ///
+    /// Applies to `AXDateField`.
+    (month_field, AXUIElement, kAXMonthFieldAttribute),
+    /// Elements after the splitter.
+    ///
+    /// Applies to `AXSplitter`.
+    (next_contents, CFArray<AXUIElement>, kAXNextContentsAttribute),
+    /// Number of characters in the text.
+    ///
+    /// Applies to `AXTextField`, `AXTextArea`.
+    (number_of_characters, CFNumber, kAXNumberOfCharactersAttribute),
+    /// Whether the grid is laid out row by row.
+    ///
+    /// Applies to `AXGrid`.
+    (ordered_by_row, CFBoolean, kAXOrderedByRowAttribute),
+    /// `AXHorizontalOrientation` or `AXVerticalOrientation`.
+    ///
+    /// Applies to `AXScrollBar`, `AXSlider`, `AXSplitGroup`, `AXSplitter`, `AXRadioGroup`.
+    (orientation, CFString, kAXOrientationAttribute),
+    /// Button that shows items that don't fit.
+    ///
+    /// Applies to `AXToolbar`.
+    (overflow_button, AXUIElement, kAXOverflowButtonAttribute),
+    /// The element that contains this one.
+    (parent, AXUIElement, kAXParentAttribute),
+    /// Text shown while the field is empty.
+    ///
+    /// Applies to `AXTextField`, `AXTextArea`, `AXComboBox`.
+    (placeholder_value, CFString, kAXPlaceholderValueAttribute),
+    /// Top-left corner in screen coordinates.
+    (position, AXValue<CGPoint>, kAXPositionAttribute, set_position),
+    /// Elements before the splitter.
+    ///
+    /// Applies to `AXSplitter`.
+    (previous_contents, CFArray<AXUIElement>, kAXPreviousContentsAttribute),
+    /// The document proxy icon in the title bar.
+    ///
+    /// Applies to `AXWindow`.
+    (proxy, AXUIElement, kAXProxyAttribute),
+    /// The element's role, e.g. `AXButton`.
+    (role, CFString, kAXRoleAttribute),
+    /// Localized, human-readable description of the role.
+    (role_description, CFString, kAXRoleDescriptionAttribute),
+    /// Number of rows.
+    ///
+    /// Applies to `AXTable`, `AXOutline`, `AXBrowser`, `AXGrid`.
+    (row_count, CFNumber, kAXRowCountAttribute),
+    /// The row header elements.
+    ///
+    /// Applies to `AXTable`, `AXGrid`.
+    (row_header_ui_elements, CFArray<AXUIElement>, kAXRowHeaderUIElementsAttribute),
+    /// Rows the cell spans.
+    ///
+    /// Applies to `AXCell`.
+    (row_index_range, AXValue<CFRange>, kAXRowIndexRangeAttribute),
+    /// All rows.
+    ///
+    /// Applies to `AXTable`, `AXOutline`, `AXBrowser`.
+    (rows, CFArray<AXUIElement>, kAXRowsAttribute),
+    /// The search button of a search field.
+    ///
+    /// Applies to `AXTextField`.
+    (search_button, AXUIElement, kAXSearchButtonAttribute),
+    /// The second field.
+    ///
+    /// Applies to `AXTimeField`.
+    (second_field, AXUIElement, kAXSecondFieldAttribute),
+    /// Whether the element is selected.
+    ///
+    /// Applies to `AXRow`, `AXCell`, `AXMenuItem`.
+    (selected, CFBoolean, kAXSelectedAttribute, set_selected),
+    /// Selected cells.
+    ///
+    /// Applies to `AXTable`, `AXGrid`.
+    (selected_cells, CFArray<AXUIElement>, kAXSelectedCellsAttribute, set_selected_cells),
+    /// Children that are selected.
+    ///
+    /// Applies to `AXList`, `AXRadioGroup`, `AXTabGroup`, `AXGrid`.
+    (selected_children, CFArray<AXUIElement>, kAXSelectedChildrenAttribute, set_selected_children),
+    /// Selected columns.
+    ///
+    /// Applies to `AXTable`, `AXOutline`, `AXBrowser`.
+    (selected_columns, CFArray<AXUIElement>, kAXSelectedColumnsAttribute, set_selected_columns),
+    /// Selected rows.
+    ///
+    /// Applies to `AXTable`, `AXOutline`, `AXBrowser`.
+    (selected_rows, CFArray<AXUIElement>, kAXSelectedRowsAttribute, set_selected_rows),
+    /// The currently selected text.
+    ///
+    /// Applies to `AXTextField`, `AXTextArea`.
+    (selected_text, CFString, kAXSelectedTextAttribute, set_selected_text),
+    /// Range of the selected text.
+    ///
+    /// Applies to `AXTextField`, `AXTextArea`.
+    (selected_text_range, AXValue<CFRange>, kAXSelectedTextRangeAttribute, set_selected_text_range),
+    /// Ranges of all selected text, for discontiguous selections.
+    ///
+    /// Applies to `AXTextField`, `AXTextArea`.
+    (selected_text_ranges, CFArray<AXValue<CFRange>>, kAXSelectedTextRangesAttribute, set_selected_text_ranges),
+    /// Elements this static text is the title of.
+    ///
+    /// Applies to `AXStaticText`.
+    (serves_as_title_for_ui_elements, CFArray<AXUIElement>, kAXServesAsTitleForUIElementsAttribute),
+    /// Range of the shared text storage this element displays.
+    ///
+    /// Applies to `AXTextArea`.
+    (shared_character_range, AXValue<CFRange>, kAXSharedCharacterRangeAttribute),
+    /// Elements that share keyboard focus with this one.
+    (shared_focus_elements, CFArray<AXUIElement>, kAXSharedFocusElementsAttribute),
+    /// Text elements that share this element's text storage.
+    ///
+    /// Applies to `AXTextArea`.
+    (shared_text_ui_elements, CFArray<AXUIElement>, kAXSharedTextUIElementsAttribute),
+    /// The menu currently shown by the element.
+    (shown_menu_ui_element, AXUIElement, kAXShownMenuUIElementAttribute),
+    /// Width and height in points.
+    (size, AXValue<CGSize>, kAXSizeAttribute, set_size),
+    /// `AXAscendingSortDirection`, `AXDescendingSortDirection` or `AXUnknownSortDirection`.
+    ///
+    /// Applies to `AXColumn`.
+    (sort_direction, CFString, kAXSortDirectionAttribute),
+    /// The splitters of a split group.
+    ///
+    /// Applies to `AXSplitGroup`.
+    (splitters, CFArray<AXUIElement>, kAXSplittersAttribute),
+    /// A more specific role, e.g. `AXCloseButton`.
+    (subrole, CFString, kAXSubroleAttribute),
+    /// The tabs of a tab group.
+    ///
+    /// Applies to `AXTabGroup`.
+    (tabs, CFArray<AXUIElement>, kAXTabsAttribute),
+    /// The element's text.
+    (text, CFString, kAXTextAttribute),
+    /// The visible title or label of the element.
+    (title, CFString, kAXTitleAttribute),
+    /// The static text that serves as the element's title.
+    (title_ui_element, AXUIElement, kAXTitleUIElementAttribute),
+    /// The window's toolbar button.
+    ///
+    /// Applies to `AXWindow`.
+    (toolbar_button, AXUIElement, kAXToolbarButtonAttribute),
+    /// The window, sheet or drawer containing the element.
+    (top_level_ui_element, AXUIElement, kAXTopLevelUIElementAttribute),
+    /// Localized description of a ruler's units.
+    ///
+    /// Applies to `AXRuler`.
+    (unit_description, CFString, kAXUnitDescriptionAttribute),
+    /// Units of a ruler.
+    ///
+    /// Applies to `AXRuler`.
+    (units, CFString, kAXUnitsAttribute),
+    /// URL of the resource the element represents.
+    (url, CFURL, kAXURLAttribute),
+    /// The element's value. Its type depends on the role.
+    (value, CFType, kAXValueAttribute, set_value),
+    /// Human-readable description of the value.
+    (value_description, CFString, kAXValueDescriptionAttribute),
+    /// Amount the value changes by for one step.
+    ///
+    /// Applies to `AXSlider`, `AXIncrementor`.
+    (value_increment, CFType, kAXValueIncrementAttribute),
+    /// Whether the value wraps around at its limits.
+    ///
+    /// Applies to `AXIncrementor`.
+    (value_wraps, CFBoolean, kAXValueWrapsAttribute),
+    /// The vertical scroll bar.
+    ///
+    /// Applies to `AXScrollArea`.
+    (vertical_scroll_bar, AXUIElement, kAXVerticalScrollBarAttribute),
+    /// Localized description of the vertical units.
+    ///
+    /// Applies to `AXLayoutArea`.
+    (vertical_unit_description, CFString, kAXVerticalUnitDescriptionAttribute),
+    /// Vertical units of a layout area.
+    ///
+    /// Applies to `AXLayoutArea`.
+    (vertical_units, CFString, kAXVerticalUnitsAttribute),
+    /// Cells scrolled into view.
+    ///
+    /// Applies to `AXTable`, `AXGrid`.
+    (visible_cells, CFArray<AXUIElement>, kAXVisibleCellsAttribute),
+    /// Range of the characters that are scrolled into view.
+    ///
+    /// Applies to `AXTextField`, `AXTextArea`.
+    (visible_character_range, AXValue<CFRange>, kAXVisibleCharacterRangeAttribute, set_visible_character_range),
+    /// Children that are currently visible.
+    ///
+    /// Applies to `AXList`, `AXGrid`, `AXTabGroup`.
+    (visible_children, CFArray<AXUIElement>, kAXVisibleChildrenAttribute),
+    /// Columns scrolled into view.
+    ///
+    /// Applies to `AXTable`, `AXOutline`, `AXBrowser`.
+    (visible_columns, CFArray<AXUIElement>, kAXVisibleColumnsAttribute),
+    /// Rows scrolled into view.
+    ///
+    /// Applies to `AXTable`, `AXOutline`, `AXBrowser`.
+    (visible_rows, CFArray<AXUIElement>, kAXVisibleRowsAttribute),
+    /// The part of the text that is visible.
+    (visible_text, CFString, kAXVisibleTextAttribute),
+    /// Value at which the indicator shows a warning.
+    ///
+    /// Applies to `AXLevelIndicator`.
+    (warning_value, CFType, kAXWarningValueAttribute),
+    /// The window containing the element.
+    (window, AXUIElement, kAXWindowAttribute),
+    /// The application's windows.
+    ///
+    /// Applies to `AXApplication`.
+    (windows, CFArray<AXUIElement>, kAXWindowsAttribute),
+    /// The year field.
+    ///
+    /// Applies to `AXDateField`.
+    (year_field, AXUIElement, kAXYearFieldAttribute),
+    /// The window's zoom button.
+    ///
+    /// Applies to `AXWindow`.
+    (zoom_button, AXUIElement, kAXZoomButtonAttribute),
+];
+
+define_parameterized_attributes![
+    /// Attributed string for a character range.
+    ///
+    /// Applies to `AXTextField`, `AXTextArea`.
+    (attributed_string_for_range, CFAttributedString, AXValue<CFRange>, kAXAttributedStringForRangeParameterizedAttribute),
+    /// Screen bounds of a character range.
+    ///
+    /// Applies to `AXTextField`, `AXTextArea`.
+    (bounds_for_range, AXValue<CGRect>, AXValue<CFRange>, kAXBoundsForRangeParameterizedAttribute),
+    /// Cell at a column and row index.
+    ///
+    /// Applies to `AXTable`, `AXGrid`.
+    (cell_for_column_and_row, AXUIElement, CFArray<CFNumber>, kAXCellForColumnAndRowParameterizedAttribute),
+    /// Converts a screen point to layout coordinates.
+    ///
+    /// Applies to `AXLayoutArea`.
+    (layout_point_for_screen_point, AXValue<CGPoint>, AXValue<CGPoint>, kAXLayoutPointForScreenPointParameterizedAttribute),
+    /// Converts a screen size to layout units.
+    ///
+    /// Applies to `AXLayoutArea`.
+    (layout_size_for_screen_size, AXValue<CGSize>, AXValue<CGSize>, kAXLayoutSizeForScreenSizeParameterizedAttribute),
+    /// Line number containing the character index.
+    ///
+    /// Applies to `AXTextField`, `AXTextArea`.
+    (line_for_index, CFNumber, CFNumber, kAXLineForIndexParameterizedAttribute),
+    /// Range of the composed character at an index.
+    ///
+    /// Applies to `AXTextField`, `AXTextArea`.
+    (range_for_index, AXValue<CFRange>, CFNumber, kAXRangeForIndexParameterizedAttribute),
+    /// Character range of a line.
+    ///
+    /// Applies to `AXTextField`, `AXTextArea`.
+    (range_for_line, AXValue<CFRange>, CFNumber, kAXRangeForLineParameterizedAttribute),
+    /// Range of the character at a screen point.
+    ///
+    /// Applies to `AXTextField`, `AXTextArea`.
+    (range_for_position, AXValue<CFRange>, AXValue<CGPoint>, kAXRangeForPositionParameterizedAttribute),
+    /// RTF data for a character range.
+    ///
+    /// Applies to `AXTextField`, `AXTextArea`.
+    (rtf_for_range, CFData, AXValue<CFRange>, kAXRTFForRangeParameterizedAttribute),
+    /// Converts a layout point to screen coordinates.
+    ///
+    /// Applies to `AXLayoutArea`.
+    (screen_point_for_layout_point, AXValue<CGPoint>, AXValue<CGPoint>, kAXScreenPointForLayoutPointParameterizedAttribute),
+    /// Converts a layout size to screen units.
+    ///
+    /// Applies to `AXLayoutArea`.
+    (screen_size_for_layout_size, AXValue<CGSize>, AXValue<CGSize>, kAXScreenSizeForLayoutSizeParameterizedAttribute),
+    /// Text in a character range.
+    ///
+    /// Applies to `AXTextField`, `AXTextArea`.
+    (string_for_range, CFString, AXValue<CFRange>, kAXStringForRangeParameterizedAttribute),
+    /// Range of the style run containing an index.
+    ///
+    /// Applies to `AXTextField`, `AXTextArea`.
+    (style_range_for_index, AXValue<CFRange>, CFNumber, kAXStyleRangeForIndexParameterizedAttribute),
+];
@@ -0,0 +1,296 @@
+// Generated by `cargo run -p codegen` from spec/accessibility.toml. Do not edit.
+
+use accessibility_sys::{
+    kAXApplicationDockItemSubrole,
+    kAXApplicationRole,
+    kAXBrowserRole,
+    kAXBusyIndicatorRole,
+    kAXButtonRole,
+    kAXCellRole,
+    kAXCheckBoxRole,
+    kAXCloseButtonSubrole,
+    kAXColorWellRole,
+    kAXColumnRole,
+    kAXComboBoxRole,
+    kAXContentListSubrole,
+    kAXDateFieldRole,
+    kAXDecorativeSubrole,
+    kAXDecrementArrowSubrole,
+    kAXDecrementPageSubrole,
+    kAXDefinitionListSubrole,
+    kAXDescriptionListSubrole,
+    kAXDialogSubrole,
+    kAXDisclosureTriangleRole,
+    kAXDockExtraDockItemSubrole,
+    kAXDockItemRole,
+    kAXDocumentDockItemSubrole,
+    kAXDrawerRole,
+    kAXFloatingWindowSubrole,
+    kAXFolderDockItemSubrole,
+    kAXFullScreenButtonSubrole,
+    kAXGridRole,
+    kAXGroupRole,
+    kAXGrowAreaRole,
+    kAXHandleRole,
+    kAXHelpTagRole,
+    kAXImageRole,
+    kAXIncrementArrowSubrole,
+    kAXIncrementPageSubrole,
+    kAXIncrementorRole,
+    kAXLayoutAreaRole,
+    kAXLayoutItemRole,
+    kAXLevelIndicatorRole,
+    kAXListRole,
+    kAXMatteRole,
+    kAXMenuBarItemRole,
+    kAXMenuBarRole,
+    kAXMenuButtonRole,
+    kAXMenuItemRole,
+    kAXMenuRole,
+    kAXMinimizeButtonSubrole,
+    kAXMinimizedWindowDockItemSubrole,
+    kAXOutlineRole,
+    kAXOutlineRowSubrole,
+    kAXPopUpButtonRole,
+    kAXPopoverRole,
+    kAXProcessSwitcherListSubrole,
+    kAXProgressIndicatorRole,
+    kAXRadioButtonRole,
+    kAXRadioGroupRole,
+    kAXRatingIndicatorSubrole,
+    kAXRelevanceIndicatorRole,
+    kAXRowRole,
+    kAXRulerMarkerRole,
+    kAXRulerRole,
+    kAXScrollAreaRole,
+    kAXScrollBarRole,
+    kAXSearchFieldSubrole,
+    kAXSecureTextFieldSubrole,
+    kAXSeparatorDockItemSubrole,
+    kAXSheetRole,
+    kAXSliderRole,
+    kAXSortButtonSubrole,
+    kAXSplitGroupRole,
+    kAXSplitterRole,
+    kAXStandardWindowSubrole,
+    kAXStaticTextRole,
+    kAXSwitchSubrole,
+    kAXSystemDialogSubrole,
+    kAXSystemFloatingWindowSubrole,
+    kAXSystemWideRole,
+    kAXTabGroupRole,
+    kAXTableRole,
+    kAXTableRowSubrole,
+    kAXTextAreaRole,
+    kAXTextFieldRole,
+    kAXTimeFieldRole,
+    kAXTimelineSubrole,
+    kAXToggleSubrole,
+    kAXToolbarButtonSubrole,
+    kAXToolbarRole,
+    kAXTrashDockItemSubrole,
+    kAXURLDockItemSubrole,
+    kAXUnknownRole,
+    kAXUnknownSubrole,
+    kAXValueIndicatorRole,
+    kAXWindowRole,
+    kAXZoomButtonSubrole,
+};
+
+define_roles![
+    /// An element's `AXRole`.
+    Role,
+    /// `AXApplication`
+    (Application, kAXApplicationRole),
+    /// `AXSystemWide`
+    (SystemWide, kAXSystemWideRole),
+    /// `AXWindow`
+    (Window, kAXWindowRole),
+    /// `AXSheet`
+    (Sheet, kAXSheetRole),
+    /// `AXDrawer`
+    (Drawer, kAXDrawerRole),
+    /// `AXGrowArea`
+    (GrowArea, kAXGrowAreaRole),
+    /// `AXImage`
+    (Image, kAXImageRole),
+    /// `AXUnknown`
+    (UnknownRole, kAXUnknownRole),
+    /// `AXButton`
+    (Button, kAXButtonRole),
+    /// `AXRadioButton`
+    (RadioButton, kAXRadioButtonRole),
+    /// `AXCheckBox`
+    (CheckBox, kAXCheckBoxRole),
+    /// `AXPopUpButton`
+    (PopUpButton, kAXPopUpButtonRole),
+    /// `AXMenuButton`
+    (MenuButton, kAXMenuButtonRole),
+    /// `AXTabGroup`
+    (TabGroup, kAXTabGroupRole),
+    /// `AXTable`
+    (Table, kAXTableRole),
+    /// `AXColumn`
+    (Column, kAXColumnRole),
+    /// `AXRow`
+    (Row, kAXRowRole),
+    /// `AXOutline`
+    (Outline, kAXOutlineRole),
+    /// `AXBrowser`
+    (Browser, kAXBrowserRole),
+    /// `AXScrollArea`
+    (ScrollArea, kAXScrollAreaRole),
+    /// `AXScrollBar`
+    (ScrollBar, kAXScrollBarRole),
+    /// `AXRadioGroup`
+    (RadioGroup, kAXRadioGroupRole),
+    /// `AXList`
+    (List, kAXListRole),
+    /// `AXGroup`
+    (Group, kAXGroupRole),
+    /// `AXValueIndicator`
+    (ValueIndicator, kAXValueIndicatorRole),
+    /// `AXComboBox`
+    (ComboBox, kAXComboBoxRole),
+    /// `AXSlider`
+    (Slider, kAXSliderRole),
+    /// `AXIncrementor`
+    (Incrementor, kAXIncrementorRole),
+    /// `AXBusyIndicator`
+    (BusyIndicator, kAXBusyIndicatorRole),
+    /// `AXProgressIndicator`
+    (ProgressIndicator, kAXProgressIndicatorRole),
+    /// `AXRelevanceIndicator`
+    (RelevanceIndicator, kAXRelevanceIndicatorRole),
+    /// `AXToolbar`
+    (Toolbar, kAXToolbarRole),
+    /// `AXDisclosureTriangle`
+    (DisclosureTriangle, kAXDisclosureTriangleRole),
+    /// `AXTextField`
+    (TextField, kAXTextFieldRole),
+    /// `AXTextArea`
+    (TextArea, kAXTextAreaRole),
+    /// `AXStaticText`
+    (StaticText, kAXStaticTextRole),
+    /// `AXMenuBar`
+    (MenuBar, kAXMenuBarRole),
+    /// `AXMenuBarItem`
+    (MenuBarItem, kAXMenuBarItemRole),
+    /// `AXMenu`
+    (Menu, kAXMenuRole),
+    /// `AXMenuItem`
+    (MenuItem, kAXMenuItemRole),
+    /// `AXSplitGroup`
+    (SplitGroup, kAXSplitGroupRole),
+    /// `AXSplitter`
+    (Splitter, kAXSplitterRole),
+    /// `AXColorWell`
+    (ColorWell, kAXColorWellRole),
+    /// `AXTimeField`
+    (TimeField, kAXTimeFieldRole),
+    /// `AXDateField`
+    (DateField, kAXDateFieldRole),
+    /// `AXHelpTag`
+    (HelpTag, kAXHelpTagRole),
+    /// `AXMatte`
+    (Matte, kAXMatteRole),
+    /// `AXDockItem`
+    (DockItem, kAXDockItemRole),
+    /// `AXRuler`
+    (Ruler, kAXRulerRole),
+    /// `AXRulerMarker`
+    (RulerMarker, kAXRulerMarkerRole),
+    /// `AXGrid`
+    (Grid, kAXGridRole),
+    /// `AXLevelIndicator`
+    (LevelIndicator, kAXLevelIndicatorRole),
+    /// `AXCell`
+    (Cell, kAXCellRole),
+    /// `AXLayoutArea`
+    (LayoutArea, kAXLayoutAreaRole),
+    /// `AXLayoutItem`
+    (LayoutItem, kAXLayoutItemRole),
+    /// `AXHandle`
+    (Handle, kAXHandleRole),
+    /// `AXPopover`
+    (Popover, kAXPopoverRole),
+];
+
+define_roles![
+    /// An element's `AXSubrole`.
+    Subrole,
+    /// `AXCloseButton`
+    (CloseButton, kAXCloseButtonSubrole),
+    /// `AXMinimizeButton`
+    (MinimizeButton, kAXMinimizeButtonSubrole),
+    /// `AXZoomButton`
+    (ZoomButton, kAXZoomButtonSubrole),
+    /// `AXToolbarButton`
+    (ToolbarButton, kAXToolbarButtonSubrole),
+    /// `AXFullScreenButton`
+    (FullScreenButton, kAXFullScreenButtonSubrole),
+    /// `AXSecureTextField`
+    (SecureTextField, kAXSecureTextFieldSubrole),
+    /// `AXTableRow`
+    (TableRow, kAXTableRowSubrole),
+    /// `AXOutlineRow`
+    (OutlineRow, kAXOutlineRowSubrole),
+    /// `AXUnknown`
+    (UnknownSubrole, kAXUnknownSubrole),
+    /// `AXStandardWindow`
+    (StandardWindow, kAXStandardWindowSubrole),
+    /// `AXDialog`
+    (Dialog, kAXDialogSubrole),
+    /// `AXSystemDialog`
+    (SystemDialog, kAXSystemDialogSubrole),
+    /// `AXFloatingWindow`
+    (FloatingWindow, kAXFloatingWindowSubrole),
+    /// `AXSystemFloatingWindow`
+    (SystemFloatingWindow, kAXSystemFloatingWindowSubrole),
+    /// `AXDecorative`
+    (Decorative, kAXDecorativeSubrole),
+    /// `AXIncrementArrow`
+    (IncrementArrow, kAXIncrementArrowSubrole),
+    /// `AXDecrementArrow`
+    (DecrementArrow, kAXDecrementArrowSubrole),
+    /// `AXIncrementPage`
+    (IncrementPage, kAXIncrementPageSubrole),
+    /// `AXDecrementPage`
+    (DecrementPage, kAXDecrementPageSubrole),
+    /// `AXSortButton`
+    (SortButton, kAXSortButtonSubrole),
+    /// `AXSearchField`
+    (SearchField, kAXSearchFieldSubrole),
+    /// `AXTimeline`
+    (Timeline, kAXTimelineSubrole),
+    /// `AXRatingIndicator`
+    (RatingIndicator, kAXRatingIndicatorSubrole),
+    /// `AXContentList`
+    (ContentList, kAXContentListSubrole),
+    /// `AXDefinitionList`
+    (DefinitionList, kAXDefinitionListSubrole),
+    /// `AXDescriptionList`
+    (DescriptionList, kAXDescriptionListSubrole),
+    /// `AXToggle`
+    (Toggle, kAXToggleSubrole),
+    /// `AXSwitch`
+    (Switch, kAXSwitchSubrole),
+    /// `AXApplicationDockItem`
+    (ApplicationDockItem, kAXApplicationDockItemSubrole),
+    /// `AXDocumentDockItem`
+    (DocumentDockItem, kAXDocumentDockItemSubrole),
+    /// `AXFolderDockItem`
+    (FolderDockItem, kAXFolderDockItemSubrole),
+    /// `AXMinimizedWindowDockItem`
+    (MinimizedWindowDockItem, kAXMinimizedWindowDockItemSubrole),
+    /// `AXURLDockItem`
+    (URLDockItem, kAXURLDockItemSubrole),
+    /// `AXDockExtraDockItem`
+    (DockExtraDockItem, kAXDockExtraDockItemSubrole),
+    /// `AXTrashDockItem`
+    (TrashDockItem, kAXTrashDockItemSubrole),
+    /// `AXSeparatorDockItem`
+    (SeparatorDockItem, kAXSeparatorDockItemSubrole),
+    /// `AXProcessSwitcherList`
+    (ProcessSwitcherList, kAXProcessSwitcherListSubrole),
+];
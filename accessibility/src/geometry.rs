@@ -0,0 +1,434 @@
+use std::cell::RefCell;
+
+use crate::{
+    AXUIElement, AXUIElementAttributes, Error, Frame, Point, SnapshotNode, TreeVisitor, TreeWalker,
+    TreeWalkerFlow,
+};
+
+impl Frame {
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn max_x(&self) -> f64 {
+        self.x + self.width
+    }
+
+    pub fn max_y(&self) -> f64 {
+        self.y + self.height
+    }
+
+    pub fn center(&self) -> Point {
+        Point {
+            x: self.x + self.width / 2.0,
+            y: self.y + self.height / 2.0,
+        }
+    }
+
+    pub fn area(&self) -> f64 {
+        self.width.max(0.0) * self.height.max(0.0)
+    }
+
+    /// True when the frame covers no area.
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0.0 || self.height <= 0.0
+    }
+
+    /// Whether `point` lies inside the frame. The left and top edges are
+    /// inside, the right and bottom edges aren't, so adjacent frames never
+    /// both contain a point.
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= self.x && point.x < self.max_x() && point.y >= self.y && point.y < self.max_y()
+    }
+
+    pub fn contains_frame(&self, other: &Frame) -> bool {
+        other.x >= self.x
+            && other.y >= self.y
+            && other.max_x() <= self.max_x()
+            && other.max_y() <= self.max_y()
+    }
+
+    /// The area covered by both frames, or `None` if they only touch or don't
+    /// meet at all.
+    pub fn intersection(&self, other: &Frame) -> Option<Frame> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let frame = Frame::new(
+            x,
+            y,
+            self.max_x().min(other.max_x()) - x,
+            self.max_y().min(other.max_y()) - y,
+        );
+
+        if frame.is_empty() {
+            None
+        } else {
+            Some(frame)
+        }
+    }
+
+    pub fn overlaps(&self, other: &Frame) -> bool {
+        self.intersection(other).is_some()
+    }
+}
+
+/// The part of the screen an element's ancestors leave visible.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum Clip {
+    Unbounded,
+    Rect(Frame),
+    Hidden,
+}
+
+impl Clip {
+    /// Clips `frame`, returning its visible part and the clip for its
+    /// children. Elements without a frame, like applications, don't clip
+    /// their children.
+    pub(crate) fn apply(self, frame: Option<Frame>) -> (Option<Frame>, Clip) {
+        match (self, frame) {
+            (Clip::Hidden, _) => (None, Clip::Hidden),
+            (clip, None) => (None, clip),
+            (Clip::Unbounded, Some(frame)) if frame.is_empty() => (None, Clip::Hidden),
+            (Clip::Unbounded, Some(frame)) => (Some(frame), Clip::Rect(frame)),
+            (Clip::Rect(clip), Some(frame)) => match frame.intersection(&clip) {
+                Some(visible) => (Some(visible), Clip::Rect(visible)),
+                None => (None, Clip::Hidden),
+            },
+        }
+    }
+}
+
+impl SnapshotNode {
+    /// Visits this node and its descendants in depth-first pre-order along
+    /// with their visible frames: each frame clipped against the frames of
+    /// every ancestor. The visible frame is `None` for nodes without a frame
+    /// and for nodes clipped out of view.
+    pub fn walk_visible<'a>(&'a self, f: &mut impl FnMut(&'a SnapshotNode, Option<Frame>)) {
+        self.walk_visible_one(f, Clip::Unbounded)
+    }
+
+    fn walk_visible_one<'a>(
+        &'a self,
+        f: &mut impl FnMut(&'a SnapshotNode, Option<Frame>),
+        clip: Clip,
+    ) {
+        let (visible, clip) = clip.apply(self.frame);
+
+        f(self, visible);
+
+        for child in &self.children {
+            child.walk_visible_one(f, clip);
+        }
+    }
+
+    /// The visible frame of every node that has one.
+    pub fn visible_frames(&self) -> Vec<(&SnapshotNode, Frame)> {
+        let mut frames = Vec::new();
+
+        self.walk_visible(&mut |node, visible| {
+            if let Some(visible) = visible {
+                frames.push((node, visible));
+            }
+        });
+        frames
+    }
+
+    /// The deepest node whose visible frame contains `point`. Earlier
+    /// siblings are treated as being in front of later ones, which matches
+    /// the order applications report their windows in.
+    ///
+    /// This is the offline counterpart of
+    /// [`AXUIElementGeometry::element_at`].
+    pub fn element_at(&self, point: Point) -> Option<&SnapshotNode> {
+        self.element_at_one(point, Clip::Unbounded)
+    }
+
+    fn element_at_one(&self, point: Point, clip: Clip) -> Option<&SnapshotNode> {
+        let (visible, clip) = clip.apply(self.frame);
+
+        match visible {
+            Some(visible) if !visible.contains(point) => return None,
+            None if clip == Clip::Hidden => return None,
+            _ => {}
+        }
+
+        self.children
+            .iter()
+            .find_map(|child| child.element_at_one(point, clip))
+            .or_else(|| visible.map(|_| self))
+    }
+
+    /// Every node whose visible frame overlaps `rect`, in pre-order.
+    pub fn elements_in_rect(&self, rect: &Frame) -> Vec<&SnapshotNode> {
+        let mut found = Vec::new();
+
+        self.walk_visible(&mut |node, visible| {
+            if visible.is_some_and(|v| v.overlaps(rect)) {
+                found.push(node);
+            }
+        });
+        found
+    }
+
+    /// Pairs of siblings whose visible frames overlap, which usually means
+    /// one of them is obscuring the other.
+    pub fn overlapping_siblings(&self) -> Vec<(&SnapshotNode, &SnapshotNode)> {
+        let mut pairs = Vec::new();
+
+        self.overlapping_siblings_one(Clip::Unbounded, &mut pairs);
+        pairs
+    }
+
+    fn overlapping_siblings_one<'a>(
+        &'a self,
+        clip: Clip,
+        pairs: &mut Vec<(&'a SnapshotNode, &'a SnapshotNode)>,
+    ) {
+        let (_, clip) = clip.apply(self.frame);
+        let children: Vec<_> = self
+            .children
+            .iter()
+            .map(|child| (child, clip.apply(child.frame).0))
+            .collect();
+
+        for (i, (a, a_frame)) in children.iter().enumerate() {
+            for (b, b_frame) in &children[i + 1..] {
+                if let (Some(a_frame), Some(b_frame)) = (a_frame, b_frame) {
+                    if a_frame.overlaps(b_frame) {
+                        pairs.push((*a, *b));
+                    }
+                }
+            }
+        }
+
+        for child in &self.children {
+            child.overlapping_siblings_one(clip, pairs);
+        }
+    }
+}
+
+pub trait AXUIElementGeometry {
+    /// The frame clipped against the frames of every ancestor, or `None` when
+    /// the element is scrolled or clipped out of view.
+    fn visible_frame(&self) -> Result<Option<Frame>, Error>;
+    fn center(&self) -> Result<Point, Error>;
+    /// The topmost element at `point` in screen coordinates. Call this on
+    /// [`AXUIElement::system_wide`] to search every application.
+    fn element_at(&self, point: Point) -> Result<AXUIElement, Error>;
+    /// Every element below this one whose visible frame overlaps `rect`.
+    fn elements_in_rect(&self, rect: &Frame) -> Vec<AXUIElement>;
+}
+
+impl AXUIElementGeometry for AXUIElement {
+    fn visible_frame(&self) -> Result<Option<Frame>, Error> {
+        let frame = Frame::from(self.frame()?);
+        let mut clip = Clip::Unbounded;
+        let path = self.ancestry();
+
+        for ancestor in &path[..path.len() - 1] {
+            clip = clip.apply(ancestor.frame().ok().map(Frame::from)).1;
+        }
+
+        Ok(clip.apply(Some(frame)).0)
+    }
+
+    fn center(&self) -> Result<Point, Error> {
+        Ok(Frame::from(self.frame()?).center())
+    }
+
+    fn element_at(&self, point: Point) -> Result<AXUIElement, Error> {
+        self.element_at_position(point.x as f32, point.y as f32)
+    }
+
+    fn elements_in_rect(&self, rect: &Frame) -> Vec<AXUIElement> {
+        let collector = RectCollector {
+            rect: *rect,
+            clips: RefCell::new(Vec::new()),
+            found: RefCell::new(Vec::new()),
+        };
+
+        TreeWalker::new().walk(self, &collector);
+        collector.found.into_inner()
+    }
+}
+
+struct RectCollector {
+    rect: Frame,
+    clips: RefCell<Vec<Clip>>,
+    found: RefCell<Vec<AXUIElement>>,
+}
+
+impl TreeVisitor for RectCollector {
+    fn enter_element(&self, element: &AXUIElement) -> TreeWalkerFlow {
+        let mut clips = self.clips.borrow_mut();
+        let clip = clips.last().copied().unwrap_or(Clip::Unbounded);
+        let (visible, clip) = clip.apply(element.frame().ok().map(Frame::from));
+
+        clips.push(clip);
+
+        if visible.is_some_and(|v| v.overlaps(&self.rect)) {
+            self.found.borrow_mut().push(element.clone());
+        }
+
+        // Children are clipped to this element, so they can't reach the
+        // rectangle if it doesn't.
+        match (visible, clip) {
+            (_, Clip::Hidden) => TreeWalkerFlow::SkipSubtree,
+            (Some(v), _) if !v.overlaps(&self.rect) => TreeWalkerFlow::SkipSubtree,
+            _ => TreeWalkerFlow::Continue,
+        }
+    }
+
+    fn exit_element(&self, _element: &AXUIElement) {
+        self.clips.borrow_mut().pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(role: &str, frame: Option<Frame>, children: Vec<SnapshotNode>) -> SnapshotNode {
+        SnapshotNode {
+            role: role.to_owned(),
+            frame,
+            children,
+            ..Default::default()
+        }
+    }
+
+    /// An application holding a 100x100 window with two overlapping
+    /// children. The group clips one of its buttons and hides the other.
+    fn tree() -> SnapshotNode {
+        let group = node(
+            "AXGroup",
+            Some(Frame::new(20.0, 20.0, 50.0, 50.0)),
+            vec![
+                node(
+                    "AXCheckBox",
+                    Some(Frame::new(60.0, 60.0, 30.0, 30.0)),
+                    vec![],
+                ),
+                node(
+                    "AXRadioButton",
+                    Some(Frame::new(200.0, 200.0, 10.0, 10.0)),
+                    vec![],
+                ),
+            ],
+        );
+        let window = node(
+            "AXWindow",
+            Some(Frame::new(0.0, 0.0, 100.0, 100.0)),
+            vec![
+                node("AXButton", Some(Frame::new(10.0, 10.0, 30.0, 30.0)), vec![]),
+                group,
+                node("AXStaticText", None, vec![]),
+            ],
+        );
+
+        node("AXApplication", None, vec![window])
+    }
+
+    fn roles(nodes: &[&SnapshotNode]) -> Vec<String> {
+        nodes.iter().map(|node| node.role.clone()).collect()
+    }
+
+    #[test]
+    fn clip_apply() {
+        let frame = Frame::new(0.0, 0.0, 10.0, 10.0);
+        let clip = Clip::Rect(frame);
+
+        assert_eq!(Clip::Unbounded.apply(None), (None, Clip::Unbounded));
+        assert_eq!(clip.apply(None), (None, clip));
+        assert_eq!(Clip::Unbounded.apply(Some(frame)), (Some(frame), clip));
+        assert_eq!(
+            Clip::Unbounded.apply(Some(Frame::new(0.0, 0.0, 0.0, 10.0))),
+            (None, Clip::Hidden)
+        );
+        assert_eq!(
+            clip.apply(Some(Frame::new(5.0, 5.0, 10.0, 10.0))),
+            (
+                Some(Frame::new(5.0, 5.0, 5.0, 5.0)),
+                Clip::Rect(Frame::new(5.0, 5.0, 5.0, 5.0))
+            )
+        );
+        assert_eq!(
+            clip.apply(Some(Frame::new(10.0, 0.0, 10.0, 10.0))),
+            (None, Clip::Hidden)
+        );
+        assert_eq!(Clip::Hidden.apply(Some(frame)), (None, Clip::Hidden));
+    }
+
+    #[test]
+    fn visible_frames() {
+        let tree = tree();
+        let frames: Vec<_> = tree
+            .visible_frames()
+            .into_iter()
+            .map(|(node, frame)| (node.role.as_str(), frame))
+            .collect();
+
+        assert_eq!(
+            frames,
+            vec![
+                ("AXWindow", Frame::new(0.0, 0.0, 100.0, 100.0)),
+                ("AXButton", Frame::new(10.0, 10.0, 30.0, 30.0)),
+                ("AXGroup", Frame::new(20.0, 20.0, 50.0, 50.0)),
+                ("AXCheckBox", Frame::new(60.0, 60.0, 10.0, 10.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn element_at() {
+        let tree = tree();
+        let at = |x, y| {
+            tree.element_at(Point { x, y })
+                .map(|node| node.role.as_str())
+        };
+
+        assert_eq!(at(15.0, 15.0), Some("AXButton"));
+        // The button comes before the group, so it's in front.
+        assert_eq!(at(25.0, 25.0), Some("AXButton"));
+        assert_eq!(at(50.0, 50.0), Some("AXGroup"));
+        assert_eq!(at(65.0, 65.0), Some("AXCheckBox"));
+        // Inside the check box's frame but outside the group clipping it.
+        assert_eq!(at(80.0, 80.0), Some("AXWindow"));
+        assert_eq!(at(205.0, 205.0), None);
+        assert_eq!(at(-1.0, 50.0), None);
+    }
+
+    #[test]
+    fn elements_in_rect() {
+        let tree = tree();
+
+        assert_eq!(
+            roles(&tree.elements_in_rect(&Frame::new(55.0, 55.0, 10.0, 10.0))),
+            ["AXWindow", "AXGroup", "AXCheckBox"]
+        );
+        assert_eq!(
+            roles(&tree.elements_in_rect(&Frame::new(80.0, 80.0, 200.0, 200.0))),
+            ["AXWindow"]
+        );
+        assert!(tree
+            .elements_in_rect(&Frame::new(300.0, 300.0, 10.0, 10.0))
+            .is_empty());
+    }
+
+    #[test]
+    fn overlapping_siblings() {
+        let tree = tree();
+        let pairs: Vec<_> = tree
+            .overlapping_siblings()
+            .into_iter()
+            .map(|(a, b)| (a.role.as_str(), b.role.as_str()))
+            .collect();
+
+        assert_eq!(pairs, [("AXButton", "AXGroup")]);
+    }
+}
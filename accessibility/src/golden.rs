@@ -0,0 +1,440 @@
+use std::{
+    collections::BTreeSet,
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use accessibility_sys::{
+    kAXFocusedAttribute, kAXFocusedUIElementAttribute, kAXFocusedWindowAttribute,
+    kAXInsertionPointLineNumberAttribute, kAXPositionAttribute, kAXSelectedTextRangeAttribute,
+    kAXSelectedTextRangesAttribute, kAXSizeAttribute, kAXVisibleCharacterRangeAttribute,
+};
+
+use crate::{AXUIElement, AttributeFilter, Frame, SnapshotNode, TreeDiff};
+
+/// Environment variable that, when set to `1`, makes golden assertions write
+/// the actual snapshot over the golden file instead of failing.
+pub const UPDATE_ENV: &str = "ACCESSIBILITY_UPDATE_SNAPSHOTS";
+
+/// Controls which parts of a snapshot are considered stable enough to compare.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NormalizeConfig {
+    /// Attributes removed from every node, including the ones stored in
+    /// their own fields, like `AXTitle` and `AXValue`.
+    pub exclude: BTreeSet<String>,
+    /// Frames are rounded to a multiple of this many points. `None` removes
+    /// frames altogether.
+    pub frame_precision: Option<f64>,
+}
+
+impl Default for NormalizeConfig {
+    /// Drops frames and the attributes that follow focus, selection and
+    /// scrolling.
+    fn default() -> Self {
+        let exclude = [
+            kAXFocusedAttribute,
+            kAXFocusedUIElementAttribute,
+            kAXFocusedWindowAttribute,
+            kAXInsertionPointLineNumberAttribute,
+            kAXPositionAttribute,
+            kAXSelectedTextRangeAttribute,
+            kAXSelectedTextRangesAttribute,
+            kAXSizeAttribute,
+            kAXVisibleCharacterRangeAttribute,
+        ];
+
+        Self {
+            exclude: exclude.iter().map(|s| s.to_string()).collect(),
+            frame_precision: None,
+        }
+    }
+}
+
+impl NormalizeConfig {
+    pub fn exclude(mut self, attribute: &str) -> Self {
+        self.exclude.insert(attribute.to_owned());
+        self
+    }
+
+    pub fn include(mut self, attribute: &str) -> Self {
+        self.exclude.remove(attribute);
+        self
+    }
+
+    pub fn frame_precision(mut self, precision: Option<f64>) -> Self {
+        self.frame_precision = precision;
+        self
+    }
+
+    pub fn normalize(&self, node: &SnapshotNode) -> SnapshotNode {
+        let filter = AttributeFilter {
+            exclude: self.exclude.iter().cloned().collect(),
+            ..Default::default()
+        };
+        let mut node = node.clone();
+
+        self.normalize_in_place(&filter, &mut node);
+        node
+    }
+
+    fn normalize_in_place(&self, filter: &AttributeFilter, node: &mut SnapshotNode) {
+        filter.apply(node);
+        node.frame = match self.frame_precision {
+            Some(precision) => node.frame.map(|f| round_frame(f, precision)),
+            None => None,
+        };
+
+        for child in &mut node.children {
+            self.normalize_in_place(filter, child);
+        }
+    }
+}
+
+fn round_frame(frame: Frame, precision: f64) -> Frame {
+    let round = |v: f64| {
+        if precision > 0.0 {
+            (v / precision).round() * precision
+        } else {
+            v
+        }
+    };
+
+    Frame {
+        x: round(frame.x),
+        y: round(frame.y),
+        width: round(frame.width),
+        height: round(frame.height),
+    }
+}
+
+/// Anything that can be turned into a snapshot for a golden comparison.
+pub trait IntoSnapshot {
+    fn into_snapshot(self) -> SnapshotNode;
+}
+
+impl IntoSnapshot for &AXUIElement {
+    fn into_snapshot(self) -> SnapshotNode {
+        SnapshotNode::capture(self)
+    }
+}
+
+impl IntoSnapshot for &SnapshotNode {
+    fn into_snapshot(self) -> SnapshotNode {
+        self.clone()
+    }
+}
+
+impl IntoSnapshot for SnapshotNode {
+    fn into_snapshot(self) -> SnapshotNode {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub enum GoldenOutcome {
+    /// The snapshot matched the golden file.
+    Matched,
+    /// The golden file was created or overwritten because updates were
+    /// accepted.
+    Written,
+    /// There is no golden file yet. The snapshot was written next to where
+    /// it should be, with a `.new` extension.
+    Missing { new_path: PathBuf },
+    /// The snapshot differs from the golden file. The actual snapshot was
+    /// written with a `.new` extension.
+    Mismatch { diff: TreeDiff, new_path: PathBuf },
+}
+
+/// Where `assert_ui_snapshot!` keeps the golden file called `name`.
+pub fn golden_path(manifest_dir: &str, name: &str) -> PathBuf {
+    Path::new(manifest_dir)
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{}.json", name))
+}
+
+fn new_path(golden: &Path) -> PathBuf {
+    let mut path = golden.as_os_str().to_owned();
+
+    path.push(".new");
+    PathBuf::from(path)
+}
+
+fn updates_accepted() -> bool {
+    env::var(UPDATE_ENV).map(|v| v == "1").unwrap_or(false)
+}
+
+fn write_snapshot(path: &Path, snapshot: &SnapshotNode) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut data = serde_json::to_string_pretty(snapshot)?;
+
+    data.push('\n');
+    fs::write(path, data)
+}
+
+pub fn read_snapshot(path: &Path) -> io::Result<SnapshotNode> {
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+/// Normalizes `actual` and compares it against the golden file at `golden`.
+///
+/// Works purely on snapshot data, so golden files can be checked without
+/// access to a live application.
+pub fn compare_golden(
+    actual: &SnapshotNode,
+    golden: &Path,
+    config: &NormalizeConfig,
+) -> io::Result<GoldenOutcome> {
+    let actual = config.normalize(actual);
+    let new_path = new_path(golden);
+
+    if updates_accepted() {
+        write_snapshot(golden, &actual)?;
+        let _ = fs::remove_file(&new_path);
+        return Ok(GoldenOutcome::Written);
+    }
+
+    let expected = match read_snapshot(golden) {
+        Ok(expected) => config.normalize(&expected),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            write_snapshot(&new_path, &actual)?;
+            return Ok(GoldenOutcome::Missing { new_path });
+        }
+        Err(e) => return Err(e),
+    };
+    let diff = TreeDiff::new(&expected, &actual);
+
+    if diff.is_empty() {
+        let _ = fs::remove_file(&new_path);
+        Ok(GoldenOutcome::Matched)
+    } else {
+        write_snapshot(&new_path, &actual)?;
+        Ok(GoldenOutcome::Mismatch { diff, new_path })
+    }
+}
+
+/// Implementation of [`assert_ui_snapshot!`]. Panics on mismatch.
+pub fn assert_golden(actual: impl IntoSnapshot, golden: &Path, config: &NormalizeConfig) {
+    let outcome = compare_golden(&actual.into_snapshot(), golden, config)
+        .unwrap_or_else(|e| panic!("{}: {}", golden.display(), e));
+
+    match outcome {
+        GoldenOutcome::Matched | GoldenOutcome::Written => {}
+        GoldenOutcome::Missing { new_path } => panic!(
+            "no golden snapshot at {}\nactual snapshot written to {}\nrerun with {}=1 to accept it",
+            golden.display(),
+            new_path.display(),
+            UPDATE_ENV,
+        ),
+        GoldenOutcome::Mismatch { diff, new_path } => panic!(
+            "UI snapshot does not match {}\n\n{}\nactual snapshot written to {}\nrerun with {}=1 to accept it",
+            golden.display(),
+            diff,
+            new_path.display(),
+            UPDATE_ENV,
+        ),
+    }
+}
+
+/// Asserts that an element (or snapshot) matches a golden file stored in
+/// `tests/snapshots/<name>.json` of the calling crate.
+///
+/// ```ignore
+/// assert_ui_snapshot!(&window, "preferences_window");
+/// assert_ui_snapshot!(&window, "preferences_window", NormalizeConfig::default().frame_precision(Some(10.0)));
+/// ```
+#[macro_export]
+macro_rules! assert_ui_snapshot {
+    ($element:expr, $name:expr) => {
+        $crate::assert_ui_snapshot!($element, $name, $crate::golden::NormalizeConfig::default())
+    };
+    ($element:expr, $name:expr, $config:expr) => {
+        $crate::golden::assert_golden(
+            $element,
+            &$crate::golden::golden_path(env!("CARGO_MANIFEST_DIR"), $name),
+            &$config,
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::AXAny;
+    use accessibility_sys::{kAXTitleAttribute, kAXValueAttribute};
+
+    /// Serializes the tests that read [`UPDATE_ENV`], since one of them sets
+    /// it.
+    static ENV: Mutex<()> = Mutex::new(());
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!(
+            "accessibility-golden-{}-{}",
+            std::process::id(),
+            name
+        ));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn button(title: &str) -> SnapshotNode {
+        let mut node = SnapshotNode {
+            role: "AXButton".to_owned(),
+            title: Some(title.to_owned()),
+            frame: Some(Frame::new(12.4, 7.6, 100.2, 50.5)),
+            ..Default::default()
+        };
+
+        node.attributes
+            .insert(kAXFocusedAttribute.to_owned(), AXAny::Bool(true));
+        node.attributes
+            .insert("AXHelp".to_owned(), AXAny::from("Saves the file"));
+        node
+    }
+
+    fn window(title: &str) -> SnapshotNode {
+        SnapshotNode {
+            role: "AXWindow".to_owned(),
+            children: vec![button(title)],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn normalize_drops_frames_and_excluded_attributes_by_default() {
+        let normalized = NormalizeConfig::default().normalize(&window("Save"));
+        let button = &normalized.children[0];
+
+        assert_eq!(button.frame, None);
+        assert!(!button.attributes.contains_key(kAXFocusedAttribute));
+        assert!(button.attributes.contains_key("AXHelp"));
+    }
+
+    #[test]
+    fn normalize_rounds_frames_to_the_precision() {
+        let config = NormalizeConfig::default()
+            .frame_precision(Some(10.0))
+            .include(kAXFocusedAttribute)
+            .exclude("AXHelp");
+        let button = config.normalize(&button("Save"));
+
+        assert_eq!(button.frame, Some(Frame::new(10.0, 10.0, 100.0, 50.0)));
+        assert!(button.attributes.contains_key(kAXFocusedAttribute));
+        assert!(!button.attributes.contains_key("AXHelp"));
+    }
+
+    #[test]
+    fn normalize_excludes_lifted_attributes() {
+        let mut clock = button("12:00");
+
+        clock.value = Some(AXAny::from("12:00:01"));
+        clock.identifier = Some("clock".to_owned());
+
+        let normalized = NormalizeConfig::default()
+            .exclude(kAXValueAttribute)
+            .exclude(kAXTitleAttribute)
+            .normalize(&clock);
+
+        assert_eq!(normalized.value, None);
+        assert_eq!(normalized.title, None);
+        assert_eq!(normalized.identifier.as_deref(), Some("clock"));
+        assert_eq!(normalized.role, "AXButton");
+
+        let mut later = clock.clone();
+
+        later.value = Some(AXAny::from("12:00:02"));
+        later.title = Some("12:01".to_owned());
+
+        let config = NormalizeConfig::default()
+            .exclude(kAXValueAttribute)
+            .exclude(kAXTitleAttribute);
+
+        assert_eq!(config.normalize(&clock), config.normalize(&later));
+    }
+
+    #[test]
+    fn missing_golden_writes_new_file() {
+        let _env = ENV.lock().unwrap();
+        let golden = temp_dir("missing").join("window.json");
+        let outcome = compare_golden(&window("Save"), &golden, &NormalizeConfig::default());
+
+        match outcome.unwrap() {
+            GoldenOutcome::Missing { new_path } => {
+                assert_eq!(new_path, golden.with_extension("json.new"));
+                assert!(new_path.exists());
+            }
+            outcome => panic!("unexpected outcome {:?}", outcome),
+        }
+
+        assert!(!golden.exists());
+    }
+
+    #[test]
+    fn matching_golden_removes_stale_new_file() {
+        let _env = ENV.lock().unwrap();
+        let golden = temp_dir("matched").join("window.json");
+        let config = NormalizeConfig::default();
+
+        write_snapshot(&golden, &config.normalize(&window("Save"))).unwrap();
+        fs::write(new_path(&golden), "stale").unwrap();
+
+        let outcome = compare_golden(&window("Save"), &golden, &config).unwrap();
+
+        assert!(matches!(outcome, GoldenOutcome::Matched));
+        assert!(!new_path(&golden).exists());
+    }
+
+    #[test]
+    fn mismatch_writes_the_actual_snapshot_next_to_the_golden() {
+        let _env = ENV.lock().unwrap();
+        let golden = temp_dir("mismatch").join("window.json");
+        let config = NormalizeConfig::default();
+
+        write_snapshot(&golden, &config.normalize(&window("Save"))).unwrap();
+
+        match compare_golden(&window("Save As"), &golden, &config).unwrap() {
+            GoldenOutcome::Mismatch { diff, new_path } => {
+                assert!(!diff.is_empty());
+                assert_eq!(
+                    read_snapshot(&new_path).unwrap(),
+                    config.normalize(&window("Save As"))
+                );
+            }
+            outcome => panic!("unexpected outcome {:?}", outcome),
+        }
+
+        // The golden file itself is left alone.
+        assert_eq!(
+            read_snapshot(&golden).unwrap(),
+            config.normalize(&window("Save"))
+        );
+    }
+
+    #[test]
+    fn accepted_updates_overwrite_the_golden() {
+        let _env = ENV.lock().unwrap();
+        let golden = temp_dir("update").join("snapshots").join("window.json");
+        let config = NormalizeConfig::default();
+
+        write_snapshot(&golden, &config.normalize(&window("Save"))).unwrap();
+        fs::write(new_path(&golden), "stale").unwrap();
+
+        env::set_var(UPDATE_ENV, "1");
+        let outcome = compare_golden(&window("Save As"), &golden, &config);
+        env::remove_var(UPDATE_ENV);
+
+        assert!(matches!(outcome.unwrap(), GoldenOutcome::Written));
+        assert_eq!(
+            read_snapshot(&golden).unwrap(),
+            config.normalize(&window("Save As"))
+        );
+        assert!(!new_path(&golden).exists());
+    }
+}
@@ -1,8 +1,23 @@
 pub mod action;
+pub mod any;
 pub mod attribute;
+pub mod coalesce;
+pub mod diff;
+pub mod geometry;
+pub mod golden;
+pub mod menu;
+pub mod mirror;
+pub mod observer;
+pub mod role;
+pub mod selector;
+pub mod shortcut;
+pub mod snapshot;
+pub mod spatial;
+pub mod table;
 pub mod ui_element;
 mod util;
 pub mod value;
+pub mod visitor;
 
 use accessibility_sys::{error_string, AXError, AXValueType};
 use core_foundation::{
@@ -19,8 +34,22 @@ use std::{
 use thiserror::Error as TError;
 
 pub use action::*;
+pub use any::*;
 pub use attribute::*;
+pub use coalesce::*;
+pub use diff::*;
+pub use geometry::*;
+pub use menu::*;
+pub use mirror::*;
+pub use observer::*;
+pub use role::*;
+pub use selector::*;
+pub use shortcut::*;
+pub use snapshot::*;
+pub use spatial::*;
+pub use table::*;
 pub use ui_element::*;
+pub use visitor::*;
 
 #[non_exhaustive]
 #[derive(Debug, TError)]
@@ -47,6 +76,12 @@ pub enum Error {
     },
     #[error("accessibility error {}", error_string(*.0))]
     Ax(AXError),
+    #[error("menu path {} failed at {:?}: {}", .path.join(" > "), .path[*.index], .source)]
+    MenuPath {
+        path: Vec<String>,
+        index: usize,
+        source: Box<Error>,
+    },
 }
 
 fn type_name(type_id: CFTypeID) -> CFString {
@@ -58,6 +93,16 @@ pub trait TreeVisitor {
     fn exit_element(&self, element: &AXUIElement);
 }
 
+impl<T: TreeVisitor + ?Sized> TreeVisitor for &T {
+    fn enter_element(&self, element: &AXUIElement) -> TreeWalkerFlow {
+        (**self).enter_element(element)
+    }
+
+    fn exit_element(&self, element: &AXUIElement) {
+        (**self).exit_element(element)
+    }
+}
+
 pub struct TreeWalker {
     attr_children: AXAttribute<CFArray<AXUIElement>>,
 }
@@ -110,25 +155,70 @@ impl TreeWalker {
 pub struct ElementFinder {
     root: AXUIElement,
     implicit_wait: Option<Duration>,
-    predicate: Box<dyn Fn(&AXUIElement) -> bool>,
+    matcher: Matcher,
     depth: Cell<usize>,
+    path: RefCell<Vec<AXUIElement>>,
     cached: RefCell<Option<AXUIElement>>,
 }
 
+enum Matcher {
+    Predicate(Box<dyn Fn(&AXUIElement) -> bool>),
+    /// Matched against the path the walk has taken from the application, so
+    /// candidates' ancestors don't have to be read back through `AXParent`.
+    Selector(Selector),
+}
+
+impl Matcher {
+    fn matches(&self, path: &[AXUIElement]) -> bool {
+        match self {
+            Matcher::Predicate(predicate) => path.last().is_some_and(predicate),
+            Matcher::Selector(selector) => selector.matches_path(path),
+        }
+    }
+}
+
 impl ElementFinder {
     pub fn new<F>(root: &AXUIElement, predicate: F, implicit_wait: Option<Duration>) -> Self
     where
         F: 'static + Fn(&AXUIElement) -> bool,
     {
+        Self::with_matcher(root, Matcher::Predicate(Box::new(predicate)), implicit_wait)
+    }
+
+    fn with_matcher(root: &AXUIElement, matcher: Matcher, implicit_wait: Option<Duration>) -> Self {
         Self {
             root: root.clone(),
-            predicate: Box::new(predicate),
+            matcher,
             implicit_wait,
             depth: Cell::new(0),
+            path: RefCell::new(Vec::new()),
             cached: RefCell::new(None),
         }
     }
 
+    /// Finds elements below `root` matching `selector`.
+    pub fn with_selector(
+        root: &AXUIElement,
+        selector: Selector,
+        implicit_wait: Option<Duration>,
+    ) -> Self {
+        Self::with_matcher(root, Matcher::Selector(selector), implicit_wait)
+    }
+
+    /// The ancestors of `root`, which start the path each walk extends.
+    /// Only selectors look at the path, so predicates skip reading them.
+    fn root_path(&self) -> Vec<AXUIElement> {
+        match self.matcher {
+            Matcher::Predicate(_) => Vec::new(),
+            Matcher::Selector(_) => {
+                let mut path = self.root.ancestry();
+
+                path.pop();
+                path
+            }
+        }
+    }
+
     pub fn find(&self) -> Result<AXUIElement, Error> {
         if let Some(result) = &*self.cached.borrow() {
             return Ok(result.clone());
@@ -146,6 +236,7 @@ impl ElementFinder {
                 return Ok(result.clone());
             }
 
+            self.path.replace(self.root_path());
             walker.walk(&self.root, self);
             let now = Instant::now();
 
@@ -158,6 +249,36 @@ impl ElementFinder {
         }
     }
 
+    /// Finds every matching element, waiting up to the implicit wait for
+    /// there to be at least one. Matches are in depth-first pre-order.
+    pub fn find_all(&self) -> Result<Vec<AXUIElement>, Error> {
+        let deadline = Instant::now() + self.implicit_wait.unwrap_or_default();
+        let walker = TreeWalker::new();
+
+        loop {
+            let collector = FindAll {
+                finder: self,
+                depth: Cell::new(0),
+                path: RefCell::new(self.root_path()),
+                found: RefCell::new(Vec::new()),
+            };
+
+            walker.walk(&self.root, &collector);
+
+            let found = collector.found.into_inner();
+            let now = Instant::now();
+
+            if !found.is_empty() {
+                return Ok(found);
+            } else if now >= deadline {
+                return Err(Error::NotFound);
+            } else {
+                let time_left = deadline.saturating_duration_since(now);
+                thread::sleep(std::cmp::min(time_left, Duration::from_millis(250)));
+            }
+        }
+    }
+
     pub fn reset(&self) {
         self.cached.replace(None);
     }
@@ -166,6 +287,14 @@ impl ElementFinder {
         self.find()?.attribute(attribute)
     }
 
+    pub fn parameterized_attribute<T: TCFType, U: TCFType>(
+        &self,
+        attribute: &AXAttribute<T>,
+        parameter: &U,
+    ) -> Result<T, Error> {
+        self.find()?.parameterized_attribute(attribute, parameter)
+    }
+
     pub fn set_attribute<T: TCFType>(
         &self,
         attribute: &AXAttribute<T>,
@@ -179,13 +308,14 @@ impl ElementFinder {
     }
 }
 
-const MAX_DEPTH: usize = 100;
+pub(crate) const MAX_DEPTH: usize = 100;
 
 impl TreeVisitor for ElementFinder {
     fn enter_element(&self, element: &AXUIElement) -> TreeWalkerFlow {
         self.depth.set(self.depth.get() + 1);
+        self.path.borrow_mut().push(element.clone());
 
-        if (self.predicate)(element) {
+        if self.matcher.matches(&self.path.borrow()) {
             self.cached.replace(Some(element.clone()));
             return TreeWalkerFlow::Exit;
         }
@@ -198,6 +328,36 @@ impl TreeVisitor for ElementFinder {
     }
 
     fn exit_element(&self, _element: &AXUIElement) {
-        self.depth.set(self.depth.get() - 1)
+        self.depth.set(self.depth.get() - 1);
+        self.path.borrow_mut().pop();
+    }
+}
+
+struct FindAll<'a> {
+    finder: &'a ElementFinder,
+    depth: Cell<usize>,
+    path: RefCell<Vec<AXUIElement>>,
+    found: RefCell<Vec<AXUIElement>>,
+}
+
+impl TreeVisitor for FindAll<'_> {
+    fn enter_element(&self, element: &AXUIElement) -> TreeWalkerFlow {
+        self.depth.set(self.depth.get() + 1);
+        self.path.borrow_mut().push(element.clone());
+
+        if self.finder.matcher.matches(&self.path.borrow()) {
+            self.found.borrow_mut().push(element.clone());
+        }
+
+        if self.depth.get() > MAX_DEPTH {
+            TreeWalkerFlow::SkipSubtree
+        } else {
+            TreeWalkerFlow::Continue
+        }
+    }
+
+    fn exit_element(&self, _element: &AXUIElement) {
+        self.depth.set(self.depth.get() - 1);
+        self.path.borrow_mut().pop();
     }
 }
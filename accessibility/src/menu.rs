@@ -0,0 +1,409 @@
+use std::{
+    cell::Cell,
+    rc::Rc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use accessibility_sys::{
+    kAXEnabledAttribute, kAXErrorCannotComplete, kAXMenuBarRole, kAXMenuItemCmdCharAttribute,
+    kAXMenuItemCmdGlyphAttribute, kAXMenuItemCmdModifiersAttribute,
+    kAXMenuItemCmdVirtualKeyAttribute, kAXMenuOpenedNotification, kAXMenuRole,
+};
+use core_foundation::runloop::{kCFRunLoopDefaultMode, CFRunLoop};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AXUIElement, AXUIElementActions, AXUIElementAttributes, Error, Observer, Role, Shortcut,
+    SnapshotNode,
+};
+
+/// How menu titles are compared against path segments.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TitleMatch {
+    pub ignore_case: bool,
+    /// Treat a trailing `…` or `...` as absent, so `"Export…"`,
+    /// `"Export..."` and `"Export"` all match each other.
+    pub ignore_ellipsis: bool,
+}
+
+impl Default for TitleMatch {
+    fn default() -> Self {
+        Self {
+            ignore_case: false,
+            ignore_ellipsis: true,
+        }
+    }
+}
+
+impl TitleMatch {
+    pub fn normalize(&self, title: &str) -> String {
+        let mut title = title.trim();
+
+        if self.ignore_ellipsis {
+            title = title
+                .strip_suffix('…')
+                .or_else(|| title.strip_suffix("..."))
+                .unwrap_or(title)
+                .trim_end();
+        }
+
+        if self.ignore_case {
+            title.to_lowercase()
+        } else {
+            title.to_owned()
+        }
+    }
+
+    pub fn matches(&self, title: &str, segment: &str) -> bool {
+        self.normalize(title) == self.normalize(segment)
+    }
+}
+
+/// How to tell that a submenu has opened after pressing its parent item.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MenuWait {
+    /// Wait for `AXMenuOpened`, falling back to polling if the observer can't
+    /// be created.
+    Notification,
+    /// Poll the item until its submenu has children.
+    Poll,
+}
+
+#[derive(Clone, Debug)]
+pub struct MenuOptions {
+    pub title_match: TitleMatch,
+    pub wait: MenuWait,
+    /// How long to wait for each submenu to open.
+    pub timeout: Duration,
+}
+
+impl Default for MenuOptions {
+    fn default() -> Self {
+        Self {
+            title_match: TitleMatch::default(),
+            wait: MenuWait::Notification,
+            timeout: Duration::from_secs(2),
+        }
+    }
+}
+
+/// One entry of a menu tree, as returned by
+/// [`AXUIElementMenus::list_menu_items`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MenuItem {
+    pub title: String,
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shortcut: Option<Shortcut>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<MenuItem>,
+}
+
+impl MenuItem {
+    /// Rebuilds the menu tree from a snapshot. `node` may be the menu bar
+    /// itself or any node containing it, such as a captured application.
+    pub fn from_snapshot(node: &SnapshotNode) -> Vec<MenuItem> {
+        let mut menu_bar = None;
+
+        node.walk(&mut |n, _| {
+            if menu_bar.is_none() && n.role == kAXMenuBarRole {
+                menu_bar = Some(n);
+            }
+        });
+
+        menu_bar.map(snapshot_items).unwrap_or_default()
+    }
+}
+
+/// Path-based navigation of a menu bar or menu.
+pub trait AXUIElementMenus {
+    /// Opens each menu along `path` and presses the last item, e.g.
+    /// `app.menu_bar()?.invoke(&["File", "Export", "PDF…"])`.
+    fn invoke(&self, path: &[&str]) -> Result<(), Error>;
+    fn invoke_with(&self, path: &[&str], options: &MenuOptions) -> Result<(), Error>;
+    /// Finds the item at `path` without opening any menus.
+    fn menu_item(&self, path: &[&str], title_match: &TitleMatch) -> Result<AXUIElement, Error>;
+    /// Reads the whole menu tree below this element.
+    fn list_menu_items(&self) -> Result<Vec<MenuItem>, Error>;
+}
+
+impl AXUIElementMenus for AXUIElement {
+    fn invoke(&self, path: &[&str]) -> Result<(), Error> {
+        self.invoke_with(path, &MenuOptions::default())
+    }
+
+    fn invoke_with(&self, path: &[&str], options: &MenuOptions) -> Result<(), Error> {
+        let mut container = self.clone();
+
+        for (index, segment) in path.iter().enumerate() {
+            let fail = |source: Error| path_error(path, index, source);
+            let item = find_item(&container, segment, &options.title_match).map_err(fail)?;
+
+            if index + 1 == path.len() {
+                item.press().map_err(fail)?;
+            } else {
+                container = open_submenu(&item, options).map_err(fail)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn menu_item(&self, path: &[&str], title_match: &TitleMatch) -> Result<AXUIElement, Error> {
+        let mut item = self.clone();
+
+        for (index, segment) in path.iter().enumerate() {
+            item = find_item(&item, segment, title_match)
+                .map_err(|source| path_error(path, index, source))?;
+        }
+
+        Ok(item)
+    }
+
+    fn list_menu_items(&self) -> Result<Vec<MenuItem>, Error> {
+        Ok(menu_items(self)?
+            .iter()
+            .map(|item| MenuItem {
+                title: item.title().map(|t| t.to_string()).unwrap_or_default(),
+                enabled: item.enabled().map(bool::from).unwrap_or(false),
+                shortcut: Shortcut::from_element(item),
+                children: item.list_menu_items().unwrap_or_default(),
+            })
+            .collect())
+    }
+}
+
+fn snapshot_items(container: &SnapshotNode) -> Vec<MenuItem> {
+    let mut items = Vec::new();
+
+    for child in &container.children {
+        if child.role == kAXMenuRole {
+            items.extend(snapshot_items(child));
+            continue;
+        }
+
+        let number = |name: &str| {
+            child
+                .attribute(name)
+                .and_then(|v| v.as_i64())
+                .map(|n| n as u32)
+        };
+        let cmd_char = child.attribute(kAXMenuItemCmdCharAttribute);
+
+        items.push(MenuItem {
+            title: child.title.clone().unwrap_or_default(),
+            enabled: child
+                .attribute(kAXEnabledAttribute)
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            shortcut: Shortcut::from_ax(
+                cmd_char.as_ref().and_then(|c| c.as_str()),
+                number(kAXMenuItemCmdVirtualKeyAttribute),
+                number(kAXMenuItemCmdGlyphAttribute),
+                number(kAXMenuItemCmdModifiersAttribute),
+            ),
+            children: snapshot_items(child),
+        });
+    }
+
+    items
+}
+
+fn path_error(path: &[&str], index: usize, source: Error) -> Error {
+    Error::MenuPath {
+        path: path.iter().map(|s| s.to_string()).collect(),
+        index,
+        source: Box::new(source),
+    }
+}
+
+/// The items directly below a menu bar, a menu, or an item with a submenu.
+fn menu_items(container: &AXUIElement) -> Result<Vec<AXUIElement>, Error> {
+    if let Role::MenuBar | Role::Menu = container.role_typed()? {
+        return Ok(container.children()?.iter().map(|c| c.clone()).collect());
+    }
+
+    match submenu(container) {
+        Some(menu) => menu_items(&menu),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn submenu(item: &AXUIElement) -> Option<AXUIElement> {
+    item.children()
+        .ok()?
+        .iter()
+        .find_map(|child| match child.role_typed() {
+            Ok(Role::Menu) => Some(child.clone()),
+            _ => None,
+        })
+}
+
+fn find_item(
+    container: &AXUIElement,
+    segment: &str,
+    title_match: &TitleMatch,
+) -> Result<AXUIElement, Error> {
+    menu_items(container)?
+        .into_iter()
+        .find(|item| match item.title() {
+            Ok(title) => title_match.matches(&title.to_string(), segment),
+            Err(_) => false,
+        })
+        .ok_or(Error::NotFound)
+}
+
+/// Presses `item` and waits for its submenu to open.
+fn open_submenu(item: &AXUIElement, options: &MenuOptions) -> Result<AXUIElement, Error> {
+    let opened = Rc::new(Cell::new(false));
+    let observer = match options.wait {
+        MenuWait::Notification => menu_observer(item, opened.clone()).ok(),
+        MenuWait::Poll => None,
+    };
+    let deadline = Instant::now() + options.timeout;
+
+    item.press()?;
+
+    loop {
+        let menu = submenu(item);
+        let ready = match &observer {
+            Some(_) => opened.get(),
+            None => menu
+                .as_ref()
+                .and_then(|m| m.children().ok())
+                .is_some_and(|c| !c.is_empty()),
+        };
+
+        if ready {
+            return menu.ok_or(Error::NotFound);
+        }
+
+        let now = Instant::now();
+
+        if now >= deadline {
+            // Menus that were already populated may never announce
+            // themselves, so settle for what is there.
+            return menu.ok_or(Error::Ax(kAXErrorCannotComplete));
+        }
+
+        let step = std::cmp::min(deadline - now, Duration::from_millis(50));
+
+        match &observer {
+            Some(_) => {
+                CFRunLoop::run_in_mode(unsafe { kCFRunLoopDefaultMode }, step, true);
+            }
+            None => thread::sleep(step),
+        }
+    }
+}
+
+fn menu_observer(item: &AXUIElement, opened: Rc<Cell<bool>>) -> Result<Observer, Error> {
+    let pid = item.pid()?;
+    let observer = Observer::new(pid, move |_| opened.set(true))?;
+
+    observer.add_notification(&AXUIElement::application(pid), kAXMenuOpenedNotification)?;
+    observer.schedule();
+    Ok(observer)
+}
+
+#[cfg(test)]
+mod tests {
+    use accessibility_sys::{
+        kAXMenuBarItemRole, kAXMenuItemModifierShift, kAXMenuItemRole, kAXWindowRole,
+    };
+
+    use super::*;
+    use crate::{AXAny, Key, Modifiers, Number};
+
+    fn node(role: &str, title: &str, children: Vec<SnapshotNode>) -> SnapshotNode {
+        SnapshotNode {
+            role: role.to_owned(),
+            title: Some(title.to_owned()),
+            children,
+            ..Default::default()
+        }
+    }
+
+    fn item(title: &str, enabled: bool, cmd_char: Option<&str>, modifiers: u32) -> SnapshotNode {
+        let mut node = node(kAXMenuItemRole, title, vec![]);
+
+        node.attributes
+            .insert(kAXEnabledAttribute.to_owned(), AXAny::Bool(enabled));
+        if let Some(c) = cmd_char {
+            node.attributes
+                .insert(kAXMenuItemCmdCharAttribute.to_owned(), AXAny::from(c));
+            node.attributes.insert(
+                kAXMenuItemCmdModifiersAttribute.to_owned(),
+                AXAny::Number(Number::Integer(modifiers as i64)),
+            );
+        }
+
+        node
+    }
+
+    fn menu(title: &str, items: Vec<SnapshotNode>) -> SnapshotNode {
+        node(
+            kAXMenuBarItemRole,
+            title,
+            vec![node(kAXMenuRole, "", items)],
+        )
+    }
+
+    #[test]
+    fn from_snapshot_reads_menus() {
+        let mut export = menu("Export", vec![item("PDF", true, Some("e"), 0)]);
+        export.role = kAXMenuItemRole.to_owned();
+        let bar = node(
+            kAXMenuBarRole,
+            "",
+            vec![menu(
+                "File",
+                vec![
+                    item("Save", true, Some("s"), 0),
+                    item("Save As…", false, Some("s"), kAXMenuItemModifierShift),
+                    item("Close All", true, None, 0),
+                    export,
+                ],
+            )],
+        );
+        let app = node(
+            "AXApplication",
+            "App",
+            vec![node(kAXWindowRole, "Window", vec![]), bar],
+        );
+
+        let items = MenuItem::from_snapshot(&app);
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].title, "File");
+
+        let file = &items[0].children;
+        let titles: Vec<&str> = file.iter().map(|i| i.title.as_str()).collect();
+        assert_eq!(titles, ["Save", "Save As…", "Close All", "Export"]);
+
+        assert!(file[0].enabled);
+        assert_eq!(
+            file[0].shortcut,
+            Some(Shortcut {
+                key: Key::Character("S".to_owned()),
+                modifiers: Modifiers {
+                    command: true,
+                    ..Default::default()
+                },
+            })
+        );
+        assert!(!file[1].enabled);
+        assert!(file[1].shortcut.as_ref().unwrap().modifiers.shift);
+        assert_eq!(file[2].shortcut, None);
+        assert_eq!(file[3].children.len(), 1);
+        assert_eq!(file[3].children[0].title, "PDF");
+    }
+
+    #[test]
+    fn from_snapshot_without_menu_bar() {
+        let window = node(kAXWindowRole, "Window", vec![]);
+
+        assert!(MenuItem::from_snapshot(&window).is_empty());
+    }
+}
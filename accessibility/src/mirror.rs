@@ -0,0 +1,627 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use accessibility_sys::{
+    kAXCreatedNotification, kAXErrorInvalidUIElement, kAXLayoutChangedNotification,
+    kAXTitleChangedNotification, kAXUIElementDestroyedNotification, kAXValueChangedNotification,
+};
+
+use crate::{AXUIElement, AXUIElementAttributes, Error, Observer, ObserverEvent, SnapshotNode};
+
+/// Notifications a [`TreeMirror`] reacts to.
+pub const MIRROR_NOTIFICATIONS: [&str; 5] = [
+    kAXCreatedNotification,
+    kAXUIElementDestroyedNotification,
+    kAXValueChangedNotification,
+    kAXTitleChangedNotification,
+    kAXLayoutChangedNotification,
+];
+
+/// Source of element data for a [`TreeMirror`].
+pub trait MirrorBackend {
+    type Element: Clone + Eq + Hash;
+
+    /// Reads the attributes of a single element. Children in the returned
+    /// node are ignored.
+    fn read(&self, element: &Self::Element) -> Result<SnapshotNode, Error>;
+    fn children(&self, element: &Self::Element) -> Result<Vec<Self::Element>, Error>;
+    fn parent(&self, element: &Self::Element) -> Result<Option<Self::Element>, Error>;
+}
+
+/// Reads live elements over the accessibility API.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct AXBackend;
+
+impl MirrorBackend for AXBackend {
+    type Element = AXUIElement;
+
+    fn read(&self, element: &AXUIElement) -> Result<SnapshotNode, Error> {
+        // An element that can't report its role is gone; anything else it
+        // fails to report is just missing from the node.
+        element.role()?;
+        Ok(SnapshotNode::read(element))
+    }
+
+    fn children(&self, element: &AXUIElement) -> Result<Vec<AXUIElement>, Error> {
+        match element.children() {
+            Ok(children) => Ok(children.into_iter().map(|c| c.clone()).collect()),
+            Err(e) if is_gone(&e) => Err(e),
+            Err(_) => Ok(Vec::new()),
+        }
+    }
+
+    fn parent(&self, element: &AXUIElement) -> Result<Option<AXUIElement>, Error> {
+        match element.parent() {
+            Ok(parent) => Ok(Some(parent)),
+            Err(e) if is_gone(&e) => Err(e),
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+#[derive(Clone, Debug)]
+pub struct MirrorNode<E> {
+    pub element: E,
+    pub parent: Option<NodeId>,
+    pub children: Vec<NodeId>,
+    /// The element's attributes. `data.children` is always empty; the tree
+    /// structure is in `children`.
+    pub data: SnapshotNode,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MirrorChange {
+    Added {
+        id: NodeId,
+        parent: Option<NodeId>,
+    },
+    Removed {
+        id: NodeId,
+        data: Box<SnapshotNode>,
+    },
+    Updated {
+        id: NodeId,
+        /// Names of the attributes whose values changed.
+        attributes: Vec<String>,
+    },
+}
+
+/// An in-process copy of an element tree, kept current by feeding it observer
+/// events.
+///
+/// Only the parts of the tree named by an event are re-read, so queries
+/// against the mirror never block on the target application.
+pub struct TreeMirror<B: MirrorBackend = AXBackend> {
+    backend: B,
+    root: NodeId,
+    nodes: HashMap<NodeId, MirrorNode<B::Element>>,
+    index: HashMap<B::Element, NodeId>,
+    next_id: usize,
+    changes: Vec<MirrorChange>,
+}
+
+impl TreeMirror<AXBackend> {
+    pub fn from_element(root: &AXUIElement) -> Result<Self, Error> {
+        Self::new(AXBackend, root.clone())
+    }
+
+    /// Registers every notification in [`MIRROR_NOTIFICATIONS`] on the
+    /// mirror's root element.
+    pub fn register(&self, observer: &Observer) -> Result<(), Error> {
+        let root = &self.nodes[&self.root].element;
+
+        for notification in MIRROR_NOTIFICATIONS.iter() {
+            observer.add_notification(root, notification)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<B: MirrorBackend> TreeMirror<B> {
+    /// Snapshots the tree below `root`. The initial snapshot does not produce
+    /// change records.
+    pub fn new(backend: B, root: B::Element) -> Result<Self, Error> {
+        let mut mirror = Self {
+            backend,
+            root: NodeId(0),
+            nodes: HashMap::new(),
+            index: HashMap::new(),
+            next_id: 0,
+            changes: Vec::new(),
+        };
+
+        mirror.root = mirror.insert_subtree(root, None, 0)?;
+        mirror.changes.clear();
+        Ok(mirror)
+    }
+
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    pub fn root(&self) -> NodeId {
+        self.root
+    }
+
+    pub fn node(&self, id: NodeId) -> Option<&MirrorNode<B::Element>> {
+        self.nodes.get(&id)
+    }
+
+    pub fn id_of(&self, element: &B::Element) -> Option<NodeId> {
+        self.index.get(element).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Finds the first node, in depth-first order, matching `predicate`.
+    pub fn find(&self, predicate: impl Fn(&SnapshotNode) -> bool) -> Option<NodeId> {
+        let mut stack = vec![self.root];
+
+        while let Some(id) = stack.pop() {
+            let node = &self.nodes[&id];
+
+            if predicate(&node.data) {
+                return Some(id);
+            }
+
+            stack.extend(node.children.iter().rev());
+        }
+
+        None
+    }
+
+    /// Rebuilds a nested snapshot of the subtree at `id`.
+    pub fn to_snapshot(&self, id: NodeId) -> Option<SnapshotNode> {
+        let node = self.nodes.get(&id)?;
+        let mut data = node.data.clone();
+
+        data.children = node
+            .children
+            .iter()
+            .filter_map(|child| self.to_snapshot(*child))
+            .collect();
+        Some(data)
+    }
+
+    /// Returns and clears the changes recorded since the last call.
+    pub fn take_changes(&mut self) -> Vec<MirrorChange> {
+        std::mem::take(&mut self.changes)
+    }
+
+    /// Applies one observer event. Events for elements outside the mirrored
+    /// tree are ignored.
+    pub fn handle_event(&mut self, event: &ObserverEvent<B::Element>) -> Result<(), Error> {
+        #[allow(non_upper_case_globals)]
+        match event.notification.as_str() {
+            kAXCreatedNotification => self.element_created(&event.element),
+            kAXUIElementDestroyedNotification => {
+                if let Some(id) = self.id_of(&event.element) {
+                    self.remove_subtree(id);
+                }
+
+                Ok(())
+            }
+            kAXValueChangedNotification | kAXTitleChangedNotification => {
+                match self.id_of(&event.element) {
+                    Some(id) => self.refresh_node(id),
+                    None => Ok(()),
+                }
+            }
+            kAXLayoutChangedNotification => match self.id_of(&event.element) {
+                Some(id) => self.refresh_subtree(id),
+                None => Ok(()),
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Re-reads the subtree at `id`, recording whatever changed.
+    pub fn refresh_subtree(&mut self, id: NodeId) -> Result<(), Error> {
+        self.refresh_node(id)?;
+
+        if self.nodes.contains_key(&id) {
+            for child in self.sync_children(id)? {
+                self.refresh_subtree(child)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn element_created(&mut self, element: &B::Element) -> Result<(), Error> {
+        if let Some(id) = self.id_of(element) {
+            return self.refresh_node(id);
+        }
+
+        // The new element's parent may itself be new, so climb until we reach
+        // a node we already know about and resync the children from there.
+        let mut current = element.clone();
+
+        for _ in 0..crate::MAX_DEPTH {
+            let parent = match self.backend.parent(&current) {
+                Ok(Some(parent)) => parent,
+                Ok(None) | Err(_) => return Ok(()),
+            };
+
+            if let Some(id) = self.id_of(&parent) {
+                self.sync_children(id)?;
+                return Ok(());
+            }
+
+            current = parent;
+        }
+
+        Ok(())
+    }
+
+    fn refresh_node(&mut self, id: NodeId) -> Result<(), Error> {
+        let element = self.nodes[&id].element.clone();
+
+        match self.backend.read(&element) {
+            Ok(mut data) => {
+                data.children.clear();
+
+                let node = self.nodes.get_mut(&id).unwrap();
+                let attributes = changed_attributes(&node.data, &data);
+
+                if !attributes.is_empty() {
+                    node.data = data;
+                    self.changes.push(MirrorChange::Updated { id, attributes });
+                }
+
+                Ok(())
+            }
+            Err(e) if is_gone(&e) => {
+                self.remove_subtree(id);
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Brings the child list of `id` up to date, inserting new subtrees and
+    /// removing vanished ones. Returns the children that were already known.
+    fn sync_children(&mut self, id: NodeId) -> Result<Vec<NodeId>, Error> {
+        let element = self.nodes[&id].element.clone();
+        let depth = self.depth(id);
+        let elements = match self.backend.children(&element) {
+            Ok(elements) => elements,
+            Err(e) if is_gone(&e) => {
+                self.remove_subtree(id);
+                return Ok(Vec::new());
+            }
+            Err(e) => return Err(e),
+        };
+        let mut children = Vec::with_capacity(elements.len());
+        let mut existing = Vec::new();
+
+        for child in elements {
+            match self.id_of(&child) {
+                Some(child_id) if self.nodes[&child_id].parent == Some(id) => {
+                    children.push(child_id);
+                    existing.push(child_id);
+                }
+                Some(child_id) => {
+                    // Reparented: drop it from its old location first.
+                    self.remove_subtree(child_id);
+
+                    if let Ok(child_id) = self.insert_subtree(child, Some(id), depth + 1) {
+                        children.push(child_id);
+                    }
+                }
+                None => {
+                    if let Ok(child_id) = self.insert_subtree(child, Some(id), depth + 1) {
+                        children.push(child_id);
+                    }
+                }
+            }
+        }
+
+        let keep: HashSet<NodeId> = children.iter().copied().collect();
+        let old = std::mem::take(&mut self.nodes.get_mut(&id).unwrap().children);
+
+        for child in old {
+            if !keep.contains(&child) {
+                self.remove_subtree(child);
+            }
+        }
+
+        self.nodes.get_mut(&id).unwrap().children = children;
+        Ok(existing)
+    }
+
+    fn insert_subtree(
+        &mut self,
+        element: B::Element,
+        parent: Option<NodeId>,
+        depth: usize,
+    ) -> Result<NodeId, Error> {
+        let mut data = self.backend.read(&element)?;
+        let id = NodeId(self.next_id);
+
+        data.children.clear();
+        self.next_id += 1;
+        self.index.insert(element.clone(), id);
+        self.nodes.insert(
+            id,
+            MirrorNode {
+                element: element.clone(),
+                parent,
+                children: Vec::new(),
+                data,
+            },
+        );
+        self.changes.push(MirrorChange::Added { id, parent });
+
+        if depth < crate::MAX_DEPTH {
+            let mut children = Vec::new();
+
+            for child in self.backend.children(&element).unwrap_or_default() {
+                if self.index.contains_key(&child) {
+                    continue;
+                }
+
+                if let Ok(child_id) = self.insert_subtree(child, Some(id), depth + 1) {
+                    children.push(child_id);
+                }
+            }
+
+            self.nodes.get_mut(&id).unwrap().children = children;
+        }
+
+        Ok(id)
+    }
+
+    fn remove_subtree(&mut self, id: NodeId) {
+        let node = match self.nodes.remove(&id) {
+            Some(node) => node,
+            None => return,
+        };
+
+        if let Some(parent) = node.parent.and_then(|p| self.nodes.get_mut(&p)) {
+            parent.children.retain(|c| *c != id);
+        }
+
+        for child in &node.children {
+            self.remove_subtree(*child);
+        }
+
+        self.index.remove(&node.element);
+        self.changes.push(MirrorChange::Removed {
+            id,
+            data: Box::new(node.data),
+        });
+    }
+
+    fn depth(&self, mut id: NodeId) -> usize {
+        let mut depth = 0;
+
+        while let Some(parent) = self.nodes[&id].parent {
+            depth += 1;
+            id = parent;
+        }
+
+        depth
+    }
+}
+
+fn is_gone(error: &Error) -> bool {
+    matches!(error, Error::Ax(code) if *code == kAXErrorInvalidUIElement)
+}
+
+fn changed_attributes(old: &SnapshotNode, new: &SnapshotNode) -> Vec<String> {
+    let old = old.all_attributes();
+    let new = new.all_attributes();
+    let mut changed: Vec<String> = old
+        .iter()
+        .filter(|(name, value)| new.get(*name) != Some(value))
+        .map(|(name, _)| name.clone())
+        .collect();
+
+    changed.extend(new.keys().filter(|name| !old.contains_key(*name)).cloned());
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    /// A node's data, parent and children.
+    type FakeNode = (SnapshotNode, Option<u32>, Vec<u32>);
+
+    #[derive(Default)]
+    struct FakeBackend {
+        nodes: RefCell<HashMap<u32, FakeNode>>,
+    }
+
+    fn gone() -> Error {
+        Error::Ax(kAXErrorInvalidUIElement)
+    }
+
+    impl FakeBackend {
+        fn add(&self, element: u32, parent: Option<u32>, title: &str) {
+            let node = SnapshotNode {
+                role: "AXGroup".to_owned(),
+                title: Some(title.to_owned()),
+                ..Default::default()
+            };
+            let mut nodes = self.nodes.borrow_mut();
+
+            nodes.insert(element, (node, parent, Vec::new()));
+
+            if let Some(parent) = parent {
+                nodes.get_mut(&parent).unwrap().2.push(element);
+            }
+        }
+
+        fn remove(&self, element: u32) {
+            let mut nodes = self.nodes.borrow_mut();
+            let (_, parent, children) = nodes.remove(&element).unwrap();
+
+            if let Some(parent) = parent {
+                nodes.get_mut(&parent).unwrap().2.retain(|c| *c != element);
+            }
+
+            let mut stack = children;
+
+            while let Some(child) = stack.pop() {
+                stack.extend(nodes.remove(&child).unwrap().2);
+            }
+        }
+
+        fn set_title(&self, element: u32, title: &str) {
+            self.nodes.borrow_mut().get_mut(&element).unwrap().0.title = Some(title.to_owned());
+        }
+    }
+
+    impl MirrorBackend for FakeBackend {
+        type Element = u32;
+
+        fn read(&self, element: &u32) -> Result<SnapshotNode, Error> {
+            self.nodes
+                .borrow()
+                .get(element)
+                .map(|(node, _, _)| node.clone())
+                .ok_or_else(gone)
+        }
+
+        fn children(&self, element: &u32) -> Result<Vec<u32>, Error> {
+            self.nodes
+                .borrow()
+                .get(element)
+                .map(|(_, _, children)| children.clone())
+                .ok_or_else(gone)
+        }
+
+        fn parent(&self, element: &u32) -> Result<Option<u32>, Error> {
+            self.nodes
+                .borrow()
+                .get(element)
+                .map(|(_, parent, _)| *parent)
+                .ok_or_else(gone)
+        }
+    }
+
+    /// 1 > 2 > 3, with 4 as a second child of 1.
+    fn mirror() -> TreeMirror<FakeBackend> {
+        let backend = FakeBackend::default();
+
+        backend.add(1, None, "app");
+        backend.add(2, Some(1), "window");
+        backend.add(3, Some(2), "button");
+        backend.add(4, Some(1), "menu bar");
+        TreeMirror::new(backend, 1).unwrap()
+    }
+
+    fn event(element: u32, notification: &str) -> ObserverEvent<u32> {
+        ObserverEvent::new(element, notification, std::time::Instant::now())
+    }
+
+    #[test]
+    fn initial_snapshot_records_no_changes() {
+        let mut mirror = mirror();
+
+        assert_eq!(mirror.len(), 4);
+        assert!(mirror.take_changes().is_empty());
+        assert_eq!(mirror.to_snapshot(mirror.root()).unwrap().children.len(), 2);
+    }
+
+    #[test]
+    fn created_child_is_added_under_its_parent() {
+        let mut mirror = mirror();
+
+        mirror.backend().add(5, Some(2), "field");
+        mirror
+            .handle_event(&event(5, kAXCreatedNotification))
+            .unwrap();
+
+        let id = mirror.id_of(&5).unwrap();
+        let window = mirror.id_of(&2).unwrap();
+
+        assert_eq!(
+            mirror.take_changes(),
+            [MirrorChange::Added {
+                id,
+                parent: Some(window),
+            }]
+        );
+        assert_eq!(mirror.node(window).unwrap().children.len(), 2);
+        assert_eq!(
+            mirror.node(id).unwrap().data.title.as_deref(),
+            Some("field")
+        );
+    }
+
+    #[test]
+    fn destroyed_element_removes_its_subtree() {
+        let mut mirror = mirror();
+        let window = mirror.id_of(&2).unwrap();
+        let button = mirror.id_of(&3).unwrap();
+
+        mirror.backend().remove(2);
+        mirror
+            .handle_event(&event(2, kAXUIElementDestroyedNotification))
+            .unwrap();
+
+        let removed: Vec<NodeId> = mirror
+            .take_changes()
+            .into_iter()
+            .map(|change| match change {
+                MirrorChange::Removed { id, .. } => id,
+                change => panic!("unexpected change {:?}", change),
+            })
+            .collect();
+
+        assert_eq!(removed, [button, window]);
+        assert_eq!(mirror.len(), 2);
+        assert_eq!(mirror.id_of(&3), None);
+        assert_eq!(mirror.node(mirror.root()).unwrap().children.len(), 1);
+    }
+
+    #[test]
+    fn title_change_updates_the_node() {
+        let mut mirror = mirror();
+        let button = mirror.id_of(&3).unwrap();
+
+        mirror.backend().set_title(3, "OK");
+        mirror
+            .handle_event(&event(3, kAXTitleChangedNotification))
+            .unwrap();
+
+        assert_eq!(
+            mirror.take_changes(),
+            [MirrorChange::Updated {
+                id: button,
+                attributes: vec!["AXTitle".to_owned()],
+            }]
+        );
+        assert_eq!(
+            mirror.node(button).unwrap().data.title.as_deref(),
+            Some("OK")
+        );
+    }
+
+    #[test]
+    fn events_outside_the_tree_are_ignored() {
+        let mut mirror = mirror();
+
+        mirror
+            .handle_event(&event(99, kAXValueChangedNotification))
+            .unwrap();
+        assert!(mirror.take_changes().is_empty());
+    }
+}
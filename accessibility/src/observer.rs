@@ -0,0 +1,151 @@
+use std::{cell::RefCell, ffi::c_void, time::Instant};
+
+use accessibility_sys::{
+    pid_t, AXObserverAddNotification, AXObserverCreate, AXObserverGetRunLoopSource,
+    AXObserverGetTypeID, AXObserverRef, AXObserverRemoveNotification, AXUIElementRef,
+};
+use core_foundation::{
+    base::TCFType,
+    declare_TCFType, impl_CFTypeDescription, impl_TCFType,
+    runloop::{kCFRunLoopDefaultMode, CFRunLoop, CFRunLoopSource, CFRunLoopSourceInvalidate},
+    string::{CFString, CFStringRef},
+};
+
+use crate::{
+    util::{ax_call, ax_call_void},
+    AXUIElement, Error,
+};
+
+declare_TCFType!(AXObserver, AXObserverRef);
+impl_TCFType!(AXObserver, AXObserverRef, AXObserverGetTypeID);
+impl_CFTypeDescription!(AXObserver);
+
+/// A single notification delivered by an [`Observer`].
+///
+/// The element type is generic so that code consuming the event stream can be
+/// driven by synthetic events in place of live `AXUIElement`s.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ObserverEvent<E = AXUIElement> {
+    pub element: E,
+    pub notification: String,
+    pub timestamp: Instant,
+}
+
+impl<E> ObserverEvent<E> {
+    pub fn new(element: E, notification: impl Into<String>, timestamp: Instant) -> Self {
+        Self {
+            element,
+            notification: notification.into(),
+            timestamp,
+        }
+    }
+}
+
+type Callback = RefCell<Box<dyn FnMut(ObserverEvent)>>;
+
+/// Delivers accessibility notifications for one process to a callback.
+///
+/// Events are only delivered while the observer's run loop source is scheduled
+/// (see [`Observer::schedule`]) and the run loop is running.
+pub struct Observer {
+    observer: AXObserver,
+    callback: Box<Callback>,
+}
+
+unsafe extern "C" fn observer_callback(
+    _observer: AXObserverRef,
+    element: AXUIElementRef,
+    notification: CFStringRef,
+    refcon: *mut c_void,
+) {
+    let callback = &*(refcon as *const Callback);
+    let event = ObserverEvent::new(
+        AXUIElement::wrap_under_get_rule(element),
+        CFString::wrap_under_get_rule(notification).to_string(),
+        Instant::now(),
+    );
+
+    // A callback that spins the run loop itself must not re-enter.
+    if let Ok(mut callback) = callback.try_borrow_mut() {
+        (callback)(event);
+    }
+}
+
+impl Observer {
+    pub fn new<F>(pid: pid_t, callback: F) -> Result<Self, Error>
+    where
+        F: 'static + FnMut(ObserverEvent),
+    {
+        let observer = unsafe {
+            AXObserver::wrap_under_create_rule(
+                ax_call(|x| AXObserverCreate(pid, observer_callback, x)).map_err(Error::Ax)?,
+            )
+        };
+
+        Ok(Self {
+            observer,
+            callback: Box::new(RefCell::new(Box::new(callback))),
+        })
+    }
+
+    pub fn add_notification(&self, element: &AXUIElement, notification: &str) -> Result<(), Error> {
+        let notification = CFString::new(notification);
+        let refcon = &*self.callback as *const Callback as *mut c_void;
+
+        unsafe {
+            ax_call_void(|| {
+                AXObserverAddNotification(
+                    self.observer.0,
+                    element.as_concrete_TypeRef(),
+                    notification.as_concrete_TypeRef(),
+                    refcon,
+                )
+            })
+            .map_err(Error::Ax)
+        }
+    }
+
+    pub fn remove_notification(
+        &self,
+        element: &AXUIElement,
+        notification: &str,
+    ) -> Result<(), Error> {
+        let notification = CFString::new(notification);
+
+        unsafe {
+            ax_call_void(|| {
+                AXObserverRemoveNotification(
+                    self.observer.0,
+                    element.as_concrete_TypeRef(),
+                    notification.as_concrete_TypeRef(),
+                )
+            })
+            .map_err(Error::Ax)
+        }
+    }
+
+    pub fn run_loop_source(&self) -> CFRunLoopSource {
+        unsafe { CFRunLoopSource::wrap_under_get_rule(AXObserverGetRunLoopSource(self.observer.0)) }
+    }
+
+    /// Adds the observer to the current thread's run loop in the default mode.
+    pub fn schedule(&self) {
+        unsafe {
+            CFRunLoop::get_current().add_source(&self.run_loop_source(), kCFRunLoopDefaultMode);
+        }
+    }
+
+    pub fn unschedule(&self) {
+        unsafe {
+            CFRunLoop::get_current().remove_source(&self.run_loop_source(), kCFRunLoopDefaultMode);
+        }
+    }
+}
+
+impl Drop for Observer {
+    fn drop(&mut self) {
+        // The callback box is about to be freed, so make sure no run loop can
+        // deliver another event to it.
+        unsafe { CFRunLoopSourceInvalidate(AXObserverGetRunLoopSource(self.observer.0)) }
+    }
+}
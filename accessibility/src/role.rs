@@ -0,0 +1,101 @@
+use std::{convert::Infallible, fmt, str::FromStr};
+
+macro_rules! define_roles {
+    ($(#[$meta:meta])* $enum:ident, $($(#[$vmeta:meta])* ($variant:ident, $const:ident)),*,) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub enum $enum {
+            $(
+                $(#[$vmeta])*
+                $variant,
+            )*
+            /// A value not listed in `spec/accessibility.toml`. This is not the
+            /// listed `AXUnknown` value, which elements report when they have
+            /// no more specific role.
+            Other(String),
+        }
+
+        impl AsRef<str> for $enum {
+            fn as_ref(&self) -> &str {
+                match self {
+                    $($enum::$variant => $const,)*
+                    $enum::Other(value) => value,
+                }
+            }
+        }
+
+        impl FromStr for $enum {
+            type Err = Infallible;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                #[allow(non_upper_case_globals)]
+                Ok(match s {
+                    $($const => $enum::$variant,)*
+                    other => $enum::Other(other.to_owned()),
+                })
+            }
+        }
+
+        impl From<&str> for $enum {
+            fn from(s: &str) -> Self {
+                match s.parse() {
+                    Ok(value) => value,
+                    Err(never) => match never {},
+                }
+            }
+        }
+
+        impl fmt::Display for $enum {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_ref())
+            }
+        }
+    };
+}
+
+include!("generated/roles.rs");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roles_round_trip() {
+        let roles = [
+            (Role::Button, "AXButton"),
+            (Role::Window, "AXWindow"),
+            (Role::UnknownRole, "AXUnknown"),
+            (Role::Other("AXWebArea".to_owned()), "AXWebArea"),
+        ];
+
+        for (role, name) in roles.iter() {
+            assert_eq!(name.parse::<Role>().unwrap(), *role);
+            assert_eq!(Role::from(*name), *role);
+            assert_eq!(role.to_string(), *name);
+            assert_eq!(role.as_ref(), *name);
+        }
+    }
+
+    #[test]
+    fn subroles_round_trip() {
+        let subroles = [
+            (Subrole::CloseButton, "AXCloseButton"),
+            (Subrole::StandardWindow, "AXStandardWindow"),
+            (Subrole::UnknownSubrole, "AXUnknown"),
+            (Subrole::Other("AXTabButton".to_owned()), "AXTabButton"),
+        ];
+
+        for (subrole, name) in subroles.iter() {
+            assert_eq!(name.parse::<Subrole>().unwrap(), *subrole);
+            assert_eq!(Subrole::from(*name), *subrole);
+            assert_eq!(subrole.to_string(), *name);
+            assert_eq!(subrole.as_ref(), *name);
+        }
+    }
+
+    #[test]
+    fn other_keeps_the_value() {
+        assert_eq!(Role::from(""), Role::Other(String::new()));
+        assert_eq!(Role::from("axbutton"), Role::Other("axbutton".to_owned()));
+    }
+}
@@ -0,0 +1,640 @@
+use std::{fmt, str::FromStr};
+
+use accessibility_sys::{kAXIdentifierAttribute, kAXRoleAttribute};
+use core_foundation::string::CFString;
+use thiserror::Error as TError;
+
+use crate::{AXAny, AXAttribute, AXUIElement, SnapshotNode};
+
+/// A CSS-like element selector, e.g. `AXWindow > AXButton[AXTitle="OK"]`.
+///
+/// Each step names a role (or `*` for any role) followed by any number of
+/// conditions: `#id` matches `AXIdentifier`, `[AXEnabled]` requires the
+/// attribute to be present and `[AXTitle=OK]` compares its value. Besides
+/// `=`, conditions can use `!=`, `*=` (contains), `^=` (starts with) and `$=`
+/// (ends with). Values may be quoted and are compared against the text of
+/// the decoded value, so booleans are `true` and `false`. Steps separated by
+/// whitespace match descendants; `>` matches direct children.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Selector {
+    steps: Vec<Step>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Step {
+    /// How this step relates to the previous one. Ignored for the first.
+    combinator: Combinator,
+    role: Option<String>,
+    conditions: Vec<Condition>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Condition {
+    attribute: String,
+    test: Option<(Operator, String)>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Operator {
+    Equals,
+    NotEquals,
+    Contains,
+    StartsWith,
+    EndsWith,
+}
+
+impl Operator {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Operator::Equals => "=",
+            Operator::NotEquals => "!=",
+            Operator::Contains => "*=",
+            Operator::StartsWith => "^=",
+            Operator::EndsWith => "$=",
+        }
+    }
+
+    fn test(&self, actual: &str, expected: &str) -> bool {
+        match self {
+            Operator::Equals => actual == expected,
+            Operator::NotEquals => actual != expected,
+            Operator::Contains => actual.contains(expected),
+            Operator::StartsWith => actual.starts_with(expected),
+            Operator::EndsWith => actual.ends_with(expected),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, TError)]
+#[error("invalid selector {selector:?} at offset {offset}: {message}")]
+pub struct ParseSelectorError {
+    pub selector: String,
+    pub offset: usize,
+    pub message: &'static str,
+}
+
+/// Something a [`Selector`] can be matched against.
+pub trait Selectable {
+    fn selector_attribute(&self, name: &str) -> Option<AXAny>;
+}
+
+impl Selectable for AXUIElement {
+    fn selector_attribute(&self, name: &str) -> Option<AXAny> {
+        self.attribute(&AXAttribute::new(&CFString::new(name)))
+            .ok()
+            .map(|v| AXAny::from(&v))
+    }
+}
+
+impl Selectable for SnapshotNode {
+    fn selector_attribute(&self, name: &str) -> Option<AXAny> {
+        self.attribute(name)
+    }
+}
+
+impl<T: Selectable> Selectable for &T {
+    fn selector_attribute(&self, name: &str) -> Option<AXAny> {
+        (*self).selector_attribute(name)
+    }
+}
+
+fn text(value: AXAny) -> String {
+    match value {
+        AXAny::String(s) => s,
+        value => value.to_string(),
+    }
+}
+
+impl Step {
+    fn matches(&self, target: &impl Selectable) -> bool {
+        let role_matches = match &self.role {
+            Some(role) => target
+                .selector_attribute(kAXRoleAttribute)
+                .is_some_and(|r| text(r) == *role),
+            None => true,
+        };
+
+        role_matches
+            && self.conditions.iter().all(|condition| {
+                let value = target.selector_attribute(&condition.attribute);
+
+                match (&condition.test, value) {
+                    (None, value) => value.is_some(),
+                    (Some((Operator::NotEquals, _)), None) => true,
+                    (Some(_), None) => false,
+                    (Some((op, expected)), Some(value)) => op.test(&text(value), expected),
+                }
+            })
+    }
+}
+
+impl Selector {
+    /// Whether the last element of `path` matches, given its ancestors. The
+    /// path runs from the root of the search down to the candidate.
+    pub fn matches_path<T: Selectable>(&self, path: &[T]) -> bool {
+        match path.len() {
+            0 => false,
+            n => self.matches_at(self.steps.len() - 1, path, n - 1),
+        }
+    }
+
+    fn matches_at<T: Selectable>(&self, step: usize, path: &[T], index: usize) -> bool {
+        if !self.steps[step].matches(&path[index]) {
+            return false;
+        }
+
+        if step == 0 {
+            return true;
+        }
+
+        match self.steps[step].combinator {
+            Combinator::Child => index > 0 && self.matches_at(step - 1, path, index - 1),
+            Combinator::Descendant => (0..index)
+                .rev()
+                .any(|ancestor| self.matches_at(step - 1, path, ancestor)),
+        }
+    }
+
+    /// Matches a live element, reading its ancestors through `AXParent` only
+    /// when the selector has more than one step.
+    pub fn matches_element(&self, element: &AXUIElement) -> bool {
+        let last = self.steps.last().expect("selectors have at least one step");
+
+        if !last.matches(element) {
+            return false;
+        }
+
+        if self.steps.len() == 1 {
+            return true;
+        }
+
+        self.matches_path(&element.ancestry())
+    }
+
+    /// Every node below and including `root` that matches, in pre-order.
+    pub fn select<'a>(&self, root: &'a SnapshotNode) -> Vec<&'a SnapshotNode> {
+        let mut path: Vec<&SnapshotNode> = Vec::new();
+        let mut found = Vec::new();
+
+        root.walk(&mut |node, depth| {
+            path.truncate(depth);
+            path.push(node);
+
+            if self.matches_path(&path) {
+                found.push(node);
+            }
+        });
+        found
+    }
+}
+
+impl FromStr for Selector {
+    type Err = ParseSelectorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Parser {
+            input: s,
+            offset: 0,
+        }
+        .selector()
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    offset: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error<T>(&self, message: &'static str) -> Result<T, ParseSelectorError> {
+        Err(ParseSelectorError {
+            selector: self.input.to_owned(),
+            offset: self.offset,
+            message,
+        })
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.offset..].chars().next()
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        if self.input[self.offset..].starts_with(s) {
+            self.offset += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.offset;
+
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.offset += c.len_utf8();
+        }
+
+        self.offset > start
+    }
+
+    fn name(&mut self) -> &'a str {
+        let start = self.offset;
+
+        while let Some(c) = self.peek() {
+            if c.is_alphanumeric() || c == '_' || c == '-' || c == '.' {
+                self.offset += c.len_utf8();
+            } else {
+                break;
+            }
+        }
+
+        &self.input[start..self.offset]
+    }
+
+    fn selector(mut self) -> Result<Selector, ParseSelectorError> {
+        let mut steps = Vec::new();
+        let mut combinator = Combinator::Descendant;
+
+        self.skip_whitespace();
+
+        loop {
+            steps.push(self.step(combinator)?);
+
+            let spaced = self.skip_whitespace();
+
+            if self.peek().is_none() {
+                break;
+            } else if self.eat(">") {
+                self.skip_whitespace();
+                combinator = Combinator::Child;
+            } else if spaced {
+                combinator = Combinator::Descendant;
+            } else {
+                return self.error("unexpected character");
+            }
+        }
+
+        Ok(Selector { steps })
+    }
+
+    fn step(&mut self, combinator: Combinator) -> Result<Step, ParseSelectorError> {
+        let star = self.eat("*");
+        let role = if star {
+            None
+        } else {
+            Some(self.name().to_owned()).filter(|r| !r.is_empty())
+        };
+        let mut conditions = Vec::new();
+
+        loop {
+            if self.eat("#") {
+                let id = self.name();
+
+                if id.is_empty() {
+                    return self.error("expected an identifier after #");
+                }
+
+                conditions.push(Condition {
+                    attribute: kAXIdentifierAttribute.to_owned(),
+                    test: Some((Operator::Equals, id.to_owned())),
+                });
+            } else if self.eat("[") {
+                conditions.push(self.condition()?);
+            } else {
+                break;
+            }
+        }
+
+        if !star && role.is_none() && conditions.is_empty() {
+            return self.error("expected a role, *, #id or [condition]");
+        }
+
+        Ok(Step {
+            combinator,
+            role,
+            conditions,
+        })
+    }
+
+    fn condition(&mut self) -> Result<Condition, ParseSelectorError> {
+        self.skip_whitespace();
+
+        let attribute = self.name().to_owned();
+
+        if attribute.is_empty() {
+            return self.error("expected an attribute name");
+        }
+
+        self.skip_whitespace();
+
+        if self.eat("]") {
+            return Ok(Condition {
+                attribute,
+                test: None,
+            });
+        }
+
+        let operators = [
+            Operator::NotEquals,
+            Operator::Contains,
+            Operator::StartsWith,
+            Operator::EndsWith,
+            Operator::Equals,
+        ];
+        let op = match operators.iter().find(|op| self.eat(op.as_str())) {
+            Some(op) => *op,
+            None => return self.error("expected ], =, !=, *=, ^= or $="),
+        };
+
+        self.skip_whitespace();
+
+        let value = self.value()?;
+
+        self.skip_whitespace();
+
+        if !self.eat("]") {
+            return self.error("expected ]");
+        }
+
+        Ok(Condition {
+            attribute,
+            test: Some((op, value)),
+        })
+    }
+
+    fn value(&mut self) -> Result<String, ParseSelectorError> {
+        let quote = match self.peek() {
+            Some(q @ '"') | Some(q @ '\'') => q,
+            _ => {
+                let start = self.offset;
+
+                while self.peek().is_some_and(|c| c != ']') {
+                    self.offset += self.peek().unwrap().len_utf8();
+                }
+
+                return Ok(self.input[start..self.offset].trim_end().to_owned());
+            }
+        };
+        let mut value = String::new();
+
+        self.offset += 1;
+
+        loop {
+            match self.peek() {
+                None => return self.error("unterminated string"),
+                Some('\\') => {
+                    self.offset += 1;
+
+                    match self.peek() {
+                        Some(c) => {
+                            value.push(c);
+                            self.offset += c.len_utf8();
+                        }
+                        None => return self.error("unterminated string"),
+                    }
+                }
+                Some(c) if c == quote => {
+                    self.offset += 1;
+                    return Ok(value);
+                }
+                Some(c) => {
+                    value.push(c);
+                    self.offset += c.len_utf8();
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                match step.combinator {
+                    Combinator::Descendant => write!(f, " ")?,
+                    Combinator::Child => write!(f, " > ")?,
+                }
+            }
+
+            match &step.role {
+                Some(role) => write!(f, "{}", role)?,
+                None if step.conditions.is_empty() => write!(f, "*")?,
+                None => {}
+            }
+
+            for condition in &step.conditions {
+                match &condition.test {
+                    None => write!(f, "[{}]", condition.attribute)?,
+                    Some((op, value)) => write!(
+                        f,
+                        "[{}{}\"{}\"]",
+                        condition.attribute,
+                        op.as_str(),
+                        value.replace('\\', "\\\\").replace('"', "\\\"")
+                    )?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(role: &str, title: Option<&str>, children: Vec<SnapshotNode>) -> SnapshotNode {
+        SnapshotNode {
+            role: role.to_owned(),
+            title: title.map(str::to_owned),
+            children,
+            ..Default::default()
+        }
+    }
+
+    /// An application with a window holding a group of two buttons, one of
+    /// them disabled, and a third button directly.
+    fn tree() -> SnapshotNode {
+        let mut cancel = node("AXButton", Some("Cancel"), vec![]);
+
+        cancel
+            .attributes
+            .insert("AXEnabled".to_owned(), AXAny::Bool(false));
+
+        let save = SnapshotNode {
+            identifier: Some("save".to_owned()),
+            ..node("AXButton", Some("Save As"), vec![])
+        };
+        let group = node("AXGroup", None, vec![save, cancel]);
+        let window = node(
+            "AXWindow",
+            Some("Main"),
+            vec![group, node("AXButton", Some("Help"), vec![])],
+        );
+
+        node("AXApplication", Some("App"), vec![window])
+    }
+
+    fn select(selector: &str) -> Vec<String> {
+        let tree = tree();
+        let selector: Selector = selector.parse().unwrap();
+
+        selector
+            .select(&tree)
+            .iter()
+            .map(|node| node.label())
+            .collect()
+    }
+
+    fn error(selector: &str) -> (usize, &'static str) {
+        let e = selector.parse::<Selector>().unwrap_err();
+
+        (e.offset, e.message)
+    }
+
+    #[test]
+    fn parses_steps() {
+        assert_eq!(select("*").len(), 6);
+        assert_eq!(select("#save"), ["AXButton #save"]);
+        assert_eq!(select("[AXEnabled]"), ["AXButton \"Cancel\""]);
+        assert_eq!(
+            select("AXButton"),
+            ["AXButton #save", "AXButton \"Cancel\"", "AXButton \"Help\""]
+        );
+        assert_eq!(select("  AXWindow  "), ["AXWindow \"Main\""]);
+    }
+
+    #[test]
+    fn operators() {
+        assert_eq!(select("[AXTitle=Save As]"), ["AXButton #save"]);
+        assert_eq!(select("[AXTitle = 'Save As' ]"), ["AXButton #save"]);
+        assert_eq!(
+            select("AXButton[AXTitle!=Help]"),
+            ["AXButton #save", "AXButton \"Cancel\""]
+        );
+        assert_eq!(
+            select("[AXTitle*=a]"),
+            ["AXWindow \"Main\"", "AXButton #save", "AXButton \"Cancel\""]
+        );
+        assert_eq!(select("[AXTitle^=Ca]"), ["AXButton \"Cancel\""]);
+        assert_eq!(
+            select("[AXTitle$=p]"),
+            ["AXApplication \"App\"", "AXButton \"Help\""]
+        );
+    }
+
+    #[test]
+    fn quoted_values() {
+        let value = |selector: &str| {
+            let selector: Selector = selector.parse().unwrap();
+
+            selector.steps[0].conditions[0].test.clone().unwrap().1
+        };
+
+        assert_eq!(value(r#"[AXTitle="say \"hi\""]"#), r#"say "hi""#);
+        assert_eq!(value(r#"[AXTitle='it\'s']"#), "it's");
+        assert_eq!(value(r#"[AXTitle="a\\b"]"#), r"a\b");
+        assert_eq!(value(r#"[AXTitle="a]b"]"#), "a]b");
+        assert_eq!(value("[AXTitle=\"two\nlines\"]"), "two\nlines");
+    }
+
+    #[test]
+    fn error_offsets() {
+        let role = "expected a role, *, #id or [condition]";
+
+        assert_eq!(error(""), (0, role));
+        assert_eq!(error("AXWindow > "), (11, role));
+        assert_eq!(error("AXButton)"), (8, "unexpected character"));
+        assert_eq!(error("#"), (1, "expected an identifier after #"));
+        assert_eq!(error("AXButton["), (9, "expected an attribute name"));
+        assert_eq!(
+            error("AXButton[AXTitle"),
+            (16, "expected ], =, !=, *=, ^= or $=")
+        );
+        assert_eq!(error("AXButton[AXTitle=OK"), (19, "expected ]"));
+        assert_eq!(error("AXButton[AXTitle=\"OK"), (20, "unterminated string"));
+        assert_eq!(error("[AXTitle=\"OK\" x]"), (14, "expected ]"));
+    }
+
+    #[test]
+    fn child_and_descendant() {
+        assert_eq!(select("AXWindow > AXButton"), ["AXButton \"Help\""]);
+        assert_eq!(
+            select("AXWindow AXButton"),
+            ["AXButton #save", "AXButton \"Cancel\"", "AXButton \"Help\""]
+        );
+        assert_eq!(
+            select("AXApplication > AXWindow > AXGroup > #save"),
+            ["AXButton #save"]
+        );
+        assert!(select("AXApplication > AXButton").is_empty());
+        assert!(select("AXButton AXWindow").is_empty());
+    }
+
+    #[test]
+    fn descendant_backtracks() {
+        // The nearest group ancestor of the button is Leaf, whose parent
+        // isn't Outer; only trying the next group up finds the match.
+        let tree = node(
+            "AXGroup",
+            Some("Outer"),
+            vec![node(
+                "AXGroup",
+                Some("Inner"),
+                vec![node(
+                    "AXGroup",
+                    Some("Leaf"),
+                    vec![node("AXButton", Some("OK"), vec![])],
+                )],
+            )],
+        );
+        let selector: Selector = "AXGroup[AXTitle=Outer] > AXGroup AXButton".parse().unwrap();
+
+        assert_eq!(selector.select(&tree).len(), 1);
+    }
+
+    #[test]
+    fn not_equals_matches_missing_attributes() {
+        assert_eq!(
+            select("AXButton[AXEnabled!=false]"),
+            ["AXButton #save", "AXButton \"Help\""]
+        );
+        assert!(select("AXButton[AXEnabled=true]").is_empty());
+        assert!(select("AXButton[AXHelp*=x]").is_empty());
+    }
+
+    #[test]
+    fn display_round_trips() {
+        let selectors = [
+            "*",
+            "AXWindow > AXButton[AXTitle=\"OK\"]",
+            "AXGroup #save",
+            "[AXEnabled][AXTitle!=\"\"]",
+            "* > AXButton[AXTitle*=\"a\"][AXTitle^=\"b\"][AXTitle$=\"c\"]",
+            r#"[AXTitle="say \"hi\" \\ bye"]"#,
+            "[AXTitle=\"two\nlines \u{e9}\"]",
+        ];
+
+        for text in selectors.iter() {
+            let selector: Selector = text.parse().unwrap();
+            let printed = selector.to_string();
+
+            assert_eq!(printed.parse::<Selector>(), Ok(selector), "{}", printed);
+        }
+
+        assert_eq!(
+            "AXWindow>AXButton#ok"
+                .parse::<Selector>()
+                .unwrap()
+                .to_string(),
+            "AXWindow > AXButton[AXIdentifier=\"ok\"]"
+        );
+    }
+}
@@ -0,0 +1,543 @@
+use std::{fmt, str::FromStr};
+
+use accessibility_sys::{
+    kAXMenuItemModifierControl, kAXMenuItemModifierNoCommand, kAXMenuItemModifierOption,
+    kAXMenuItemModifierShift,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error as TError;
+
+use crate::{AXUIElement, AXUIElementAttributes};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Modifiers {
+    pub control: bool,
+    pub option: bool,
+    pub shift: bool,
+    pub command: bool,
+}
+
+impl Modifiers {
+    /// Decodes an `AXMenuItemCmdModifiers` value. Command is implied unless
+    /// `kAXMenuItemModifierNoCommand` is set.
+    pub fn from_ax(bits: u32) -> Self {
+        Self {
+            control: bits & kAXMenuItemModifierControl != 0,
+            option: bits & kAXMenuItemModifierOption != 0,
+            shift: bits & kAXMenuItemModifierShift != 0,
+            command: bits & kAXMenuItemModifierNoCommand == 0,
+        }
+    }
+
+    pub fn to_ax(&self) -> u32 {
+        let mut bits = 0;
+
+        if self.control {
+            bits |= kAXMenuItemModifierControl;
+        }
+        if self.option {
+            bits |= kAXMenuItemModifierOption;
+        }
+        if self.shift {
+            bits |= kAXMenuItemModifierShift;
+        }
+        if !self.command {
+            bits |= kAXMenuItemModifierNoCommand;
+        }
+
+        bits
+    }
+
+    fn flags(&self) -> [(bool, &'static str, &'static str); 4] {
+        [
+            (self.control, "⌃", "Ctrl"),
+            (self.option, "⌥", "Option"),
+            (self.shift, "⇧", "Shift"),
+            (self.command, "⌘", "Cmd"),
+        ]
+    }
+}
+
+/// Keys that menus draw with a glyph rather than a character.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum NamedKey {
+    Tab,
+    BackTab,
+    Enter,
+    Return,
+    Space,
+    Delete,
+    ForwardDelete,
+    Escape,
+    Clear,
+    Left,
+    Right,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    Help,
+    CapsLock,
+    Power,
+    Eject,
+    F(u8),
+}
+
+impl NamedKey {
+    pub fn name(&self) -> String {
+        match self {
+            NamedKey::F(n) => format!("F{}", n),
+            other => format!("{:?}", other),
+        }
+    }
+
+    pub fn symbol(&self) -> String {
+        let symbol = match self {
+            NamedKey::Tab => "⇥",
+            NamedKey::BackTab => "⇤",
+            NamedKey::Enter => "⌤",
+            NamedKey::Return => "↩",
+            NamedKey::Space => "␣",
+            NamedKey::Delete => "⌫",
+            NamedKey::ForwardDelete => "⌦",
+            NamedKey::Escape => "⎋",
+            NamedKey::Clear => "⌧",
+            NamedKey::Left => "←",
+            NamedKey::Right => "→",
+            NamedKey::Up => "↑",
+            NamedKey::Down => "↓",
+            NamedKey::PageUp => "⇞",
+            NamedKey::PageDown => "⇟",
+            NamedKey::Home => "↖",
+            NamedKey::End => "↘",
+            NamedKey::Help => "?⃝",
+            NamedKey::CapsLock => "⇪",
+            NamedKey::Power => "⌽",
+            NamedKey::Eject => "⏏",
+            NamedKey::F(_) => return self.name(),
+        };
+
+        symbol.to_owned()
+    }
+
+    /// Looks a key up by name (case-insensitive) or symbol.
+    pub fn parse(text: &str) -> Option<Self> {
+        let lower = text.to_lowercase();
+
+        if let Some(n) = lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+            return if (1..=20).contains(&n) {
+                Some(NamedKey::F(n))
+            } else {
+                None
+            };
+        }
+
+        let aliases = [
+            ("esc", NamedKey::Escape),
+            ("backspace", NamedKey::Delete),
+            ("del", NamedKey::ForwardDelete),
+            ("pgup", NamedKey::PageUp),
+            ("pgdn", NamedKey::PageDown),
+            ("leftarrow", NamedKey::Left),
+            ("rightarrow", NamedKey::Right),
+            ("uparrow", NamedKey::Up),
+            ("downarrow", NamedKey::Down),
+        ];
+
+        GLYPHS
+            .iter()
+            .map(|(_, key)| *key)
+            .find(|key| key.name().to_lowercase() == lower || key.symbol() == text)
+            .or_else(|| {
+                aliases
+                    .iter()
+                    .find(|(alias, _)| *alias == lower)
+                    .map(|(_, key)| *key)
+            })
+    }
+}
+
+/// `AXMenuItemCmdGlyph` codes, from the Carbon menu glyph constants.
+pub const GLYPHS: &[(u32, NamedKey)] = &[
+    (0x02, NamedKey::Tab),
+    (0x03, NamedKey::BackTab),
+    (0x04, NamedKey::Enter),
+    (0x09, NamedKey::Space),
+    (0x0A, NamedKey::ForwardDelete),
+    (0x0B, NamedKey::Return),
+    (0x0C, NamedKey::Return),
+    (0x17, NamedKey::Delete),
+    (0x1B, NamedKey::Escape),
+    (0x1C, NamedKey::Clear),
+    (0x62, NamedKey::PageUp),
+    (0x63, NamedKey::CapsLock),
+    (0x64, NamedKey::Left),
+    (0x65, NamedKey::Right),
+    (0x66, NamedKey::Home),
+    (0x67, NamedKey::Help),
+    (0x68, NamedKey::Up),
+    (0x69, NamedKey::End),
+    (0x6A, NamedKey::Down),
+    (0x6B, NamedKey::PageDown),
+    (0x6E, NamedKey::Power),
+    (0x6F, NamedKey::F(1)),
+    (0x70, NamedKey::F(2)),
+    (0x71, NamedKey::F(3)),
+    (0x72, NamedKey::F(4)),
+    (0x73, NamedKey::F(5)),
+    (0x74, NamedKey::F(6)),
+    (0x75, NamedKey::F(7)),
+    (0x76, NamedKey::F(8)),
+    (0x77, NamedKey::F(9)),
+    (0x78, NamedKey::F(10)),
+    (0x79, NamedKey::F(11)),
+    (0x7A, NamedKey::F(12)),
+    (0x87, NamedKey::F(13)),
+    (0x88, NamedKey::F(14)),
+    (0x89, NamedKey::F(15)),
+    (0x8C, NamedKey::Eject),
+    (0x8F, NamedKey::F(16)),
+    (0x90, NamedKey::F(17)),
+    (0x91, NamedKey::F(18)),
+    (0x92, NamedKey::F(19)),
+];
+
+/// Virtual key codes (`kVK_*`) of keys that have no character.
+const VIRTUAL_KEYS: &[(u32, NamedKey)] = &[
+    (0x24, NamedKey::Return),
+    (0x30, NamedKey::Tab),
+    (0x31, NamedKey::Space),
+    (0x33, NamedKey::Delete),
+    (0x35, NamedKey::Escape),
+    (0x40, NamedKey::F(17)),
+    (0x47, NamedKey::Clear),
+    (0x4C, NamedKey::Enter),
+    (0x4F, NamedKey::F(18)),
+    (0x50, NamedKey::F(19)),
+    (0x5A, NamedKey::F(20)),
+    (0x60, NamedKey::F(5)),
+    (0x61, NamedKey::F(6)),
+    (0x62, NamedKey::F(7)),
+    (0x63, NamedKey::F(3)),
+    (0x64, NamedKey::F(8)),
+    (0x65, NamedKey::F(9)),
+    (0x67, NamedKey::F(11)),
+    (0x69, NamedKey::F(13)),
+    (0x6A, NamedKey::F(16)),
+    (0x6B, NamedKey::F(14)),
+    (0x6D, NamedKey::F(10)),
+    (0x6F, NamedKey::F(12)),
+    (0x71, NamedKey::F(15)),
+    (0x72, NamedKey::Help),
+    (0x73, NamedKey::Home),
+    (0x74, NamedKey::PageUp),
+    (0x75, NamedKey::ForwardDelete),
+    (0x76, NamedKey::F(4)),
+    (0x77, NamedKey::End),
+    (0x78, NamedKey::F(2)),
+    (0x79, NamedKey::PageDown),
+    (0x7A, NamedKey::F(1)),
+    (0x7B, NamedKey::Left),
+    (0x7C, NamedKey::Right),
+    (0x7D, NamedKey::Down),
+    (0x7E, NamedKey::Up),
+];
+
+pub fn glyph_key(glyph: u32) -> Option<NamedKey> {
+    GLYPHS
+        .iter()
+        .find(|(g, _)| *g == glyph)
+        .map(|(_, key)| *key)
+}
+
+pub fn virtual_key(code: u32) -> Option<NamedKey> {
+    VIRTUAL_KEYS
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map(|(_, key)| *key)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Key {
+    /// A printable key, stored in upper case.
+    Character(String),
+    Named(NamedKey),
+    /// A glyph code missing from [`GLYPHS`].
+    Glyph(u32),
+    /// A virtual key code with no character and no known name.
+    VirtualKey(u32),
+}
+
+impl Key {
+    fn symbol(&self) -> String {
+        match self {
+            Key::Character(c) => c.clone(),
+            Key::Named(key) => key.symbol(),
+            Key::Glyph(g) => format!("<glyph {:#x}>", g),
+            Key::VirtualKey(k) => format!("<key {:#x}>", k),
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            Key::Named(key) => key.name(),
+            other => other.symbol(),
+        }
+    }
+}
+
+/// A menu item's keyboard shortcut.
+///
+/// `Display` renders it the way menus do (`⇧⌘S`); the alternate form (`{:#}`)
+/// spells it out (`Cmd+Shift+S`). Both forms parse back with `FromStr`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Shortcut {
+    pub key: Key,
+    pub modifiers: Modifiers,
+}
+
+impl Shortcut {
+    /// Decodes the raw `AXMenuItemCmd*` attribute values. Returns `None` for
+    /// items without a shortcut.
+    pub fn from_ax(
+        cmd_char: Option<&str>,
+        virtual_key_code: Option<u32>,
+        glyph: Option<u32>,
+        modifiers: Option<u32>,
+    ) -> Option<Self> {
+        let key = if let Some(glyph) = glyph.filter(|g| *g != 0) {
+            glyph_key(glyph).map_or(Key::Glyph(glyph), Key::Named)
+        } else if let Some(c) = cmd_char.map(str::trim).filter(|c| !c.is_empty()) {
+            Key::Character(c.to_uppercase())
+        } else if let Some(code) = virtual_key_code {
+            virtual_key(code).map_or(Key::VirtualKey(code), Key::Named)
+        } else {
+            return None;
+        };
+
+        Some(Self {
+            key,
+            modifiers: Modifiers::from_ax(modifiers.unwrap_or_default()),
+        })
+    }
+
+    /// Reads and decodes the shortcut of a menu item.
+    pub fn from_element(item: &AXUIElement) -> Option<Self> {
+        let number = |n: Result<core_foundation::number::CFNumber, _>| {
+            n.ok().and_then(|n| n.to_i64()).map(|n| n as u32)
+        };
+
+        Self::from_ax(
+            item.menu_item_cmd_char()
+                .ok()
+                .map(|c| c.to_string())
+                .as_deref(),
+            number(item.menu_item_cmd_virtual_key()),
+            number(item.menu_item_cmd_glyph()),
+            number(item.menu_item_cmd_modifiers()),
+        )
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flags = self.modifiers.flags();
+
+        if f.alternate() {
+            // Spelled out with Cmd first, the way documentation writes it.
+            for i in [3, 2, 1, 0].iter() {
+                if flags[*i].0 {
+                    write!(f, "{}+", flags[*i].2)?;
+                }
+            }
+
+            write!(f, "{}", self.key.name())
+        } else {
+            for (set, symbol, _) in flags.iter() {
+                if *set {
+                    f.write_str(symbol)?;
+                }
+            }
+
+            write!(f, "{}", self.key.symbol())
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, TError)]
+#[error("invalid shortcut {0:?}")]
+pub struct ParseShortcutError(pub String);
+
+impl FromStr for Shortcut {
+    type Err = ParseShortcutError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseShortcutError(s.to_owned());
+        let mut modifiers = Modifiers::default();
+        let mut rest = s.trim();
+
+        // Leading modifier symbols: "⇧⌘S".
+        while let Some(c) = rest.chars().next() {
+            if !set_modifier(&mut modifiers, &c.to_string()) {
+                break;
+            }
+
+            rest = &rest[c.len_utf8()..];
+        }
+
+        // Spelled-out modifiers: "Cmd+Shift+S". A trailing separator is the
+        // key itself, as in "Cmd++".
+        loop {
+            let split = rest
+                .char_indices()
+                .find(|(i, c)| (*c == '+' || *c == '-') && *i > 0)
+                .map(|(i, _)| i);
+
+            match split {
+                Some(i) if set_modifier(&mut modifiers, &rest[..i]) => rest = &rest[i + 1..],
+                _ => break,
+            }
+        }
+
+        if rest.is_empty() {
+            return Err(err());
+        }
+
+        let key = if let Some(key) = parse_raw_key(rest) {
+            key
+        } else if rest.chars().count() == 1 && NamedKey::parse(rest).is_none() {
+            Key::Character(rest.to_uppercase())
+        } else {
+            Key::Named(NamedKey::parse(rest).ok_or_else(err)?)
+        };
+
+        Ok(Self { key, modifiers })
+    }
+}
+
+/// Parses the `<glyph 0x..>` and `<key 0x..>` forms `Display` uses for
+/// unknown codes.
+fn parse_raw_key(text: &str) -> Option<Key> {
+    let inner = text.strip_prefix('<')?.strip_suffix('>')?;
+    let (kind, code) = inner.split_once(' ')?;
+    let code = u32::from_str_radix(code.trim_start_matches("0x"), 16).ok()?;
+
+    match kind {
+        "glyph" => Some(Key::Glyph(code)),
+        "key" => Some(Key::VirtualKey(code)),
+        _ => None,
+    }
+}
+
+fn set_modifier(modifiers: &mut Modifiers, name: &str) -> bool {
+    match name.trim().to_lowercase().as_str() {
+        "⌘" | "cmd" | "command" => modifiers.command = true,
+        "⇧" | "shift" => modifiers.shift = true,
+        "⌥" | "opt" | "option" | "alt" => modifiers.option = true,
+        "⌃" | "ctrl" | "control" => modifiers.control = true,
+        _ => return false,
+    }
+
+    true
+}
+
+impl Serialize for Shortcut {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Shortcut {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shortcut(glyph: u32, modifiers: u32) -> Shortcut {
+        Shortcut::from_ax(None, None, Some(glyph), Some(modifiers)).unwrap()
+    }
+
+    #[test]
+    fn function_key_glyphs() {
+        let expected = [
+            (0x6F, 1),
+            (0x7A, 12),
+            (0x87, 13),
+            (0x88, 14),
+            (0x89, 15),
+            (0x8F, 16),
+            (0x90, 17),
+            (0x91, 18),
+            (0x92, 19),
+        ];
+
+        for (glyph, n) in expected.iter() {
+            assert_eq!(
+                glyph_key(*glyph),
+                Some(NamedKey::F(*n)),
+                "glyph {:#x}",
+                glyph
+            );
+        }
+    }
+
+    #[test]
+    fn arrow_and_kana_codes_are_not_function_keys() {
+        // 0x7B-0x7D are arrow virtual key codes, not glyphs, and 0x8E is
+        // kMenuKanaGlyph.
+        assert_eq!(virtual_key(0x7B), Some(NamedKey::Left));
+        assert_eq!(virtual_key(0x7C), Some(NamedKey::Right));
+        assert_eq!(virtual_key(0x7D), Some(NamedKey::Down));
+        assert_eq!(glyph_key(0x7B), None);
+        assert_eq!(glyph_key(0x8E), None);
+        assert_eq!(shortcut(0x8E, 0).key, Key::Glyph(0x8E));
+    }
+
+    #[test]
+    fn display_forms_round_trip() {
+        let glyphs = GLYPHS.iter().map(|(glyph, _)| *glyph).chain(Some(0x8E));
+        let modifiers = [
+            0,
+            kAXMenuItemModifierShift,
+            kAXMenuItemModifierOption | kAXMenuItemModifierControl,
+            kAXMenuItemModifierNoCommand | kAXMenuItemModifierShift,
+        ];
+
+        for glyph in glyphs {
+            for bits in modifiers.iter() {
+                let shortcut = shortcut(glyph, *bits);
+
+                assert_eq!(shortcut.to_string().parse(), Ok(shortcut.clone()));
+                assert_eq!(format!("{:#}", shortcut).parse(), Ok(shortcut.clone()));
+            }
+        }
+    }
+
+    #[test]
+    fn function_keys_display_by_name() {
+        let f16 = shortcut(0x8F, kAXMenuItemModifierShift);
+
+        assert_eq!(f16.to_string(), "⇧⌘F16");
+        assert_eq!(format!("{:#}", f16), "Cmd+Shift+F16");
+        assert_eq!("Cmd+Shift+F16".parse(), Ok(f16));
+    }
+
+    #[test]
+    fn characters_and_virtual_keys() {
+        let save = Shortcut::from_ax(Some("s"), None, None, Some(kAXMenuItemModifierShift));
+        let unknown = Shortcut::from_ax(None, Some(0x99), None, Some(0));
+
+        assert_eq!(save.clone().unwrap().to_string(), "⇧⌘S");
+        assert_eq!("⇧⌘S".parse().ok(), save);
+        assert_eq!(unknown.clone().unwrap().to_string(), "⌘<key 0x99>");
+        assert_eq!("⌘<key 0x99>".parse().ok(), unknown);
+    }
+}
@@ -0,0 +1,533 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    fmt, mem,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use accessibility_sys::{
+    kAXChildrenAttribute, kAXErrorNoValue, kAXFrameAttribute, kAXIdentifierAttribute,
+    kAXRoleAttribute, kAXSubroleAttribute, kAXTitleAttribute, kAXValueAttribute,
+};
+use core_graphics_types::geometry::{CGPoint, CGRect, CGSize};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AXAny, AXAttribute, AXUIElement, AXUIElementAttributes, Error, TreeVisitor, TreeWalker,
+    TreeWalkerFlow, MAX_DEPTH,
+};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Frame {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl From<CGRect> for Frame {
+    fn from(rect: CGRect) -> Self {
+        Self {
+            x: rect.origin.x,
+            y: rect.origin.y,
+            width: rect.size.width,
+            height: rect.size.height,
+        }
+    }
+}
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}) {}x{}", self.x, self.y, self.width, self.height)
+    }
+}
+
+impl From<Frame> for CGRect {
+    fn from(frame: Frame) -> Self {
+        CGRect::new(
+            &CGPoint::new(frame.x, frame.y),
+            &CGSize::new(frame.width, frame.height),
+        )
+    }
+}
+
+/// A detached copy of an element's attributes and, optionally, its subtree.
+///
+/// The attributes most code cares about are lifted into their own fields;
+/// everything else the element reports lives in `attributes`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotNode {
+    pub role: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subrole: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<AXAny>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame: Option<Frame>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub attributes: BTreeMap<String, AXAny>,
+    /// Attributes the element lists but failed to return, with the error.
+    /// Only filled in by [`SnapshotBuilder::recording_errors`].
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub errors: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<SnapshotNode>,
+}
+
+impl SnapshotNode {
+    /// Reads the attributes of `element` without descending into children.
+    pub fn read(element: &AXUIElement) -> Self {
+        Self::read_with(element, false)
+    }
+
+    fn read_with(element: &AXUIElement, record_errors: bool) -> Self {
+        let mut node = SnapshotNode {
+            role: element.role().map(|r| r.to_string()).unwrap_or_default(),
+            subrole: element.subrole().ok().map(|s| s.to_string()),
+            title: element.title().ok().map(|s| s.to_string()),
+            identifier: element.identifier().ok().map(|s| s.to_string()),
+            value: element.value().ok().map(|v| AXAny::from(&v)),
+            frame: element.frame().ok().map(Frame::from),
+            ..Default::default()
+        };
+
+        if let Ok(names) = element.attribute_names() {
+            for name in names.into_iter() {
+                let key = name.to_string();
+                let lifted = is_lifted(&key);
+
+                // Lifted attributes were read above; they're only read again
+                // to find out why they failed.
+                if lifted && (!record_errors || node.attribute(&key).is_some()) {
+                    continue;
+                }
+
+                #[allow(non_upper_case_globals)]
+                match element.attribute(&AXAttribute::new(&name)) {
+                    Ok(value) if !lifted => {
+                        node.attributes.insert(key, AXAny::from(&value));
+                    }
+                    Ok(_) | Err(Error::Ax(kAXErrorNoValue)) => {}
+                    Err(e) if record_errors => {
+                        node.errors.insert(key, e.to_string());
+                    }
+                    Err(_) => {}
+                }
+            }
+        }
+
+        node
+    }
+
+    /// Captures `element` and its whole subtree.
+    pub fn capture(element: &AXUIElement) -> Self {
+        let builder = SnapshotBuilder::default();
+
+        TreeWalker::new().walk(element, &builder);
+        builder.finish().unwrap_or_default()
+    }
+
+    /// Returns a copy of this node without its children.
+    pub fn shallow(&self) -> Self {
+        Self {
+            children: Vec::new(),
+            ..self.clone()
+        }
+    }
+
+    /// Looks up an attribute by its accessibility name, including the lifted
+    /// ones.
+    pub fn attribute(&self, name: &str) -> Option<AXAny> {
+        #[allow(non_upper_case_globals)]
+        match name {
+            kAXRoleAttribute => Some(AXAny::from(self.role.as_str())),
+            kAXSubroleAttribute => self.subrole.as_deref().map(AXAny::from),
+            kAXTitleAttribute => self.title.as_deref().map(AXAny::from),
+            kAXIdentifierAttribute => self.identifier.as_deref().map(AXAny::from),
+            kAXValueAttribute => self.value.clone(),
+            kAXFrameAttribute => self.frame.map(AXAny::Rect),
+            _ => self.attributes.get(name).cloned(),
+        }
+    }
+
+    /// Every attribute of this node, including the lifted ones.
+    pub fn all_attributes(&self) -> BTreeMap<String, AXAny> {
+        let mut attributes = self.attributes.clone();
+        let lifted = [
+            kAXRoleAttribute,
+            kAXSubroleAttribute,
+            kAXTitleAttribute,
+            kAXIdentifierAttribute,
+            kAXValueAttribute,
+            kAXFrameAttribute,
+        ];
+
+        for name in lifted.iter() {
+            if let Some(value) = self.attribute(name) {
+                attributes.insert(name.to_string(), value);
+            }
+        }
+
+        attributes
+    }
+
+    /// Visits this node and its descendants in depth-first pre-order.
+    pub fn walk<'a>(&'a self, f: &mut impl FnMut(&'a SnapshotNode, usize)) {
+        self.walk_one(f, 0)
+    }
+
+    fn walk_one<'a>(&'a self, f: &mut impl FnMut(&'a SnapshotNode, usize), depth: usize) {
+        f(self, depth);
+
+        for child in &self.children {
+            child.walk_one(f, depth + 1);
+        }
+    }
+
+    /// A short human-readable label, e.g. `AXButton "OK"` or
+    /// `AXTextField #username`.
+    pub fn label(&self) -> String {
+        let mut label = self.role.clone();
+
+        if let Some(identifier) = &self.identifier {
+            label.push_str(&format!(" #{}", identifier));
+        } else if let Some(title) = self.title.as_ref().filter(|t| !t.is_empty()) {
+            label.push_str(&format!(" {:?}", title));
+        }
+
+        label
+    }
+
+    /// Number of nodes in this subtree, including this one.
+    pub fn node_count(&self) -> usize {
+        1 + self.children.iter().map(|c| c.node_count()).sum::<usize>()
+    }
+
+    /// Reshapes a captured tree the way [`MaxDepth`](crate::MaxDepth) and
+    /// [`Collapse`](crate::Collapse) shape a live walk: drops nodes more than
+    /// `max_depth` levels below this one, and replaces each node below it
+    /// that `collapse` picks with its children.
+    pub fn prune(&mut self, max_depth: usize, collapse: &impl Fn(&SnapshotNode) -> bool) {
+        self.children = prune(mem::take(&mut self.children), 1, max_depth, collapse);
+    }
+}
+
+fn prune(
+    nodes: Vec<SnapshotNode>,
+    depth: usize,
+    max_depth: usize,
+    collapse: &impl Fn(&SnapshotNode) -> bool,
+) -> Vec<SnapshotNode> {
+    if depth > max_depth {
+        return Vec::new();
+    }
+
+    let mut kept = Vec::new();
+
+    for mut node in nodes {
+        let children = prune(
+            mem::take(&mut node.children),
+            depth + 1,
+            max_depth,
+            collapse,
+        );
+
+        if collapse(&node) {
+            kept.extend(children);
+        } else {
+            node.children = children;
+            kept.push(node);
+        }
+    }
+
+    kept
+}
+
+fn is_lifted(name: &str) -> bool {
+    [
+        kAXRoleAttribute,
+        kAXSubroleAttribute,
+        kAXTitleAttribute,
+        kAXIdentifierAttribute,
+        kAXValueAttribute,
+        kAXFrameAttribute,
+        kAXChildrenAttribute,
+    ]
+    .contains(&name)
+}
+
+/// Which attributes a [`SnapshotBuilder`] keeps. The role is always kept.
+#[derive(Clone, Debug, Default)]
+pub struct AttributeFilter {
+    /// When not empty, only these attributes are kept.
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    /// Drop empty strings, arrays and dictionaries.
+    pub no_empty: bool,
+}
+
+impl AttributeFilter {
+    pub fn keeps(&self, name: &str, value: &AXAny) -> bool {
+        let empty = match value {
+            AXAny::String(s) | AXAny::AttributedString(s) => s.is_empty(),
+            AXAny::Array(items) => items.is_empty(),
+            AXAny::Dictionary(entries) => entries.is_empty(),
+            _ => false,
+        };
+
+        (self.include.is_empty() || self.include.iter().any(|n| n == name))
+            && !self.exclude.iter().any(|n| n == name)
+            && !(self.no_empty && empty)
+    }
+
+    /// Removes the attributes of `node` this filter doesn't keep, leaving
+    /// its children alone.
+    pub fn apply(&self, node: &mut SnapshotNode) {
+        let keeps = |name: &str, value: Option<AXAny>| value.is_some_and(|v| self.keeps(name, &v));
+
+        if !keeps(
+            kAXSubroleAttribute,
+            node.subrole.as_deref().map(AXAny::from),
+        ) {
+            node.subrole = None;
+        }
+
+        if !keeps(kAXTitleAttribute, node.title.as_deref().map(AXAny::from)) {
+            node.title = None;
+        }
+
+        if !keeps(
+            kAXIdentifierAttribute,
+            node.identifier.as_deref().map(AXAny::from),
+        ) {
+            node.identifier = None;
+        }
+
+        if !keeps(kAXValueAttribute, node.value.clone()) {
+            node.value = None;
+        }
+
+        if !keeps(kAXFrameAttribute, node.frame.map(AXAny::Rect)) {
+            node.frame = None;
+        }
+
+        node.attributes
+            .retain(|name, value| self.keeps(name, value));
+    }
+}
+
+/// A [`TreeVisitor`] that copies the elements it visits into a
+/// [`SnapshotNode`] tree. Wrap it in the adapters from
+/// [`visitor`](crate::visitor) to capture part of a tree.
+#[derive(Default)]
+pub struct SnapshotBuilder {
+    filter: AttributeFilter,
+    record_errors: bool,
+    stack: RefCell<Vec<SnapshotNode>>,
+    root: RefCell<Option<SnapshotNode>>,
+}
+
+impl SnapshotBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_filter(filter: AttributeFilter) -> Self {
+        Self {
+            filter,
+            ..Self::default()
+        }
+    }
+
+    /// Records the attributes each element fails to return in
+    /// [`SnapshotNode::errors`].
+    pub fn recording_errors(mut self) -> Self {
+        self.record_errors = true;
+        self
+    }
+
+    /// The captured tree, or `None` if nothing was visited.
+    pub fn finish(self) -> Option<SnapshotNode> {
+        self.root.into_inner()
+    }
+}
+
+impl TreeVisitor for SnapshotBuilder {
+    fn enter_element(&self, element: &AXUIElement) -> TreeWalkerFlow {
+        let mut stack = self.stack.borrow_mut();
+        let mut node = SnapshotNode::read_with(element, self.record_errors);
+
+        self.filter.apply(&mut node);
+        stack.push(node);
+
+        if stack.len() > MAX_DEPTH {
+            TreeWalkerFlow::SkipSubtree
+        } else {
+            TreeWalkerFlow::Continue
+        }
+    }
+
+    fn exit_element(&self, _element: &AXUIElement) {
+        let mut stack = self.stack.borrow_mut();
+        let node = stack.pop().expect("unbalanced tree walk");
+
+        match stack.last_mut() {
+            Some(parent) => parent.children.push(node),
+            None => *self.root.borrow_mut() = Some(node),
+        }
+    }
+}
+
+/// A snapshot with what's needed to make sense of it later: where and when
+/// it was captured, how long that took and which attributes failed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotFile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pid: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bundle_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Seconds since the Unix epoch when the capture started.
+    pub captured_at: f64,
+    /// How long the capture took, in seconds.
+    pub duration: f64,
+    pub node_count: usize,
+    pub root: SnapshotNode,
+}
+
+impl SnapshotFile {
+    /// Captures `element` and its whole subtree, recording attribute errors.
+    pub fn capture(element: &AXUIElement) -> Self {
+        let captured_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs_f64())
+            .unwrap_or_default();
+        let start = Instant::now();
+        let builder = SnapshotBuilder::new().recording_errors();
+
+        TreeWalker::new().walk(element, &builder);
+
+        let root = builder.finish().unwrap_or_default();
+        let duration = start.elapsed().as_secs_f64();
+        let pid = element.pid().ok().filter(|&pid| pid > 0);
+        let app = pid.and_then(|pid| {
+            AXUIElement::running_applications()
+                .into_iter()
+                .find(|app| app.pid == pid)
+        });
+
+        Self {
+            pid,
+            bundle_id: app.as_ref().and_then(|app| app.bundle_id.clone()),
+            name: app.and_then(|app| app.name),
+            captured_at,
+            duration,
+            node_count: root.node_count(),
+            root,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(role: &str, title: &str, children: Vec<SnapshotNode>) -> SnapshotNode {
+        SnapshotNode {
+            role: role.to_owned(),
+            title: Some(title.to_owned()),
+            children,
+            ..Default::default()
+        }
+    }
+
+    fn tree() -> SnapshotNode {
+        node(
+            "AXWindow",
+            "Window",
+            vec![
+                node(
+                    "AXGroup",
+                    "Outer",
+                    vec![node(
+                        "AXGroup",
+                        "Inner",
+                        vec![node("AXButton", "OK", vec![])],
+                    )],
+                ),
+                node("AXButton", "Cancel", vec![]),
+            ],
+        )
+    }
+
+    fn labels(node: &SnapshotNode) -> Vec<(usize, String)> {
+        let mut labels = Vec::new();
+
+        node.walk(&mut |n, depth| labels.push((depth, n.label())));
+        labels
+    }
+
+    #[test]
+    fn prune_limits_depth() {
+        let mut root = tree();
+
+        root.prune(1, &|_| false);
+        assert_eq!(
+            labels(&root),
+            [
+                (0, "AXWindow \"Window\"".to_owned()),
+                (1, "AXGroup \"Outer\"".to_owned()),
+                (1, "AXButton \"Cancel\"".to_owned()),
+            ]
+        );
+
+        let mut root = tree();
+
+        root.prune(0, &|_| false);
+        assert_eq!(root.node_count(), 1);
+    }
+
+    #[test]
+    fn prune_collapses_in_place() {
+        let mut root = tree();
+
+        root.prune(usize::MAX, &|n| n.role == "AXGroup");
+        assert_eq!(
+            labels(&root),
+            [
+                (0, "AXWindow \"Window\"".to_owned()),
+                (1, "AXButton \"OK\"".to_owned()),
+                (1, "AXButton \"Cancel\"".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn prune_never_collapses_the_root() {
+        let mut root = tree();
+
+        root.prune(usize::MAX, &|n| n.role != "AXButton");
+        assert_eq!(root.role, "AXWindow");
+        assert_eq!(root.node_count(), 3);
+    }
+
+    #[test]
+    fn prune_counts_depth_before_collapsing() {
+        let mut root = tree();
+
+        // The button sits three levels down, even once its groups collapse.
+        root.prune(2, &|n| n.role == "AXGroup");
+        assert_eq!(
+            labels(&root),
+            [
+                (0, "AXWindow \"Window\"".to_owned()),
+                (1, "AXButton \"Cancel\"".to_owned()),
+            ]
+        );
+    }
+}
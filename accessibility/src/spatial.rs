@@ -0,0 +1,325 @@
+use std::{collections::HashMap, str::FromStr};
+
+use rstar::{PointDistance, RTree, RTreeObject, AABB};
+
+use crate::{Frame, Point, SnapshotNode};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "left" => Ok(Direction::Left),
+            "right" => Ok(Direction::Right),
+            "up" => Ok(Direction::Up),
+            "down" => Ok(Direction::Down),
+            _ => Err(format!("unknown direction {:?}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Entry {
+    frame: Frame,
+    index: usize,
+}
+
+impl RTreeObject for Entry {
+    type Envelope = AABB<[f64; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        envelope(&self.frame)
+    }
+}
+
+impl PointDistance for Entry {
+    fn distance_2(&self, point: &[f64; 2]) -> f64 {
+        self.envelope().distance_2(point)
+    }
+}
+
+fn envelope(frame: &Frame) -> AABB<[f64; 2]> {
+    AABB::from_corners([frame.x, frame.y], [frame.max_x(), frame.max_y()])
+}
+
+/// An R-tree over the visible frames of a snapshot, for answering many
+/// spatial queries against the same tree.
+///
+/// Nodes are identified by address, so the nodes passed to
+/// [`next_in_direction`](Self::next_in_direction) must come from the
+/// snapshot the index was built from.
+pub struct SpatialIndex<'a> {
+    tree: RTree<Entry>,
+    nodes: Vec<(&'a SnapshotNode, Frame)>,
+    indices: HashMap<*const SnapshotNode, usize>,
+}
+
+impl<'a> SpatialIndex<'a> {
+    /// Indexes every node of `root` that has a visible frame.
+    pub fn new(root: &'a SnapshotNode) -> Self {
+        Self::with_filter(root, |_| true)
+    }
+
+    /// Indexes the nodes with a visible frame that `filter` accepts, e.g.
+    /// only leaves or only focusable controls.
+    pub fn with_filter(root: &'a SnapshotNode, filter: impl Fn(&SnapshotNode) -> bool) -> Self {
+        let nodes: Vec<_> = root
+            .visible_frames()
+            .into_iter()
+            .filter(|(node, _)| filter(node))
+            .collect();
+        let entries = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, (_, frame))| Entry {
+                frame: *frame,
+                index,
+            })
+            .collect();
+        let indices = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, (node, _))| (*node as *const SnapshotNode, index))
+            .collect();
+
+        Self {
+            tree: RTree::bulk_load(entries),
+            nodes,
+            indices,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The visible frame `node` was indexed with.
+    pub fn frame(&self, node: &SnapshotNode) -> Option<Frame> {
+        self.indices
+            .get(&(node as *const SnapshotNode))
+            .map(|&index| self.nodes[index].1)
+    }
+
+    /// Every node containing `point`, smallest first.
+    pub fn at_point(&self, point: Point) -> Vec<&'a SnapshotNode> {
+        let mut found: Vec<&Entry> = self
+            .tree
+            .locate_all_at_point(&[point.x, point.y])
+            .filter(|entry| entry.frame.contains(point))
+            .collect();
+
+        found.sort_by(|a, b| {
+            a.frame
+                .area()
+                .total_cmp(&b.frame.area())
+                .then(b.index.cmp(&a.index))
+        });
+        self.resolve(found)
+    }
+
+    /// Every node overlapping `rect`, in tree order.
+    pub fn in_rect(&self, rect: &Frame) -> Vec<&'a SnapshotNode> {
+        let mut found: Vec<&Entry> = self
+            .tree
+            .locate_in_envelope_intersecting(&envelope(rect))
+            .filter(|entry| entry.frame.overlaps(rect))
+            .collect();
+
+        found.sort_by_key(|entry| entry.index);
+        self.resolve(found)
+    }
+
+    /// The `k` nodes closest to `point`, measured to the nearest edge of each
+    /// frame. Nodes containing the point are at distance zero.
+    pub fn nearest(&self, point: Point, k: usize) -> Vec<&'a SnapshotNode> {
+        let found = self
+            .tree
+            .nearest_neighbor_iter(&[point.x, point.y])
+            .take(k)
+            .collect();
+
+        self.resolve(found)
+    }
+
+    /// The node a user moving from `node` in `direction` would expect to
+    /// land on.
+    ///
+    /// Candidates must lie past `node`'s edge in that direction, which rules
+    /// out its ancestors and descendants. Candidates in line with `node` win
+    /// over ones off to the side; after that the closest wins, with distance
+    /// along the direction weighted more heavily than sideways distance.
+    pub fn next_in_direction(
+        &self,
+        node: &SnapshotNode,
+        direction: Direction,
+    ) -> Option<&'a SnapshotNode> {
+        let source = self.frame(node)?;
+        let (min, max) = (f64::MIN, f64::MAX);
+        let half_plane = match direction {
+            Direction::Left => AABB::from_corners([min, min], [source.x, max]),
+            Direction::Right => AABB::from_corners([source.max_x(), min], [max, max]),
+            Direction::Up => AABB::from_corners([min, min], [max, source.y]),
+            Direction::Down => AABB::from_corners([min, source.max_y()], [max, max]),
+        };
+        let own = self.indices.get(&(node as *const SnapshotNode));
+        let best = self
+            .tree
+            .locate_in_envelope_intersecting(&half_plane)
+            .filter(|entry| Some(&entry.index) != own)
+            .filter(|entry| is_candidate(&source, &entry.frame, direction))
+            .min_by(|a, b| {
+                let a = score(&source, &a.frame, direction);
+                let b = score(&source, &b.frame, direction);
+
+                a.0.cmp(&b.0).then(a.1.total_cmp(&b.1))
+            })?;
+
+        Some(self.nodes[best.index].0)
+    }
+
+    fn resolve(&self, entries: Vec<&Entry>) -> Vec<&'a SnapshotNode> {
+        entries
+            .into_iter()
+            .map(|entry| self.nodes[entry.index].0)
+            .collect()
+    }
+}
+
+/// Edges of `frame` as (near, far) along `direction` and (start, end) across
+/// it, oriented so that moving in `direction` increases the first pair.
+fn axes(frame: &Frame, direction: Direction) -> ((f64, f64), (f64, f64)) {
+    match direction {
+        Direction::Right => ((frame.x, frame.max_x()), (frame.y, frame.max_y())),
+        Direction::Left => ((-frame.max_x(), -frame.x), (frame.y, frame.max_y())),
+        Direction::Down => ((frame.y, frame.max_y()), (frame.x, frame.max_x())),
+        Direction::Up => ((-frame.max_y(), -frame.y), (frame.x, frame.max_x())),
+    }
+}
+
+fn is_candidate(source: &Frame, candidate: &Frame, direction: Direction) -> bool {
+    let ((source_near, source_far), _) = axes(source, direction);
+    let ((near, far), _) = axes(candidate, direction);
+
+    (source_far <= near || source_near < near) && source_far < far
+}
+
+/// How much more distance along the direction counts than the same distance
+/// sideways. A candidate a little further ahead but level with the source
+/// beats one that's slightly closer but diagonal to it.
+const MAJOR_AXIS_WEIGHT: f64 = 13.0;
+
+/// Lower is better: (not in line, weighted squared distance).
+fn score(source: &Frame, candidate: &Frame, direction: Direction) -> (bool, f64) {
+    let ((_, source_far), (source_start, source_end)) = axes(source, direction);
+    let ((near, _), (start, end)) = axes(candidate, direction);
+    let in_line = start < source_end && end > source_start;
+    let major = (near - source_far).max(0.0);
+    let minor = ((start + end) - (source_start + source_end)) / 2.0;
+
+    (!in_line, MAJOR_AXIS_WEIGHT * major * major + minor * minor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(title: &str, frame: Frame) -> SnapshotNode {
+        SnapshotNode {
+            role: "AXButton".to_owned(),
+            title: Some(title.to_owned()),
+            frame: Some(frame),
+            ..Default::default()
+        }
+    }
+
+    /// A window with a row of two buttons, one button below the first, and
+    /// one off to the right below the row.
+    fn tree() -> SnapshotNode {
+        SnapshotNode {
+            role: "AXWindow".to_owned(),
+            title: Some("window".to_owned()),
+            frame: Some(Frame::new(0.0, 0.0, 300.0, 300.0)),
+            children: vec![
+                node("a", Frame::new(10.0, 10.0, 50.0, 20.0)),
+                node("b", Frame::new(100.0, 10.0, 50.0, 20.0)),
+                node("c", Frame::new(10.0, 100.0, 50.0, 20.0)),
+                node("d", Frame::new(200.0, 40.0, 50.0, 20.0)),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn titles(nodes: &[&SnapshotNode]) -> Vec<String> {
+        nodes.iter().filter_map(|node| node.title.clone()).collect()
+    }
+
+    #[test]
+    fn at_point() {
+        let tree = tree();
+        let index = SpatialIndex::new(&tree);
+
+        assert_eq!(index.len(), 5);
+        assert_eq!(
+            titles(&index.at_point(Point { x: 15.0, y: 15.0 })),
+            ["a", "window"]
+        );
+        assert_eq!(
+            titles(&index.at_point(Point { x: 80.0, y: 15.0 })),
+            ["window"]
+        );
+        assert!(index.at_point(Point { x: 400.0, y: 15.0 }).is_empty());
+    }
+
+    #[test]
+    fn in_rect() {
+        let tree = tree();
+        let index = SpatialIndex::new(&tree);
+
+        assert_eq!(
+            titles(&index.in_rect(&Frame::new(0.0, 0.0, 120.0, 25.0))),
+            ["window", "a", "b"]
+        );
+        assert!(index
+            .in_rect(&Frame::new(400.0, 400.0, 10.0, 10.0))
+            .is_empty());
+    }
+
+    #[test]
+    fn next_in_direction() {
+        let tree = tree();
+        let index = SpatialIndex::new(&tree);
+        let next = |title: &str, direction| {
+            let node = tree
+                .children
+                .iter()
+                .find(|node| node.title.as_deref() == Some(title))
+                .unwrap();
+
+            index
+                .next_in_direction(node, direction)
+                .and_then(|node| node.title.as_deref())
+        };
+
+        assert_eq!(next("a", Direction::Right), Some("b"));
+        assert_eq!(next("a", Direction::Down), Some("c"));
+        assert_eq!(next("c", Direction::Up), Some("a"));
+        assert_eq!(next("b", Direction::Left), Some("a"));
+        // Nothing is level with b on the right, so the diagonal d wins.
+        assert_eq!(next("b", Direction::Right), Some("d"));
+        // The window contains a, so it's never a candidate.
+        assert_eq!(next("a", Direction::Left), None);
+        assert_eq!(next("a", Direction::Up), None);
+    }
+}
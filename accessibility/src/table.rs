@@ -0,0 +1,269 @@
+use std::ops::Deref;
+
+use accessibility_sys::{
+    kAXAscendingSortDirectionValue, kAXDescendingSortDirectionValue, kAXErrorCannotComplete,
+    kAXUnknownSortDirectionValue,
+};
+use core_foundation::{array::CFArray, number::CFNumber};
+
+use crate::{
+    AXAny, AXUIElement, AXUIElementActions, AXUIElementAttributes,
+    AXUIElementParameterizedAttributes, Error,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+    Unknown,
+}
+
+impl SortDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortDirection::Ascending => kAXAscendingSortDirectionValue,
+            SortDirection::Descending => kAXDescendingSortDirectionValue,
+            SortDirection::Unknown => kAXUnknownSortDirectionValue,
+        }
+    }
+}
+
+impl From<&str> for SortDirection {
+    fn from(value: &str) -> Self {
+        #[allow(non_upper_case_globals)]
+        match value {
+            kAXAscendingSortDirectionValue => SortDirection::Ascending,
+            kAXDescendingSortDirectionValue => SortDirection::Descending,
+            _ => SortDirection::Unknown,
+        }
+    }
+}
+
+/// High-level access to an `AXTable` (or anything else exposing `AXRows` and
+/// `AXColumns`).
+#[derive(Clone, Debug)]
+pub struct Table {
+    element: AXUIElement,
+}
+
+impl Table {
+    pub fn new(element: AXUIElement) -> Self {
+        Self { element }
+    }
+
+    pub fn element(&self) -> &AXUIElement {
+        &self.element
+    }
+
+    pub fn rows(&self) -> Result<Vec<AXUIElement>, Error> {
+        Ok(to_vec(self.element.rows()?))
+    }
+
+    pub fn visible_rows(&self) -> Result<Vec<AXUIElement>, Error> {
+        Ok(to_vec(self.element.visible_rows()?))
+    }
+
+    pub fn selected_rows(&self) -> Result<Vec<AXUIElement>, Error> {
+        Ok(to_vec(self.element.selected_rows()?))
+    }
+
+    pub fn columns(&self) -> Result<Vec<AXUIElement>, Error> {
+        Ok(to_vec(self.element.columns()?))
+    }
+
+    pub fn row_count(&self) -> Result<usize, Error> {
+        match self.element.row_count().ok().and_then(|n| n.to_i64()) {
+            Some(count) => Ok(count as usize),
+            None => Ok(self.element.rows()?.len() as usize),
+        }
+    }
+
+    pub fn column_count(&self) -> Result<usize, Error> {
+        match self.element.column_count().ok().and_then(|n| n.to_i64()) {
+            Some(count) => Ok(count as usize),
+            None => Ok(self.element.columns()?.len() as usize),
+        }
+    }
+
+    pub fn row(&self, index: usize) -> Result<AXUIElement, Error> {
+        self.rows()?.into_iter().nth(index).ok_or(Error::NotFound)
+    }
+
+    pub fn column(&self, index: usize) -> Result<AXUIElement, Error> {
+        self.columns()?
+            .into_iter()
+            .nth(index)
+            .ok_or(Error::NotFound)
+    }
+
+    /// Returns the cell at `row`, `column`. Uses `AXCellForColumnAndRow` when
+    /// the table supports it, and the row's children otherwise.
+    pub fn cell(&self, row: usize, column: usize) -> Result<AXUIElement, Error> {
+        let parameter =
+            CFArray::from_CFTypes(&[CFNumber::from(column as i64), CFNumber::from(row as i64)]);
+
+        if let Ok(cell) = self.element.cell_for_column_and_row(&parameter) {
+            return Ok(cell);
+        }
+
+        let row = self.row(row)?;
+        let cells = row.children()?;
+
+        cells
+            .get(column as isize)
+            .map(|c| c.clone())
+            .ok_or(Error::NotFound)
+    }
+
+    /// Titles of the column headers, in column order. Columns without a
+    /// header have an empty title.
+    pub fn column_headers(&self) -> Result<Vec<String>, Error> {
+        Ok(self
+            .columns()?
+            .iter()
+            .map(|column| match column.header() {
+                Ok(header) => element_text(&header),
+                Err(_) => column.title().map(|t| t.to_string()).unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    pub fn column_by_header(&self, title: &str) -> Result<usize, Error> {
+        self.column_headers()?
+            .iter()
+            .position(|header| header == title)
+            .ok_or(Error::NotFound)
+    }
+
+    pub fn sort_direction(&self, column: usize) -> Result<SortDirection, Error> {
+        let column = self.column(column)?;
+        let direction = column
+            .sort_direction()
+            .or_else(|_| column.header().and_then(|h| h.sort_direction()))?;
+
+        Ok(SortDirection::from(direction.to_string().as_str()))
+    }
+
+    /// Clicks the header of `column` until the table reports it is sorted in
+    /// `direction`.
+    pub fn sort_by(&self, column: usize, direction: SortDirection) -> Result<(), Error> {
+        let header = self.column(column)?.header()?;
+
+        // Unsorted -> ascending -> descending is the most clicks any table
+        // needs.
+        for _ in 0..3 {
+            if self.sort_direction(column).ok() == Some(direction) {
+                return Ok(());
+            }
+
+            header.press()?;
+        }
+
+        if self.sort_direction(column)? == direction {
+            Ok(())
+        } else {
+            Err(Error::Ax(kAXErrorCannotComplete))
+        }
+    }
+
+    /// Replaces the selection with the rows at `indices`.
+    pub fn select_rows(&self, indices: &[usize]) -> Result<(), Error> {
+        let rows = self.rows()?;
+        let selected = indices
+            .iter()
+            .map(|i| rows.get(*i).cloned().ok_or(Error::NotFound))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.element
+            .set_selected_rows(CFArray::from_CFTypes(&selected))
+    }
+
+    /// Reads the text of every cell, row by row.
+    pub fn grid(&self) -> Result<Vec<Vec<String>>, Error> {
+        Ok(self
+            .rows()?
+            .iter()
+            .map(|row| match row.children() {
+                Ok(cells) => cells.iter().map(|cell| element_text(&cell)).collect(),
+                Err(_) => Vec::new(),
+            })
+            .collect())
+    }
+}
+
+/// A [`Table`] whose rows can be expanded and collapsed, such as an
+/// `AXOutline`.
+#[derive(Clone, Debug)]
+pub struct Outline {
+    table: Table,
+}
+
+impl Deref for Outline {
+    type Target = Table;
+
+    fn deref(&self) -> &Table {
+        &self.table
+    }
+}
+
+impl Outline {
+    pub fn new(element: AXUIElement) -> Self {
+        Self {
+            table: Table::new(element),
+        }
+    }
+
+    pub fn is_expanded(&self, row: usize) -> Result<bool, Error> {
+        Ok(self.row(row)?.disclosing()?.into())
+    }
+
+    pub fn expand(&self, row: usize) -> Result<(), Error> {
+        self.row(row)?.set_disclosing(true)
+    }
+
+    pub fn collapse(&self, row: usize) -> Result<(), Error> {
+        self.row(row)?.set_disclosing(false)
+    }
+
+    /// Nesting depth of `row`, where top-level rows are at level 0.
+    pub fn disclosure_level(&self, row: usize) -> Result<usize, Error> {
+        Ok(self
+            .row(row)?
+            .disclosure_level()?
+            .to_i64()
+            .unwrap_or_default() as usize)
+    }
+
+    pub fn disclosed_rows(&self, row: usize) -> Result<Vec<AXUIElement>, Error> {
+        Ok(to_vec(self.row(row)?.disclosed_rows()?))
+    }
+}
+
+fn to_vec(array: CFArray<AXUIElement>) -> Vec<AXUIElement> {
+    array.iter().map(|e| e.clone()).collect()
+}
+
+/// The text a user would read off an element: its value, title or
+/// description, or failing that the text of its first child that has any.
+pub fn element_text(element: &AXUIElement) -> String {
+    let own = [
+        element.value().ok().map(|v| AXAny::from(&v).to_string()),
+        element.title().ok().map(|t| t.to_string()),
+        element.description().ok().map(|d| d.to_string()),
+    ];
+
+    if let Some(text) = own.iter().flatten().find(|t| !t.is_empty()) {
+        return text.clone();
+    }
+
+    element
+        .children()
+        .map(|children| {
+            children
+                .iter()
+                .map(|child| element_text(&child))
+                .find(|t| !t.is_empty())
+                .unwrap_or_default()
+        })
+        .unwrap_or_default()
+}
@@ -1,12 +1,15 @@
 use std::{
-    ffi::c_uchar,
+    ffi::{c_uchar, CStr},
+    hash::{Hash, Hasher},
     thread,
     time::{Duration, Instant},
 };
 
 use accessibility_sys::{
     pid_t, AXUIElementCopyActionNames, AXUIElementCopyAttributeNames,
-    AXUIElementCopyAttributeValue, AXUIElementCreateApplication, AXUIElementCreateSystemWide,
+    AXUIElementCopyAttributeValue, AXUIElementCopyElementAtPosition,
+    AXUIElementCopyParameterizedAttributeNames, AXUIElementCopyParameterizedAttributeValue,
+    AXUIElementCreateApplication, AXUIElementCreateSystemWide, AXUIElementGetPid,
     AXUIElementGetTypeID, AXUIElementIsAttributeSettable, AXUIElementPerformAction, AXUIElementRef,
     AXUIElementSetAttributeValue, AXUIElementSetMessagingTimeout,
 };
@@ -16,7 +19,7 @@ use cocoa::{
 };
 use core_foundation::{
     array::CFArray,
-    base::{CFType, TCFType, TCFTypeRef},
+    base::{CFHash, CFType, TCFType, TCFTypeRef},
     declare_TCFType, impl_CFTypeDescription, impl_TCFType,
     string::CFString,
 };
@@ -24,13 +27,27 @@ use objc::{class, msg_send, rc::autoreleasepool, sel, sel_impl};
 
 use crate::{
     util::{ax_call, ax_call_void},
-    AXAttribute, Error,
+    AXAttribute, AXUIElementAttributes, Error, MAX_DEPTH,
 };
 
+/// An application as reported by `NSWorkspace`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RunningApplication {
+    pub pid: pid_t,
+    pub name: Option<String>,
+    pub bundle_id: Option<String>,
+}
+
 declare_TCFType!(AXUIElement, AXUIElementRef);
 impl_TCFType!(AXUIElement, AXUIElementRef, AXUIElementGetTypeID);
 impl_CFTypeDescription!(AXUIElement);
 
+impl Hash for AXUIElement {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        unsafe { CFHash(self.as_CFTypeRef()) }.hash(state)
+    }
+}
+
 impl AXUIElement {
     pub fn system_wide() -> Self {
         unsafe { Self::wrap_under_create_rule(AXUIElementCreateSystemWide()) }
@@ -62,6 +79,38 @@ impl AXUIElement {
         }
     }
 
+    /// Every running application, in `NSWorkspace` order.
+    ///
+    /// Unlike [`AXUIElement::application_with_bundle`], which takes the first
+    /// match, this returns every candidate so callers can tell when a bundle
+    /// identifier or name matches more than one process.
+    pub fn running_applications() -> Vec<RunningApplication> {
+        unsafe fn string(s: id) -> Option<String> {
+            if s == nil {
+                None
+            } else {
+                let bytes = CStr::from_ptr(s.UTF8String());
+
+                Some(bytes.to_string_lossy().into_owned())
+            }
+        }
+
+        unsafe {
+            autoreleasepool(|| {
+                let workspace: id = msg_send![class![NSWorkspace], sharedWorkspace];
+                let apps: id = msg_send![workspace, runningApplications];
+
+                apps.iter()
+                    .map(|app| RunningApplication {
+                        pid: msg_send![app, processIdentifier],
+                        name: string(msg_send![app, localizedName]),
+                        bundle_id: string(msg_send![app, bundleIdentifier]),
+                    })
+                    .collect()
+            })
+        }
+    }
+
     pub fn application_with_bundle_timeout(
         bundle_id: &str,
         timeout: Duration,
@@ -85,6 +134,27 @@ impl AXUIElement {
         }
     }
 
+    pub fn pid(&self) -> Result<pid_t, Error> {
+        unsafe { ax_call(|x| AXUIElementGetPid(self.0, x)).map_err(Error::Ax) }
+    }
+
+    /// The element and its ancestors, from the application down. Stops
+    /// after `MAX_DEPTH` parents, since some applications report parent
+    /// chains that loop.
+    pub fn ancestry(&self) -> Vec<AXUIElement> {
+        let mut path = vec![self.clone()];
+
+        while path.len() <= MAX_DEPTH {
+            match path.last().unwrap().parent() {
+                Ok(parent) => path.push(parent),
+                Err(_) => break,
+            }
+        }
+
+        path.reverse();
+        path
+    }
+
     pub fn attribute_names(&self) -> Result<CFArray<CFString>, Error> {
         unsafe {
             Ok(CFArray::wrap_under_create_rule(
@@ -117,6 +187,44 @@ impl AXUIElement {
         res
     }
 
+    pub fn parameterized_attribute_names(&self) -> Result<CFArray<CFString>, Error> {
+        unsafe {
+            Ok(CFArray::wrap_under_create_rule(
+                ax_call(|x| AXUIElementCopyParameterizedAttributeNames(self.0, x))
+                    .map_err(Error::Ax)?,
+            ))
+        }
+    }
+
+    pub fn parameterized_attribute<T: TCFType, U: TCFType>(
+        &self,
+        attribute: &AXAttribute<T>,
+        parameter: &U,
+    ) -> Result<T, Error> {
+        let res = unsafe {
+            Ok(T::wrap_under_create_rule(T::Ref::from_void_ptr(
+                ax_call(|x| {
+                    AXUIElementCopyParameterizedAttributeValue(
+                        self.0,
+                        attribute.as_CFString().as_concrete_TypeRef(),
+                        parameter.as_CFTypeRef(),
+                        x,
+                    )
+                })
+                .map_err(Error::Ax)?,
+            )))
+        };
+        if let Ok(val) = &res {
+            if T::type_id() != CFType::type_id() && !val.instance_of::<T>() {
+                return Err(Error::UnexpectedType {
+                    expected: T::type_id(),
+                    received: val.type_of(),
+                });
+            }
+        }
+        res
+    }
+
     pub fn set_attribute<T: TCFType>(
         &self,
         attribute: &AXAttribute<T>,
@@ -165,6 +273,18 @@ impl AXUIElement {
         }
     }
 
+    /// The topmost element at `(x, y)` in screen coordinates. On the
+    /// system-wide element this searches every application; on an application
+    /// element, only that application.
+    pub fn element_at_position(&self, x: f32, y: f32) -> Result<AXUIElement, Error> {
+        unsafe {
+            Ok(Self::wrap_under_create_rule(
+                ax_call(|e| AXUIElementCopyElementAtPosition(self.0, x, y, e))
+                    .map_err(Error::Ax)?,
+            ))
+        }
+    }
+
     pub fn set_messaging_timeout(&self, timeout: f32) -> Result<(), Error> {
         unsafe {
             ax_call_void(|| AXUIElementSetMessagingTimeout(self.0, timeout)).map_err(Error::Ax)
@@ -1,14 +1,16 @@
-use std::{ffi::c_void, marker::PhantomData};
+use std::{ffi::c_void, marker::PhantomData, mem};
 
 use accessibility_sys::{
     kAXErrorFailure, kAXErrorSuccess, kAXValueTypeAXError, kAXValueTypeCFRange,
-    kAXValueTypeCGPoint, kAXValueTypeCGRect, kAXValueTypeCGSize, kAXValueTypeIllegal,
+    kAXValueTypeCGPoint, kAXValueTypeCGRect, kAXValueTypeCGSize, kAXValueTypeIllegal, AXError,
     AXValueCreate, AXValueGetType, AXValueGetTypeID, AXValueGetValue, AXValueRef, AXValueType,
 };
-use core_foundation::{base::CFRange, declare_TCFType, impl_CFTypeDescription, impl_TCFType};
+use core_foundation::{
+    base::CFRange, declare_TCFType, impl_CFTypeDescription, impl_TCFType, ConcreteCFType,
+};
 use core_graphics_types::geometry::{CGPoint, CGRect, CGSize};
 
-use crate::{util::ax_call, Error};
+use crate::{util::ax_call, AXAny, Error};
 
 pub trait AXValueKind {
     const TYPE: AXValueType;
@@ -26,6 +28,27 @@ impl AXValueKind for CGRect {
 impl AXValueKind for CFRange {
     const TYPE: AXValueType = kAXValueTypeCFRange;
 }
+impl AXValueKind for AXErrorValue {
+    const TYPE: AXValueType = kAXValueTypeAXError;
+}
+
+/// The payload of a `kAXValueTypeAXError` value, which stands in for
+/// attributes that couldn't be read in multi-attribute results.
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AXErrorValue(pub AXError);
+
+impl From<AXErrorValue> for AXError {
+    fn from(value: AXErrorValue) -> Self {
+        value.0
+    }
+}
+
+impl From<AXErrorValue> for Error {
+    fn from(value: AXErrorValue) -> Self {
+        Error::Ax(value.0)
+    }
+}
 
 pub(crate) fn value_type_name(kind: AXValueType) -> &'static str {
     #[allow(non_upper_case_globals)]
@@ -44,6 +67,10 @@ declare_TCFType!(AXValue<T: AXValueKind>, AXValueRef);
 impl_TCFType!(AXValue<T: AXValueKind>, AXValueRef, AXValueGetTypeID);
 impl_CFTypeDescription!(AXValue<T: AXValueKind>);
 
+// All AXValues share a type ID, so a downcast succeeds for any `T`; use
+// `value_type` or `any` to find out what is actually inside.
+unsafe impl<T: AXValueKind> ConcreteCFType for AXValue<T> {}
+
 impl<T: AXValueKind> AXValue<T> {
     pub fn new(val: &T) -> Result<Self, Error> {
         let ptr = unsafe { AXValueCreate(T::TYPE, val as *const T as *const c_void) };
@@ -65,4 +92,39 @@ impl<T: AXValueKind> AXValue<T> {
             })
         }
     }
+
+    /// The type of the wrapped value, which may differ from `T` when the value
+    /// came from an untyped attribute.
+    pub fn value_type(&self) -> AXValueType {
+        unsafe { AXValueGetType(self.0) }
+    }
+
+    /// Decodes the value according to its actual type, whatever `T` is.
+    /// Illegal or unrecognized types come back as [`AXAny::Unknown`].
+    pub fn any(&self) -> AXAny {
+        unsafe fn get<K: AXValueKind>(value: AXValueRef) -> Option<K> {
+            let mut out: K = mem::zeroed();
+
+            if AXValueGetValue(value, K::TYPE, &mut out as *mut K as *mut _) {
+                Some(out)
+            } else {
+                None
+            }
+        }
+
+        let kind = self.value_type();
+        let value = unsafe {
+            #[allow(non_upper_case_globals)]
+            match kind {
+                kAXValueTypeCGPoint => get::<CGPoint>(self.0).map(|p| AXAny::Point(p.into())),
+                kAXValueTypeCGSize => get::<CGSize>(self.0).map(|s| AXAny::Size(s.into())),
+                kAXValueTypeCGRect => get::<CGRect>(self.0).map(|r| AXAny::Rect(r.into())),
+                kAXValueTypeCFRange => get::<CFRange>(self.0).map(|r| AXAny::Range(r.into())),
+                kAXValueTypeAXError => get::<AXErrorValue>(self.0).map(|e| AXAny::Error(e.0)),
+                _ => None,
+            }
+        };
+
+        value.unwrap_or_else(|| AXAny::Unknown(format!("AXValue {}", value_type_name(kind))))
+    }
 }
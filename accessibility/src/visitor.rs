@@ -0,0 +1,193 @@
+//! [`TreeVisitor`] adapters that restrict which elements reach another
+//! visitor. They nest, e.g.
+//! `MaxDepth::new(VisibleOnly::new(Collapse::roles(&builder, roles)), 5)`.
+
+use std::cell::{Cell, RefCell};
+
+use crate::{
+    geometry::Clip, AXUIElement, AXUIElementAttributes, Frame, TreeVisitor, TreeWalkerFlow,
+};
+
+/// Keeps track of which entered elements were passed on to the inner
+/// visitor, so their exits can be passed on too.
+#[derive(Default)]
+struct Forwarded(RefCell<Vec<bool>>);
+
+impl Forwarded {
+    fn depth(&self) -> usize {
+        self.0.borrow().len()
+    }
+
+    fn enter<V: TreeVisitor>(&self, inner: &V, element: &AXUIElement) -> TreeWalkerFlow {
+        self.0.borrow_mut().push(true);
+        inner.enter_element(element)
+    }
+
+    fn skip(&self, flow: TreeWalkerFlow) -> TreeWalkerFlow {
+        self.0.borrow_mut().push(false);
+        flow
+    }
+
+    fn exit<V: TreeVisitor>(&self, inner: &V, element: &AXUIElement) {
+        if self.0.borrow_mut().pop().expect("unbalanced tree walk") {
+            inner.exit_element(element);
+        }
+    }
+}
+
+/// Stops descending below `max_depth`, where the root is at depth 0.
+pub struct MaxDepth<V> {
+    inner: V,
+    max_depth: usize,
+    depth: Cell<usize>,
+}
+
+impl<V: TreeVisitor> MaxDepth<V> {
+    pub fn new(inner: V, max_depth: usize) -> Self {
+        Self {
+            inner,
+            max_depth,
+            depth: Cell::new(0),
+        }
+    }
+
+    pub fn into_inner(self) -> V {
+        self.inner
+    }
+}
+
+impl<V: TreeVisitor> TreeVisitor for MaxDepth<V> {
+    fn enter_element(&self, element: &AXUIElement) -> TreeWalkerFlow {
+        let depth = self.depth.get();
+
+        self.depth.set(depth + 1);
+
+        match self.inner.enter_element(element) {
+            TreeWalkerFlow::Continue if depth >= self.max_depth => TreeWalkerFlow::SkipSubtree,
+            flow => flow,
+        }
+    }
+
+    fn exit_element(&self, element: &AXUIElement) {
+        self.depth.set(self.depth.get() - 1);
+        self.inner.exit_element(element);
+    }
+}
+
+/// Hides the elements `predicate` picks from the inner visitor while still
+/// walking their children, which then appear in place of their collapsed
+/// ancestor. The root is never collapsed.
+pub struct Collapse<V, F> {
+    inner: V,
+    predicate: F,
+    forwarded: Forwarded,
+}
+
+impl<V, F> Collapse<V, F>
+where
+    V: TreeVisitor,
+    F: Fn(&AXUIElement) -> bool,
+{
+    pub fn new(inner: V, predicate: F) -> Self {
+        Self {
+            inner,
+            predicate,
+            forwarded: Forwarded::default(),
+        }
+    }
+
+    pub fn into_inner(self) -> V {
+        self.inner
+    }
+}
+
+fn role(element: &AXUIElement) -> String {
+    element.role().map(|r| r.to_string()).unwrap_or_default()
+}
+
+impl<V: TreeVisitor> Collapse<V, Box<dyn Fn(&AXUIElement) -> bool>> {
+    /// Passes on only elements with one of `roles`.
+    pub fn roles(inner: V, roles: Vec<String>) -> Self {
+        Self::new(inner, Box::new(move |e| !roles.contains(&role(e))))
+    }
+
+    /// Collapses elements with one of `roles`, e.g. pass-through `AXGroup`s.
+    pub fn skip_roles(inner: V, roles: Vec<String>) -> Self {
+        Self::new(inner, Box::new(move |e| roles.contains(&role(e))))
+    }
+}
+
+impl<V, F> TreeVisitor for Collapse<V, F>
+where
+    V: TreeVisitor,
+    F: Fn(&AXUIElement) -> bool,
+{
+    fn enter_element(&self, element: &AXUIElement) -> TreeWalkerFlow {
+        if self.forwarded.depth() > 0 && (self.predicate)(element) {
+            self.forwarded.skip(TreeWalkerFlow::Continue)
+        } else {
+            self.forwarded.enter(&self.inner, element)
+        }
+    }
+
+    fn exit_element(&self, element: &AXUIElement) {
+        self.forwarded.exit(&self.inner, element)
+    }
+}
+
+/// Skips elements that can't be seen, along with their subtrees: those
+/// whose frame is empty or clipped away by their ancestors, and those left
+/// out of a parent's `AXVisibleChildren` when it reports one.
+pub struct VisibleOnly<V> {
+    inner: V,
+    forwarded: Forwarded,
+    /// The clip and visible children each entered element imposes on its
+    /// children.
+    scopes: RefCell<Vec<(Clip, Option<Vec<AXUIElement>>)>>,
+}
+
+impl<V: TreeVisitor> VisibleOnly<V> {
+    pub fn new(inner: V) -> Self {
+        Self {
+            inner,
+            forwarded: Forwarded::default(),
+            scopes: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn into_inner(self) -> V {
+        self.inner
+    }
+}
+
+impl<V: TreeVisitor> TreeVisitor for VisibleOnly<V> {
+    fn enter_element(&self, element: &AXUIElement) -> TreeWalkerFlow {
+        let mut scopes = self.scopes.borrow_mut();
+        let (clip, listed) = match scopes.last() {
+            Some((clip, Some(visible))) => (*clip, visible.contains(element)),
+            Some((clip, None)) => (*clip, true),
+            None => (Clip::Unbounded, true),
+        };
+        let frame = element.frame().ok().map(Frame::from);
+        let (visible, clip) = clip.apply(frame);
+
+        if !listed || (frame.is_some() && visible.is_none()) {
+            scopes.push((Clip::Hidden, None));
+            return self.forwarded.skip(TreeWalkerFlow::SkipSubtree);
+        }
+
+        let visible_children = element
+            .visible_children()
+            .ok()
+            .map(|children| children.iter().map(|c| c.clone()).collect());
+
+        scopes.push((clip, visible_children));
+        drop(scopes);
+        self.forwarded.enter(&self.inner, element)
+    }
+
+    fn exit_element(&self, element: &AXUIElement) {
+        self.scopes.borrow_mut().pop();
+        self.forwarded.exit(&self.inner, element)
+    }
+}
@@ -0,0 +1,314 @@
+//! `aq at`: the element at a point on screen, like the hover mode of
+//! Accessibility Inspector.
+
+use accessibility::{AXUIElement, AXUIElementGeometry, Point, SnapshotNode};
+use core_foundation::base::CFRelease;
+use core_graphics_types::geometry::CGPoint;
+use std::{ffi::c_void, path::Path, thread, time::Duration};
+
+use crate::{
+    interact, query,
+    repl::{display, Node},
+    snapshot::load_snapshot,
+};
+
+/// How often `--follow-mouse` checks where the mouse is.
+const MOUSE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Parses `<x> <y>` or `<x>,<y>` in screen coordinates.
+pub fn parse_point(args: &[String]) -> Result<Point, String> {
+    let (x, y) = match args {
+        [x, y] => (x.as_str(), y.as_str()),
+        [xy] => xy
+            .split_once(',')
+            .ok_or_else(|| format!("expected <x> <y> or <x>,<y>, got {:?}", xy))?,
+        _ => return Err("expected <x> <y> or <x>,<y>".to_owned()),
+    };
+    let coordinate = |s: &str| match s.trim().parse::<f64>() {
+        Ok(n) if n.is_finite() => Ok(n),
+        _ => Err(format!("invalid coordinate {:?}", s)),
+    };
+
+    Ok(Point {
+        x: coordinate(x)?,
+        y: coordinate(y)?,
+    })
+}
+
+/// The path to the element, one ancestor per line and indented by depth,
+/// followed by the element's attributes.
+pub fn describe<N: Node>(point: Point, path: &[N]) -> String {
+    let mut out = format!("at ({}, {})\n", point.x, point.y);
+
+    for (depth, node) in path.iter().enumerate() {
+        out.push_str(&format!(
+            "{}{}\n",
+            "  ".repeat(depth + 1),
+            query::label(node)
+        ));
+    }
+
+    if let Some(element) = path.last() {
+        let indent = "  ".repeat(path.len() + 1);
+
+        for name in element.attribute_names() {
+            match element.selector_attribute(&name) {
+                Some(value) => out.push_str(&format!("{}{}: {}\n", indent, name, display(&value))),
+                None => out.push_str(&format!("{}{}: <none>\n", indent, name)),
+            }
+        }
+    }
+
+    out
+}
+
+/// The path from `root` to the frontmost node at `point`, using the frames
+/// recorded in a snapshot.
+pub fn snapshot_path(root: &SnapshotNode, point: Point) -> Option<Vec<&SnapshotNode>> {
+    let target = root.element_at(point)?;
+    let mut path = Vec::new();
+    let mut found = None;
+
+    root.walk(&mut |node, depth| {
+        if found.is_some() {
+            return;
+        }
+
+        path.truncate(depth);
+        path.push(node);
+
+        if std::ptr::eq(node, target) {
+            found = Some(path.clone());
+        }
+    });
+    found
+}
+
+#[link(name = "ApplicationServices", kind = "framework")]
+extern "C" {
+    fn CGEventCreate(source: *const c_void) -> *const c_void;
+    fn CGEventGetLocation(event: *const c_void) -> CGPoint;
+}
+
+/// The mouse position in the same top-left-origin coordinates the
+/// accessibility API uses.
+fn mouse_location() -> Option<Point> {
+    unsafe {
+        let event = CGEventCreate(std::ptr::null());
+
+        if event.is_null() {
+            return None;
+        }
+
+        let location = CGEventGetLocation(event);
+
+        CFRelease(event);
+        Some(Point {
+            x: location.x,
+            y: location.y,
+        })
+    }
+}
+
+/// An error message and the exit code that goes with it.
+type Failure = (String, i32);
+
+fn live(point: Point) -> Result<String, Failure> {
+    let element = AXUIElement::system_wide().element_at(point).map_err(|e| {
+        (
+            format!("nothing at ({}, {}): {}", point.x, point.y, e),
+            interact::exit_code(&e),
+        )
+    })?;
+
+    Ok(describe(point, &element.ancestry()))
+}
+
+fn offline(root: &SnapshotNode, point: Point) -> Result<String, Failure> {
+    snapshot_path(root, point)
+        .map(|path| describe(point, &path))
+        .ok_or_else(|| {
+            (
+                format!("nothing at ({}, {})", point.x, point.y),
+                interact::NO_MATCH,
+            )
+        })
+}
+
+pub fn run(args: &[String], follow_mouse: bool, snapshot: Option<&Path>) -> Result<(), i32> {
+    let root = match snapshot {
+        Some(path) => Some(load_snapshot(path)?),
+        None => None,
+    };
+    let resolve = |point| match &root {
+        Some(root) => offline(root, point),
+        None => live(point),
+    };
+
+    if !follow_mouse {
+        let point = parse_point(args).map_err(|e| {
+            eprintln!("{}", e);
+            2
+        })?;
+
+        return match resolve(point) {
+            Ok(description) => {
+                print!["{}", description];
+                Ok(())
+            }
+            Err((message, code)) => {
+                eprintln!("{}", message);
+                Err(code)
+            }
+        };
+    }
+
+    if !args.is_empty() {
+        eprintln!("--follow-mouse doesn't take coordinates");
+        return Err(2);
+    }
+
+    match &root {
+        Some(root) => follow(
+            |point| {
+                root.element_at(point)
+                    .map(|node| node as *const SnapshotNode)
+            },
+            |_, point| snapshot_path(root, point).unwrap_or_default(),
+        ),
+        None => {
+            let system_wide = AXUIElement::system_wide();
+
+            follow(
+                |point| system_wide.element_at(point).ok(),
+                |element, _| element.ancestry(),
+            )
+        }
+    }
+}
+
+/// Prints the element under the mouse each time it changes. `hit` finds the
+/// element and `path` its ancestry; only a new element is described, since
+/// describing a live element reads all of its attributes.
+fn follow<K: PartialEq, N: Node>(
+    hit: impl Fn(Point) -> Option<K>,
+    path: impl Fn(&K, Point) -> Vec<N>,
+) -> ! {
+    let mut last = None;
+
+    loop {
+        if let Some(point) = mouse_location() {
+            if let Some(element) = hit(point) {
+                if last.as_ref() != Some(&element) {
+                    println!["{}", describe(point, &path(&element, point))];
+                    last = Some(element);
+                }
+            }
+        }
+
+        thread::sleep(MOUSE_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use accessibility::Frame;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn node(role: &str, title: &str, frame: Frame, children: Vec<SnapshotNode>) -> SnapshotNode {
+        SnapshotNode {
+            role: role.to_owned(),
+            title: Some(title.to_owned()),
+            frame: Some(frame),
+            children,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_points() {
+        let point = |a: &[&str]| parse_point(&args(a)).map(|p| (p.x, p.y));
+
+        assert_eq!(point(&["10", "20.5"]), Ok((10.0, 20.5)));
+        assert_eq!(point(&["10,20"]), Ok((10.0, 20.0)));
+        assert_eq!(point(&[" 10 , 20 "]), Ok((10.0, 20.0)));
+        // Screens left of or above the main display have negative
+        // coordinates.
+        assert_eq!(point(&["-1440", "-30"]), Ok((-1440.0, -30.0)));
+        assert_eq!(point(&["-1440,-30"]), Ok((-1440.0, -30.0)));
+        assert!(point(&["10"]).is_err());
+        assert!(point(&["10", "20", "30"]).is_err());
+        assert!(point(&[]).is_err());
+        assert!(point(&["x", "20"]).is_err());
+        assert!(point(&["NaN", "20"]).is_err());
+        assert!(point(&["10", "inf"]).is_err());
+    }
+
+    #[test]
+    fn describes_a_path() {
+        let button = SnapshotNode {
+            role: "AXButton".to_owned(),
+            title: Some("OK".to_owned()),
+            ..Default::default()
+        };
+        let window = SnapshotNode {
+            role: "AXWindow".to_owned(),
+            title: Some("Main".to_owned()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            describe(Point { x: 1.0, y: -2.0 }, &[&window, &button]),
+            "at (1, -2)\n  \
+             AXWindow \"Main\"\n    \
+             AXButton \"OK\"\n      \
+             AXRole: \"AXButton\"\n      \
+             AXTitle: \"OK\"\n"
+        );
+        assert_eq!(
+            describe::<&SnapshotNode>(Point { x: 0.0, y: 0.0 }, &[]),
+            "at (0, 0)\n"
+        );
+    }
+
+    #[test]
+    fn finds_the_path_in_a_snapshot() {
+        let root = node(
+            "AXWindow",
+            "Main",
+            Frame::new(0.0, 0.0, 100.0, 100.0),
+            vec![
+                node("AXButton", "OK", Frame::new(10.0, 10.0, 20.0, 20.0), vec![]),
+                node(
+                    "AXGroup",
+                    "Tools",
+                    Frame::new(40.0, 40.0, 50.0, 50.0),
+                    vec![node(
+                        "AXCheckBox",
+                        "Snap",
+                        Frame::new(50.0, 50.0, 10.0, 10.0),
+                        vec![],
+                    )],
+                ),
+            ],
+        );
+        let titles = |x, y| {
+            snapshot_path(&root, Point { x, y }).map(|path| {
+                path.iter()
+                    .filter_map(|node| node.title.as_deref())
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        assert_eq!(titles(15.0, 15.0), Some(vec!["Main", "OK"]));
+        assert_eq!(titles(55.0, 55.0), Some(vec!["Main", "Tools", "Snap"]));
+        assert_eq!(titles(45.0, 45.0), Some(vec!["Main", "Tools"]));
+        assert_eq!(titles(95.0, 5.0), Some(vec!["Main"]));
+        assert_eq!(titles(150.0, 5.0), None);
+        assert_eq!(titles(-5.0, 5.0), None);
+    }
+}
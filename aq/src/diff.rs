@@ -0,0 +1,21 @@
+use accessibility::TreeDiff;
+use std::path::Path;
+
+use crate::snapshot::load_snapshot;
+
+/// Exits with 1 when the snapshots differ, like diff(1).
+pub fn run(a: &Path, b: &Path, json: bool) -> Result<(), i32> {
+    let diff = TreeDiff::new(&load_snapshot(a)?, &load_snapshot(b)?);
+
+    if json {
+        println!["{}", serde_json::to_string_pretty(&diff).unwrap()];
+    } else {
+        print!["{}", diff];
+    }
+
+    if diff.is_empty() {
+        Ok(())
+    } else {
+        Err(1)
+    }
+}
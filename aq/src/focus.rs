@@ -0,0 +1,277 @@
+//! `aq focus`: where keyboard focus is, and with `--follow`, where it goes.
+//!
+//! An `AXObserver` watches a single process, so there is no system-wide
+//! focus notification to subscribe to. `--follow` instead checks the focused
+//! application every 250 ms and, when its pid changes, registers for
+//! `AXFocusedUIElementChanged` in the new one.
+
+use accessibility::{AXAny, AXUIElement, AXUIElementAttributes, Frame, Observer, Selectable};
+use accessibility_sys::kAXFocusedUIElementChangedNotification;
+use core_foundation::runloop::{kCFRunLoopDefaultMode, CFRunLoop};
+use serde::Serialize;
+use std::{
+    cell::RefCell,
+    fs::File,
+    io::Write,
+    path::Path,
+    rc::Rc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{interact, query, watch::text};
+
+/// How often the focused application is checked for a change.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// One focus change, as printed and as written to the `--log` timeline.
+#[derive(Debug, Serialize)]
+pub struct FocusRecord {
+    /// Seconds since the Unix epoch.
+    pub at: f64,
+    /// Seconds since tracking started.
+    pub elapsed: f64,
+    pub pid: Option<i32>,
+    pub role: Option<String>,
+    pub title: Option<String>,
+    pub frame: Option<Frame>,
+    /// Labels from the application down to the focused element.
+    pub path: Vec<String>,
+}
+
+fn seconds(time: SystemTime) -> f64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or_default()
+}
+
+impl FocusRecord {
+    /// Describes the focused element given as its path, from the
+    /// application down.
+    pub fn new<T: Selectable>(
+        path: &[T],
+        pid: Option<i32>,
+        start: SystemTime,
+        now: SystemTime,
+    ) -> Self {
+        let element = path.last();
+
+        Self {
+            at: seconds(now),
+            elapsed: now
+                .duration_since(start)
+                .map(|d| d.as_secs_f64())
+                .unwrap_or_default(),
+            pid,
+            role: element.and_then(|e| text(e, "AXRole")),
+            title: element.and_then(|e| text(e, "AXTitle")),
+            frame: match element.and_then(|e| e.selector_attribute("AXFrame")) {
+                Some(AXAny::Rect(frame)) => Some(frame),
+                _ => None,
+            },
+            path: path.iter().map(query::label).collect(),
+        }
+    }
+
+    /// The element on one line and its path, indented, on the next.
+    pub fn to_text(&self) -> String {
+        let mut line = format!(
+            "{:>10.3} {}",
+            self.elapsed,
+            self.role.as_deref().unwrap_or("?")
+        );
+
+        if let Some(title) = &self.title {
+            line.push_str(&format!(" {:?}", title));
+        }
+
+        if let Some(frame) = &self.frame {
+            line.push_str(&format!(" {}", frame));
+        }
+
+        line.push_str(&format!("\n{:>10} {}", "", self.path.join(" > ")));
+        line
+    }
+}
+
+struct Reporter {
+    start: SystemTime,
+    log: Option<File>,
+    last: Option<AXUIElement>,
+}
+
+impl Reporter {
+    fn report(&mut self, element: AXUIElement) {
+        if self.last.as_ref() == Some(&element) {
+            return;
+        }
+
+        let record = FocusRecord::new(
+            &element.ancestry(),
+            element.pid().ok(),
+            self.start,
+            SystemTime::now(),
+        );
+
+        println!["{}", record.to_text()];
+
+        if let Some(log) = &mut self.log {
+            if let Err(e) = writeln!(log, "{}", serde_json::to_string(&record).unwrap()) {
+                eprintln!("can't write the focus log: {}", e);
+                self.log = None;
+            }
+        }
+
+        self.last = Some(element);
+    }
+}
+
+/// Observes focus changes in `pid`, returning `None` if it can't.
+fn observe(pid: i32, reporter: &Rc<RefCell<Reporter>>) -> Option<Observer> {
+    let reporter = reporter.clone();
+    let observer = Observer::new(pid, move |event| {
+        reporter.borrow_mut().report(event.element)
+    })
+    .and_then(|observer| {
+        observer
+            .add_notification(
+                &AXUIElement::application(pid),
+                kAXFocusedUIElementChangedNotification,
+            )
+            .map(|_| observer)
+    });
+
+    match observer {
+        Ok(observer) => {
+            observer.schedule();
+            Some(observer)
+        }
+        Err(e) => {
+            eprintln!("can't follow focus in pid {}: {}", pid, e);
+            None
+        }
+    }
+}
+
+pub fn run(follow: bool, log: Option<&Path>) -> Result<(), i32> {
+    let log = match log {
+        Some(path) => Some(File::create(path).map_err(|e| {
+            eprintln!("{}: {}", path.display(), e);
+            1
+        })?),
+        None => None,
+    };
+    let reporter = Rc::new(RefCell::new(Reporter {
+        start: SystemTime::now(),
+        log,
+        last: None,
+    }));
+    let system_wide = AXUIElement::system_wide();
+
+    if !follow {
+        let element = system_wide.focused_ui_element().map_err(|e| {
+            eprintln!("no focused element: {}", e);
+            interact::exit_code(&e)
+        })?;
+
+        reporter.borrow_mut().report(element);
+        return Ok(());
+    }
+
+    // Observers are per application, so the observer is replaced whenever
+    // another application becomes frontmost.
+    let mut observed: Option<(i32, Option<Observer>)> = None;
+
+    loop {
+        let pid = system_wide
+            .focused_application()
+            .and_then(|app| app.pid())
+            .ok();
+
+        if pid != observed.as_ref().map(|(pid, _)| *pid) {
+            observed = pid.map(|pid| (pid, observe(pid, &reporter)));
+
+            // Activating an application moves focus without a notification
+            // from the application that received it.
+            if let Ok(element) = system_wide.focused_ui_element() {
+                reporter.borrow_mut().report(element);
+            }
+        }
+
+        CFRunLoop::run_in_mode(unsafe { kCFRunLoopDefaultMode }, POLL_INTERVAL, false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use accessibility::SnapshotNode;
+    use serde_json::json;
+
+    fn node(role: &str, title: &str) -> SnapshotNode {
+        SnapshotNode {
+            role: role.to_owned(),
+            title: Some(title.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    fn record() -> FocusRecord {
+        let start = UNIX_EPOCH + Duration::from_secs(1000);
+        let mut button = node("AXButton", "OK");
+
+        button.frame = Some(Frame {
+            x: 10.0,
+            y: 20.0,
+            width: 80.0,
+            height: 24.0,
+        });
+
+        FocusRecord::new(
+            &[
+                node("AXApplication", "Finder"),
+                node("AXWindow", "Documents"),
+                button,
+            ],
+            Some(42),
+            start,
+            start + Duration::from_millis(1500),
+        )
+    }
+
+    #[test]
+    fn text_output() {
+        assert_eq!(
+            record().to_text(),
+            "     1.500 AXButton \"OK\" (10, 20) 80x24\n           \
+             AXApplication \"Finder\" > AXWindow \"Documents\" > AXButton \"OK\""
+        );
+    }
+
+    #[test]
+    fn text_output_without_an_element() {
+        let start = UNIX_EPOCH;
+        let record = FocusRecord::new::<SnapshotNode>(&[], None, start, start);
+
+        assert_eq!(record.to_text(), "     0.000 ?\n           ");
+    }
+
+    #[test]
+    fn json_output() {
+        assert_eq!(
+            serde_json::to_value(record()).unwrap(),
+            json!({
+                "at": 1001.5,
+                "elapsed": 1.5,
+                "pid": 42,
+                "role": "AXButton",
+                "title": "OK",
+                "frame": {"x": 10.0, "y": 20.0, "width": 80.0, "height": 24.0},
+                "path": [
+                    "AXApplication \"Finder\"",
+                    "AXWindow \"Documents\"",
+                    "AXButton \"OK\"",
+                ],
+            })
+        );
+    }
+}
@@ -0,0 +1,239 @@
+use accessibility::{AXAny, Role, SnapshotNode};
+use accessibility_sys::{
+    kAXDescriptionAttribute, kAXEnabledAttribute, kAXFocusedAttribute, kAXIdentifierAttribute,
+    kAXSelectedAttribute, kAXSubroleAttribute, kAXTitleAttribute, kAXValueAttribute,
+};
+use std::{fmt::Write, str::FromStr};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Yaml,
+    Xml,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "yaml" => Ok(OutputFormat::Yaml),
+            "xml" => Ok(OutputFormat::Xml),
+            _ => Err(format!("unknown format {:?}", s)),
+        }
+    }
+}
+
+/// Renders a tree in one of the structured formats. JSON is the snapshot
+/// file format, so its output can be fed back to `aq diff`; YAML has the same
+/// schema.
+pub fn render(node: &SnapshotNode, format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Text => to_text(node),
+        OutputFormat::Json => serde_json::to_string_pretty(node).unwrap() + "\n",
+        OutputFormat::Yaml => serde_yaml::to_string(node).unwrap(),
+        OutputFormat::Xml => to_xml(node),
+    }
+}
+
+/// An indented outline with one line per attribute.
+pub fn to_text(node: &SnapshotNode) -> String {
+    let mut out = String::new();
+
+    node.walk(&mut |node, depth| {
+        let indent = "    ".repeat(depth);
+
+        writeln!(
+            out,
+            "{}- {} ({} children)",
+            indent,
+            node.role,
+            node.children.len()
+        )
+        .unwrap();
+
+        for (name, value) in node.all_attributes() {
+            writeln!(out, "{}|. {}: {}", indent, name, value).unwrap();
+        }
+    });
+    out
+}
+
+/// A page source in the shape XCUITest and Appium produce, so that their
+/// inspectors can open it: one `XCUIElementType*` element per node, with
+/// the common attributes flattened into XML attributes.
+pub fn to_xml(node: &SnapshotNode) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<AppiumAUT>\n");
+
+    xml_node(node, 1, &mut out);
+    out.push_str("</AppiumAUT>\n");
+    out
+}
+
+fn xml_node(node: &SnapshotNode, depth: usize, out: &mut String) {
+    let indent = "  ".repeat(depth);
+    let tag = element_type(&Role::from(node.role.as_str()));
+    let text = |name: &str| {
+        node.attribute(name)
+            .map(|v| match v {
+                AXAny::String(s) => s,
+                v => v.to_string(),
+            })
+            .unwrap_or_default()
+    };
+    let flag = |name: &str| {
+        node.attribute(name)
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+            .to_string()
+    };
+    let frame = node.frame.unwrap_or_default();
+    let attributes = [
+        ("type", tag.to_owned()),
+        ("role", node.role.clone()),
+        ("subrole", text(kAXSubroleAttribute)),
+        ("identifier", text(kAXIdentifierAttribute)),
+        ("title", text(kAXTitleAttribute)),
+        ("label", text(kAXDescriptionAttribute)),
+        ("value", text(kAXValueAttribute)),
+        ("enabled", flag(kAXEnabledAttribute)),
+        ("selected", flag(kAXSelectedAttribute)),
+        ("focused", flag(kAXFocusedAttribute)),
+        ("x", frame.x.to_string()),
+        ("y", frame.y.to_string()),
+        ("width", frame.width.to_string()),
+        ("height", frame.height.to_string()),
+    ];
+
+    write!(out, "{}<{}", indent, tag).unwrap();
+
+    for (name, value) in attributes.iter() {
+        write!(out, " {}=\"{}\"", name, xml_escape(value)).unwrap();
+    }
+
+    if node.children.is_empty() {
+        out.push_str("/>\n");
+    } else {
+        out.push_str(">\n");
+
+        for child in &node.children {
+            xml_node(child, depth + 1, out);
+        }
+
+        writeln!(out, "{}</{}>", indent, tag).unwrap();
+    }
+}
+
+/// The XCUITest element type corresponding to an accessibility role.
+pub fn element_type(role: &Role) -> &'static str {
+    match role {
+        Role::Application => "XCUIElementTypeApplication",
+        Role::Browser => "XCUIElementTypeBrowser",
+        Role::Button => "XCUIElementTypeButton",
+        Role::Cell => "XCUIElementTypeCell",
+        Role::CheckBox => "XCUIElementTypeCheckBox",
+        Role::ColorWell => "XCUIElementTypeColorWell",
+        Role::Column => "XCUIElementTypeTableColumn",
+        Role::ComboBox => "XCUIElementTypeComboBox",
+        Role::DisclosureTriangle => "XCUIElementTypeDisclosureTriangle",
+        Role::Drawer => "XCUIElementTypeDrawer",
+        Role::Grid => "XCUIElementTypeGrid",
+        Role::Group => "XCUIElementTypeGroup",
+        Role::GrowArea => "XCUIElementTypeHandle",
+        Role::Handle => "XCUIElementTypeHandle",
+        Role::HelpTag => "XCUIElementTypeHelpTag",
+        Role::Image => "XCUIElementTypeImage",
+        Role::Incrementor => "XCUIElementTypeIncrementArrow",
+        Role::LayoutArea => "XCUIElementTypeLayoutArea",
+        Role::LayoutItem => "XCUIElementTypeLayoutItem",
+        Role::LevelIndicator => "XCUIElementTypeLevelIndicator",
+        Role::List => "XCUIElementTypeTable",
+        Role::Matte => "XCUIElementTypeMatte",
+        Role::Menu => "XCUIElementTypeMenu",
+        Role::MenuBar => "XCUIElementTypeMenuBar",
+        Role::MenuBarItem => "XCUIElementTypeMenuBarItem",
+        Role::MenuButton => "XCUIElementTypeMenuButton",
+        Role::MenuItem => "XCUIElementTypeMenuItem",
+        Role::Outline => "XCUIElementTypeOutline",
+        Role::PopUpButton => "XCUIElementTypePopUpButton",
+        Role::Popover => "XCUIElementTypePopover",
+        Role::ProgressIndicator => "XCUIElementTypeProgressIndicator",
+        Role::RadioButton => "XCUIElementTypeRadioButton",
+        Role::RadioGroup => "XCUIElementTypeRadioGroup",
+        Role::RelevanceIndicator => "XCUIElementTypeRelevanceIndicator",
+        Role::Row => "XCUIElementTypeTableRow",
+        Role::Ruler => "XCUIElementTypeRuler",
+        Role::RulerMarker => "XCUIElementTypeRulerMarker",
+        Role::ScrollArea => "XCUIElementTypeScrollView",
+        Role::ScrollBar => "XCUIElementTypeScrollBar",
+        Role::Sheet => "XCUIElementTypeSheet",
+        Role::Slider => "XCUIElementTypeSlider",
+        Role::SplitGroup => "XCUIElementTypeSplitGroup",
+        Role::Splitter => "XCUIElementTypeSplitter",
+        Role::StaticText => "XCUIElementTypeStaticText",
+        Role::TabGroup => "XCUIElementTypeTabGroup",
+        Role::Table => "XCUIElementTypeTable",
+        Role::TextArea => "XCUIElementTypeTextView",
+        Role::TextField => "XCUIElementTypeTextField",
+        Role::Toolbar => "XCUIElementTypeToolbar",
+        Role::ValueIndicator => "XCUIElementTypeValueIndicator",
+        Role::Window => "XCUIElementTypeWindow",
+        // Web content roles, which the spec doesn't list.
+        Role::Other(role) => match role.as_str() {
+            "AXLink" => "XCUIElementTypeLink",
+            "AXWebArea" => "XCUIElementTypeWebView",
+            _ => "XCUIElementTypeOther",
+        },
+        _ => "XCUIElementTypeOther",
+    }
+}
+
+/// Escapes `value` for an attribute value. Whitespace other than spaces is
+/// written as character references so that parsers don't normalize it away,
+/// and other control characters, which XML 1.0 doesn't allow at all, are
+/// replaced.
+fn xml_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\n' => out.push_str("&#10;"),
+            '\r' => out.push_str("&#13;"),
+            '\t' => out.push_str("&#9;"),
+            c if c < ' ' => out.push(char::REPLACEMENT_CHARACTER),
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_control_characters() {
+        assert_eq!(
+            xml_escape("a<b>&\"c\"\n\r\td\u{0}\u{1b}e"),
+            "a&lt;b&gt;&amp;&quot;c&quot;&#10;&#13;&#9;d\u{fffd}\u{fffd}e"
+        );
+    }
+
+    #[test]
+    fn element_types() {
+        assert_eq!(element_type(&Role::Button), "XCUIElementTypeButton");
+        assert_eq!(element_type(&Role::from("AXLink")), "XCUIElementTypeLink");
+        assert_eq!(
+            element_type(&Role::from("AXNothing")),
+            "XCUIElementTypeOther"
+        );
+    }
+}
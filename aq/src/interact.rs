@@ -0,0 +1,177 @@
+//! `aq press`, `aq set` and `aq action`.
+//!
+//! Exit codes, for scripts:
+//!
+//! | code  | meaning                                               |
+//! | ----- | ----------------------------------------------------- |
+//! | 0     | success                                               |
+//! | 1     | any other error, e.g. an attribute of unexpected type |
+//! | 2     | bad arguments, target or value                        |
+//! | 3     | no element matched the selector                       |
+//! | 4     | several elements matched and `--first` wasn't given   |
+//! | 5     | the attribute isn't settable                          |
+//! | 6     | the element doesn't support the action                |
+//! | 10-24 | the accessibility API failed; 10 + (-25200 - AXError) |
+
+use accessibility::{AXAny, AXAttribute, AXUIElement, ElementFinder, Error, Selector};
+use accessibility_sys::{kAXErrorFailure, kAXErrorNotEnoughPrecision, kAXPressAction};
+use core_foundation::string::CFString;
+use std::time::Duration;
+use structopt::StructOpt;
+
+use crate::{
+    query,
+    target::{split_pid, TargetFlags},
+};
+
+pub const NO_MATCH: i32 = 3;
+pub const AMBIGUOUS: i32 = 4;
+pub const NOT_SETTABLE: i32 = 5;
+pub const UNSUPPORTED_ACTION: i32 = 6;
+
+/// Options shared by the commands that act on a single element.
+#[derive(Clone, Debug, StructOpt)]
+pub struct ElementOpt {
+    #[structopt(flatten)]
+    pub target: TargetFlags,
+    /// Wait this long for the element to appear, e.g. 5s or 500ms
+    #[structopt(long, parse(try_from_str = query::parse_duration))]
+    pub wait: Option<Duration>,
+    /// Act on the first match instead of failing when several match
+    #[structopt(long)]
+    pub first: bool,
+}
+
+/// The exit code for a failed accessibility call.
+pub fn exit_code(error: &Error) -> i32 {
+    match error {
+        Error::NotFound => NO_MATCH,
+        Error::Ax(code) if (kAXErrorNotEnoughPrecision..=kAXErrorFailure).contains(code) => {
+            10 + (kAXErrorFailure - code)
+        }
+        _ => 1,
+    }
+}
+
+fn fail(context: &str, error: Error) -> i32 {
+    eprintln!("{}: {}", context, error);
+    exit_code(&error)
+}
+
+fn usage(message: impl std::fmt::Display) -> i32 {
+    eprintln!("{}", message);
+    2
+}
+
+impl ElementOpt {
+    /// Splits `[pid] <selector> <rest...>` and finds the single element the
+    /// selector names, returning it with the remaining arguments.
+    fn find<'a>(
+        &self,
+        args: &'a [String],
+        rest: usize,
+    ) -> Result<(AXUIElement, &'a [String]), i32> {
+        let (pid, args) = split_pid(args, rest + 1).map_err(usage)?;
+        let selector: Selector = args[0].parse().map_err(usage)?;
+        let root = self.target.with_pid(pid).element()?;
+        let finder = ElementFinder::with_selector(&root, selector, self.wait);
+        let found = if self.first {
+            finder.find().map(|element| vec![element])
+        } else {
+            finder.find_all()
+        };
+        let mut found = found.map_err(|e| fail(&args[0], e))?;
+
+        if found.len() > 1 {
+            eprintln!("{} matches {} elements:", args[0], found.len());
+
+            for element in &found {
+                eprintln!("  {}", query::label(element));
+            }
+
+            eprintln!("make the selector more specific or pass --first");
+            return Err(AMBIGUOUS);
+        }
+
+        Ok((found.remove(0), &args[1..]))
+    }
+}
+
+pub fn action_names(element: &AXUIElement) -> Result<Vec<String>, Error> {
+    Ok(element
+        .action_names()?
+        .into_iter()
+        .map(|name| name.to_string())
+        .collect())
+}
+
+pub fn perform(element: &AXUIElement, action: &str) -> Result<(), i32> {
+    let label = query::label(element);
+    let actions = action_names(element).map_err(|e| fail(&label, e))?;
+
+    if !actions.iter().any(|a| a == action) {
+        eprintln!(
+            "{} doesn't support {} (it supports: {})",
+            label,
+            action,
+            if actions.is_empty() {
+                "nothing".to_owned()
+            } else {
+                actions.join(", ")
+            }
+        );
+        return Err(UNSUPPORTED_ACTION);
+    }
+
+    element
+        .perform_action(&CFString::new(action))
+        .map_err(|e| fail(&format!("{} {}", label, action), e))
+}
+
+pub fn press(options: &ElementOpt, args: &[String]) -> Result<(), i32> {
+    let (element, _) = options.find(args, 0)?;
+
+    perform(&element, kAXPressAction)
+}
+
+pub fn action(options: &ElementOpt, args: &[String]) -> Result<(), i32> {
+    let (element, rest) = options.find(args, 1)?;
+
+    perform(&element, &rest[0])
+}
+
+pub fn set(options: &ElementOpt, args: &[String]) -> Result<(), i32> {
+    let (element, rest) = options.find(args, 2)?;
+
+    set_value(&element, &rest[0], &rest[1])
+}
+
+/// Sets attribute `name` of `element` from `text`, which is parsed to match
+/// the attribute's current type.
+pub fn set_value(element: &AXUIElement, name: &str, text: &str) -> Result<(), i32> {
+    let label = format!("{} {}", query::label(element), name);
+    let attribute = AXAttribute::new(&CFString::new(name));
+
+    if !element
+        .is_settable(&attribute)
+        .map_err(|e| fail(&label, e))?
+    {
+        eprintln!("{} is not settable", label);
+        return Err(NOT_SETTABLE);
+    }
+
+    // The new value takes the type of the current one; attributes without a
+    // value are assumed to take strings.
+    let current = element
+        .attribute(&attribute)
+        .map(|v| AXAny::from(&v))
+        .unwrap_or_else(|_| AXAny::String(String::new()));
+    let value = current.parse_as(text).map_err(usage)?;
+    let value = value
+        .to_cftype()
+        .ok_or_else(|| usage(format!("{} values can't be set", value.kind())))?;
+
+    element
+        .set_attribute(&attribute, value)
+        .map_err(|e| fail(&label, e))
+}
@@ -1,70 +1,421 @@
 use accessibility::{
-    AXAttribute, AXUIElement, AXUIElementAttributes, TreeVisitor, TreeWalker, TreeWalkerFlow,
+    AttributeFilter, Collapse, MaxDepth, SnapshotBuilder, SnapshotNode, TreeVisitor, TreeWalker,
+    VisibleOnly,
+};
+use std::{
+    env,
+    ffi::OsString,
+    path::{Path, PathBuf},
+    process,
+    time::Duration,
 };
-use core_foundation::{array::CFArray, string::CFString};
-use std::cell::Cell;
 use structopt::StructOpt;
 
-struct PrintyBoi {
-    level: Cell<usize>,
-    indent: String,
-    children: AXAttribute<CFArray<AXUIElement>>,
+mod at;
+mod diff;
+mod focus;
+mod format;
+mod interact;
+mod query;
+mod repl;
+mod shortcuts;
+mod snapshot;
+mod target;
+mod watch;
+
+/// Which parts of the tree `aq dump` prints.
+#[derive(Debug, StructOpt)]
+pub struct DumpFilters {
+    /// Don't descend more than this many levels below the target
+    #[structopt(long)]
+    max_depth: Option<usize>,
+    /// Only print elements with these roles, e.g. AXButton,AXTextField
+    #[structopt(long, use_delimiter = true)]
+    roles: Vec<String>,
+    /// Leave out elements with these roles but keep their children, e.g. AXGroup
+    #[structopt(long, use_delimiter = true)]
+    skip_roles: Vec<String>,
+    /// Only print these attributes (the role is always printed)
+    #[structopt(long, use_delimiter = true)]
+    attrs: Vec<String>,
+    /// Don't print these attributes
+    #[structopt(long, use_delimiter = true)]
+    exclude_attrs: Vec<String>,
+    /// Don't print empty strings, arrays and dictionaries
+    #[structopt(long)]
+    no_empty: bool,
+    /// Skip elements that are off screen, clipped or not in AXVisibleChildren
+    #[structopt(long)]
+    visible_only: bool,
 }
 
-impl PrintyBoi {
-    pub fn new_with_indentation(indent: usize) -> Self {
-        Self {
-            level: Cell::new(0),
-            indent: " ".repeat(indent),
-            children: AXAttribute::children(),
+impl DumpFilters {
+    fn attribute_filter(&self) -> AttributeFilter {
+        AttributeFilter {
+            include: self.attrs.clone(),
+            exclude: self.exclude_attrs.clone(),
+            no_empty: self.no_empty,
         }
     }
-}
 
-impl TreeVisitor for PrintyBoi {
-    fn enter_element(&self, element: &AXUIElement) -> TreeWalkerFlow {
-        let indent = self.indent.repeat(self.level.get());
-        let role = element.role().unwrap_or_else(|_| CFString::new(""));
-
-        self.level.replace(self.level.get() + 1);
-        println![
-            "{}- {} ({} children)",
-            indent,
-            role,
-            element.children().map(|c| c.len()).unwrap_or_default()
-        ];
-
-        if let Ok(names) = element.attribute_names() {
-            for name in names.into_iter() {
-                if &*name == self.children.as_CFString() {
-                    continue;
-                }
-
-                if let Ok(value) = element.attribute(&AXAttribute::new(&name)) {
-                    println!["{}|. {}: {:?}", indent, *name, value];
-                }
+    /// Filters a loaded snapshot the way a live walk would have.
+    fn apply(&self, tree: &mut SnapshotNode) {
+        fn apply_attributes(node: &mut SnapshotNode, filter: &AttributeFilter) {
+            filter.apply(node);
+
+            for child in &mut node.children {
+                apply_attributes(child, filter);
             }
         }
 
-        TreeWalkerFlow::Continue
+        tree.prune(self.max_depth.unwrap_or(usize::MAX), &|node| {
+            !self.roles.is_empty() && !self.roles.contains(&node.role)
+                || self.skip_roles.contains(&node.role)
+        });
+        apply_attributes(tree, &self.attribute_filter());
     }
+}
+
+#[derive(StructOpt)]
+pub enum Opt {
+    /// Print the accessibility tree of an application
+    Dump {
+        #[structopt(flatten)]
+        target: target::TargetOpt,
+        /// Output format: text, json, yaml or xml (Appium page source)
+        #[structopt(long, default_value = "text")]
+        format: format::OutputFormat,
+        #[structopt(flatten)]
+        filters: DumpFilters,
+        /// Print a snapshot file instead of a live application
+        #[structopt(long)]
+        snapshot: Option<PathBuf>,
+    },
+    /// Save a snapshot with capture details, for diffing or bug reports
+    Snapshot {
+        #[structopt(flatten)]
+        target: target::TargetOpt,
+        /// Write to this file instead of stdout
+        #[structopt(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Compare two snapshot files
+    Diff {
+        a: PathBuf,
+        b: PathBuf,
+        /// Print the differences as JSON
+        #[structopt(long)]
+        json: bool,
+    },
+    /// Print the elements matching a selector, e.g. 'AXButton[AXTitle=OK]'
+    Query {
+        #[structopt(flatten)]
+        target: target::TargetFlags,
+        /// An optional pid followed by the selector
+        #[structopt(
+            name = "PID_AND_SELECTOR",
+            min_values = 1,
+            max_values = 2,
+            required = true
+        )]
+        args: Vec<String>,
+        /// Search a snapshot file instead of a live application
+        #[structopt(long)]
+        snapshot: Option<PathBuf>,
+        /// Comma-separated attributes to print for each match
+        #[structopt(long, use_delimiter = true)]
+        attrs: Vec<String>,
+        /// Print the number of matches instead of the matches
+        #[structopt(long)]
+        count: bool,
+        /// Stop at the first match
+        #[structopt(long)]
+        first: bool,
+        /// Wait this long for a match to appear, e.g. 5s or 500ms
+        #[structopt(long, parse(try_from_str = query::parse_duration))]
+        wait: Option<Duration>,
+    },
+    /// Press the element matching a selector
+    Press {
+        #[structopt(flatten)]
+        element: interact::ElementOpt,
+        /// An optional pid followed by the selector
+        #[structopt(
+            name = "PID_AND_SELECTOR",
+            min_values = 1,
+            max_values = 2,
+            required = true
+        )]
+        args: Vec<String>,
+    },
+    /// Perform an action, e.g. AXShowMenu, on the element matching a selector
+    Action {
+        #[structopt(flatten)]
+        element: interact::ElementOpt,
+        /// An optional pid, the selector and the action
+        #[structopt(
+            name = "PID_SELECTOR_AND_ACTION",
+            min_values = 2,
+            max_values = 3,
+            required = true
+        )]
+        args: Vec<String>,
+    },
+    /// Set an attribute of the element matching a selector
+    Set {
+        #[structopt(flatten)]
+        element: interact::ElementOpt,
+        /// An optional pid, the selector, the attribute and its new value
+        #[structopt(
+            name = "PID_SELECTOR_ATTRIBUTE_AND_VALUE",
+            min_values = 3,
+            max_values = 4,
+            required = true
+        )]
+        args: Vec<String>,
+    },
+    /// Explore an accessibility tree interactively
+    Repl {
+        #[structopt(flatten)]
+        target: target::TargetOpt,
+        /// Explore a snapshot file instead of a live application
+        #[structopt(long)]
+        snapshot: Option<PathBuf>,
+    },
+    /// Print the element at a point on screen and its ancestors
+    At {
+        /// Screen coordinates: <x> <y> or <x>,<y>
+        #[structopt(name = "X_Y", max_values = 2, allow_hyphen_values = true)]
+        coordinates: Vec<String>,
+        /// Keep printing the element under the mouse as it moves
+        #[structopt(long)]
+        follow_mouse: bool,
+        /// Look the point up in a snapshot file's frames instead of on screen
+        #[structopt(long)]
+        snapshot: Option<PathBuf>,
+    },
+    /// Print the focused element, or with --follow, every element that gets focus
+    Focus {
+        /// Keep printing as focus moves, across applications
+        #[structopt(long)]
+        follow: bool,
+        /// Also write each focus change to this file as a line of JSON
+        #[structopt(long)]
+        log: Option<PathBuf>,
+    },
+    /// Print accessibility notifications as they arrive
+    Watch {
+        #[structopt(flatten)]
+        target: target::TargetOpt,
+        /// Notifications to watch, e.g. AXValueChanged,AXFocusedUIElementChanged
+        #[structopt(
+            long,
+            use_delimiter = true,
+            default_value = "AXFocusedUIElementChanged,AXValueChanged"
+        )]
+        notifications: Vec<String>,
+        /// Only print events for elements matching this selector
+        #[structopt(long)]
+        selector: Option<String>,
+        /// Stop after this long, e.g. 30s
+        #[structopt(long, parse(try_from_str = query::parse_duration))]
+        duration: Option<Duration>,
+        /// Print a JSON record per event
+        #[structopt(long)]
+        json: bool,
+    },
+    /// List menu commands and their keyboard shortcuts
+    Shortcuts {
+        #[structopt(flatten)]
+        target: target::TargetOpt,
+        /// Read the menus from a snapshot file instead of a live application
+        #[structopt(long)]
+        snapshot: Option<PathBuf>,
+        /// Output format: text, csv or json
+        #[structopt(long, default_value = "text")]
+        format: shortcuts::ReportFormat,
+    },
+}
 
-    fn exit_element(&self, _element: &AXUIElement) {
-        self.level.replace(self.level.get() - 1);
+/// The command line with `dump` inserted before a leading pid, so that
+/// `aq <pid>` still works as it did before aq had subcommands.
+fn args() -> Vec<OsString> {
+    let mut args: Vec<OsString> = env::args_os().collect();
+
+    if args
+        .get(1)
+        .and_then(|arg| arg.to_str())
+        .is_some_and(|arg| arg.parse::<i32>().is_ok())
+    {
+        args.insert(1, "dump".into());
     }
+
+    args
 }
 
-#[derive(StructOpt)]
-pub struct Opt {
-    pub pid: i32,
+fn main() {
+    let result = match Opt::from_iter(args()) {
+        Opt::Dump {
+            target,
+            format,
+            filters,
+            snapshot,
+        } => dump(&target, format, &filters, snapshot.as_deref()),
+        Opt::Snapshot { target, output } => snapshot::run(&target, output.as_deref()),
+        Opt::Diff { a, b, json } => diff::run(&a, &b, json),
+        Opt::Query {
+            target,
+            args,
+            snapshot,
+            attrs,
+            count,
+            first,
+            wait,
+        } => query(
+            &target,
+            &args,
+            snapshot.as_deref(),
+            attrs,
+            count,
+            first,
+            wait,
+        ),
+        Opt::Press { element, args } => interact::press(&element, &args),
+        Opt::Action { element, args } => interact::action(&element, &args),
+        Opt::Set { element, args } => interact::set(&element, &args),
+        Opt::Repl { target, snapshot } => repl(&target, snapshot.as_deref()),
+        Opt::At {
+            coordinates,
+            follow_mouse,
+            snapshot,
+        } => at::run(&coordinates, follow_mouse, snapshot.as_deref()),
+        Opt::Focus { follow, log } => focus::run(follow, log.as_deref()),
+        Opt::Watch {
+            target,
+            notifications,
+            selector,
+            duration,
+            json,
+        } => watch(&target, notifications, selector, duration, json),
+        Opt::Shortcuts {
+            target,
+            snapshot,
+            format,
+        } => shortcuts::run(&target, snapshot.as_deref(), format),
+    };
+
+    if let Err(code) = result {
+        process::exit(code);
+    }
 }
 
-fn main() -> Result<(), i32> {
-    let opt = Opt::from_args();
-    let app = AXUIElement::application(opt.pid);
-    let printy = PrintyBoi::new_with_indentation(4);
-    let walker = TreeWalker::new();
+fn dump(
+    target: &target::TargetOpt,
+    format: format::OutputFormat,
+    filters: &DumpFilters,
+    snapshot: Option<&Path>,
+) -> Result<(), i32> {
+    if let Some(path) = snapshot {
+        if target.is_given() {
+            eprintln!("--snapshot can't be combined with a target");
+            return Err(2);
+        }
+
+        if filters.visible_only {
+            eprintln!("--visible-only only applies to live targets, not --snapshot");
+            return Err(2);
+        }
+
+        let mut tree = snapshot::load_snapshot(path)?;
+
+        filters.apply(&mut tree);
+        print!["{}", format::render(&tree, format)];
+        return Ok(());
+    }
+
+    let root = target.element()?;
+    let builder = SnapshotBuilder::with_filter(filters.attribute_filter());
+    let only = Collapse::roles(&builder, filters.roles.clone());
+    let roles: &dyn TreeVisitor = if filters.roles.is_empty() {
+        &builder
+    } else {
+        &only
+    };
+    let collapse = Collapse::skip_roles(roles, filters.skip_roles.clone());
+    let visible = VisibleOnly::new(&collapse);
+    let visitor: &dyn TreeVisitor = if filters.visible_only {
+        &visible
+    } else {
+        &collapse
+    };
 
-    walker.walk(&app, &printy);
+    TreeWalker::new().walk(
+        &root,
+        &MaxDepth::new(visitor, filters.max_depth.unwrap_or(usize::MAX)),
+    );
+
+    let tree = builder.finish().unwrap_or_default();
+
+    print!["{}", format::render(&tree, format)];
     Ok(())
 }
+
+fn query(
+    target: &target::TargetFlags,
+    args: &[String],
+    snapshot: Option<&Path>,
+    attrs: Vec<String>,
+    count: bool,
+    first: bool,
+    wait: Option<Duration>,
+) -> Result<(), i32> {
+    let (pid, args) = target::split_pid(args, 1).map_err(|e| {
+        eprintln!("{}", e);
+        2
+    })?;
+    let options = query::QueryOptions {
+        attrs,
+        count,
+        first,
+        wait,
+    };
+
+    query::run(&target.with_pid(pid), snapshot, &args[0], &options)
+}
+
+fn repl(target: &target::TargetOpt, snapshot: Option<&Path>) -> Result<(), i32> {
+    match snapshot {
+        Some(_) if target.is_given() => {
+            eprintln!("--snapshot can't be combined with a target");
+            Err(2)
+        }
+        Some(path) => repl::run(&snapshot::load_snapshot(path)?),
+        None => repl::run(target.element()?),
+    }
+}
+
+fn watch(
+    target: &target::TargetOpt,
+    notifications: Vec<String>,
+    selector: Option<String>,
+    duration: Option<Duration>,
+    json: bool,
+) -> Result<(), i32> {
+    let selector = match selector {
+        Some(selector) => Some(selector.parse().map_err(|e| {
+            eprintln!("{}", e);
+            2
+        })?),
+        None => None,
+    };
+
+    watch::run(
+        target,
+        watch::WatchOptions {
+            notifications,
+            selector,
+            duration,
+            json,
+        },
+    )
+}
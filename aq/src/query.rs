@@ -0,0 +1,127 @@
+use accessibility::{AXAny, AXUIElement, ElementFinder, Error, Selectable, Selector};
+use std::{path::Path, time::Duration};
+
+use crate::{interact, snapshot::load_snapshot, target::TargetOpt};
+
+pub struct QueryOptions {
+    /// Attributes to print after each match's label.
+    pub attrs: Vec<String>,
+    pub count: bool,
+    pub first: bool,
+    /// How long to wait for a live target to have a match.
+    pub wait: Option<Duration>,
+}
+
+/// Parses `500ms`, `5s`, `2m` or a bare number of seconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let err = || format!("invalid duration {:?}", s);
+    let (number, scale) = if let Some(ms) = s.strip_suffix("ms") {
+        (ms, 0.001)
+    } else if let Some(secs) = s.strip_suffix('s') {
+        (secs, 1.0)
+    } else if let Some(mins) = s.strip_suffix('m') {
+        (mins, 60.0)
+    } else {
+        (s, 1.0)
+    };
+    let number: f64 = number.trim().parse().map_err(|_| err())?;
+
+    if number.is_finite() && number >= 0.0 {
+        Ok(Duration::from_secs_f64(number * scale))
+    } else {
+        Err(err())
+    }
+}
+
+/// A human-readable label built from the attributes selectors usually
+/// match on: `AXButton "OK"` or `AXTextField #username`.
+pub fn label(target: &impl Selectable) -> String {
+    let text = |name: &str| match target.selector_attribute(name) {
+        Some(AXAny::String(s)) if !s.is_empty() => Some(s),
+        _ => None,
+    };
+    let mut label = text("AXRole").unwrap_or_default();
+
+    if let Some(identifier) = text("AXIdentifier") {
+        label.push_str(&format!(" #{}", identifier));
+    } else if let Some(title) = text("AXTitle") {
+        label.push_str(&format!(" {:?}", title));
+    }
+
+    label
+}
+
+/// One line per match: its label followed by the requested attributes.
+pub fn format_match(target: &impl Selectable, attrs: &[String]) -> String {
+    let mut line = label(target);
+
+    for name in attrs {
+        match target.selector_attribute(name) {
+            Some(AXAny::String(s)) => line.push_str(&format!("\t{}={:?}", name, s)),
+            Some(value) => line.push_str(&format!("\t{}={}", name, value)),
+            None => line.push_str(&format!("\t{}=<none>", name)),
+        }
+    }
+
+    line
+}
+
+/// Prints the matches and exits with 1 when there are none, like grep(1).
+fn report<T: Selectable>(matches: &[T], options: &QueryOptions) -> Result<(), i32> {
+    let matches = if options.first && !matches.is_empty() {
+        &matches[..1]
+    } else {
+        matches
+    };
+
+    if options.count {
+        println!["{}", matches.len()];
+    } else {
+        for target in matches {
+            println!["{}", format_match(target, &options.attrs)];
+        }
+    }
+
+    if matches.is_empty() {
+        Err(1)
+    } else {
+        Ok(())
+    }
+}
+
+pub fn run(
+    target: &TargetOpt,
+    snapshot: Option<&Path>,
+    selector: &str,
+    options: &QueryOptions,
+) -> Result<(), i32> {
+    let selector: Selector = selector.parse().map_err(|e| {
+        eprintln!("{}", e);
+        2
+    })?;
+
+    match snapshot {
+        Some(_) if target.is_given() => {
+            eprintln!("--snapshot can't be combined with a target");
+            Err(2)
+        }
+        Some(path) => report(&selector.select(&load_snapshot(path)?), options),
+        None => {
+            let finder = ElementFinder::with_selector(&target.element()?, selector, options.wait);
+            let matches = if options.first {
+                finder.find().map(|element| vec![element])
+            } else {
+                finder.find_all()
+            };
+
+            match matches {
+                Ok(matches) => report(&matches, options),
+                Err(Error::NotFound) => report::<AXUIElement>(&[], options),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    Err(interact::exit_code(&e))
+                }
+            }
+        }
+    }
+}
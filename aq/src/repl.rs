@@ -0,0 +1,657 @@
+//! `aq repl`: an interactive shell for walking an accessibility tree, live
+//! or from a snapshot.
+
+use accessibility::{
+    AXAny, AXUIElement, AXUIElementAttributes, Selectable, Selector, SnapshotNode,
+};
+use accessibility_sys::kAXPressAction;
+use rustyline::{
+    completion::Completer, error::ReadlineError, highlight::Highlighter, hint::Hinter,
+    history::DefaultHistory, validate::Validator, Context, Editor, Helper,
+};
+use std::{path::PathBuf, str::FromStr};
+
+use crate::{interact, query};
+
+const HELP: &str = "\
+ls                      list the children of the current element
+cd <index|path|selector> move to a child (0), a descendant (0/2/1) or the first match
+cd .. | ..              move to the parent
+cd /                    move to the root
+pwd                     print the path to the current element
+attrs                   print every attribute
+get <attribute>         print one attribute
+set <attribute> <value> set an attribute
+actions                 list the supported actions
+press                   perform AXPress
+action <action>         perform an action
+find <selector>         list the descendants matching a selector
+tree [-d <depth>]       print the subtree, 2 levels deep by default
+help                    print this help
+exit                    leave (so does ^D)";
+
+const COMMANDS: &[&str] = &[
+    "ls", "cd", "..", "pwd", "attrs", "get", "set", "actions", "press", "action", "find", "tree",
+    "help", "exit",
+];
+
+/// Searches stop this far below the current element.
+const MAX_FIND_DEPTH: usize = 100;
+
+/// What the REPL needs from the tree it explores, so that it runs the same
+/// against a live application and against a snapshot.
+pub trait Node: Selectable + Clone {
+    fn children(&self) -> Vec<Self>;
+    fn attribute_names(&self) -> Vec<String>;
+    fn action_names(&self) -> Vec<String>;
+    /// Sets an attribute, printing any error and returning the exit code.
+    fn set(&self, name: &str, text: &str) -> Result<(), i32>;
+    /// Performs an action, printing any error and returning the exit code.
+    fn perform(&self, action: &str) -> Result<(), i32>;
+}
+
+impl Node for AXUIElement {
+    fn children(&self) -> Vec<Self> {
+        AXUIElementAttributes::children(self)
+            .map(|children| children.iter().map(|c| c.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    fn attribute_names(&self) -> Vec<String> {
+        AXUIElement::attribute_names(self)
+            .map(|names| names.iter().map(|n| n.to_string()).collect())
+            .unwrap_or_default()
+    }
+
+    fn action_names(&self) -> Vec<String> {
+        interact::action_names(self).unwrap_or_default()
+    }
+
+    fn set(&self, name: &str, text: &str) -> Result<(), i32> {
+        interact::set_value(self, name, text)
+    }
+
+    fn perform(&self, action: &str) -> Result<(), i32> {
+        interact::perform(self, action)
+    }
+}
+
+fn read_only() -> Result<(), i32> {
+    eprintln!("snapshots are read-only");
+    Err(2)
+}
+
+impl Node for &SnapshotNode {
+    fn children(&self) -> Vec<Self> {
+        self.children.iter().collect()
+    }
+
+    fn attribute_names(&self) -> Vec<String> {
+        self.all_attributes().into_keys().collect()
+    }
+
+    fn action_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn set(&self, _name: &str, _text: &str) -> Result<(), i32> {
+        read_only()
+    }
+
+    fn perform(&self, _action: &str) -> Result<(), i32> {
+        read_only()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum CdTarget {
+    Parent,
+    Root,
+    /// Child indices, one per level.
+    Indices(Vec<usize>),
+    Selector(Selector),
+}
+
+#[derive(Clone, Debug)]
+pub enum Command {
+    Ls,
+    Cd(CdTarget),
+    Pwd,
+    Attrs,
+    Get(String),
+    Set(String, String),
+    Actions,
+    Action(String),
+    Find(Selector),
+    Tree(usize),
+    Help,
+    Exit,
+}
+
+impl FromStr for CdTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            ".." => Ok(CdTarget::Parent),
+            "/" => Ok(CdTarget::Root),
+            _ if s.starts_with(|c: char| c.is_ascii_digit()) => s
+                .split('/')
+                .map(|index| index.parse())
+                .collect::<Result<_, _>>()
+                .map(CdTarget::Indices)
+                .map_err(|_| format!("invalid index path {:?}", s)),
+            _ => s.parse().map(CdTarget::Selector).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (word, rest) = match s.split_once(char::is_whitespace) {
+            Some((word, rest)) => (word, rest.trim()),
+            None => (s, ""),
+        };
+        let required = |what: &str| {
+            if rest.is_empty() {
+                Err(format!("usage: {} {}", word, what))
+            } else {
+                Ok(rest.to_owned())
+            }
+        };
+
+        match word {
+            "ls" => Ok(Command::Ls),
+            "cd" => required("<index|path|selector>")?.parse().map(Command::Cd),
+            ".." => Ok(Command::Cd(CdTarget::Parent)),
+            "pwd" => Ok(Command::Pwd),
+            "attrs" => Ok(Command::Attrs),
+            "get" => required("<attribute>").map(Command::Get),
+            "set" => match required("<attribute> <value>")?.split_once(char::is_whitespace) {
+                Some((name, value)) => Ok(Command::Set(name.to_owned(), value.trim().to_owned())),
+                None => Err("usage: set <attribute> <value>".to_owned()),
+            },
+            "actions" => Ok(Command::Actions),
+            "press" => Ok(Command::Action(kAXPressAction.to_owned())),
+            "action" => required("<action>").map(Command::Action),
+            "find" => required("<selector>")?
+                .parse()
+                .map(Command::Find)
+                .map_err(|e: accessibility::ParseSelectorError| e.to_string()),
+            "tree" => match rest.split_whitespace().collect::<Vec<_>>().as_slice() {
+                [] => Ok(Command::Tree(2)),
+                ["-d", depth] => depth
+                    .parse()
+                    .map(Command::Tree)
+                    .map_err(|_| format!("invalid depth {:?}", depth)),
+                _ => Err("usage: tree [-d <depth>]".to_owned()),
+            },
+            "help" | "?" => Ok(Command::Help),
+            "exit" | "quit" => Ok(Command::Exit),
+            _ => Err(format!("unknown command {:?}, try help", word)),
+        }
+    }
+}
+
+/// An attribute value as the REPL prints it, with strings quoted.
+pub fn display(value: &AXAny) -> String {
+    match value {
+        AXAny::String(s) => format!("{:?}", s),
+        value => value.to_string(),
+    }
+}
+
+/// The REPL's state: the path from the root to the current element.
+pub struct Session<N> {
+    path: Vec<N>,
+}
+
+impl<N: Node> Session<N> {
+    pub fn new(root: N) -> Self {
+        Self { path: vec![root] }
+    }
+
+    pub fn current(&self) -> &N {
+        self.path.last().unwrap()
+    }
+
+    pub fn prompt(&self) -> String {
+        format!("{}> ", query::label(self.current()))
+    }
+
+    /// Runs `command`, returning false when the session should end.
+    pub fn execute(&mut self, command: Command) -> bool {
+        match command {
+            Command::Ls => {
+                for (i, child) in self.current().children().iter().enumerate() {
+                    println!["{:>4}  {}", i, query::label(child)];
+                }
+            }
+            Command::Cd(target) => self.cd(target),
+            Command::Pwd => println!["{}", self.pwd()],
+            Command::Attrs => {
+                let current = self.current();
+
+                for name in current.attribute_names() {
+                    match current.selector_attribute(&name) {
+                        Some(value) => println!["{}: {}", name, display(&value)],
+                        None => println!["{}: <none>", name],
+                    }
+                }
+            }
+            Command::Get(name) => match self.current().selector_attribute(&name) {
+                Some(value) => println!["{}", display(&value)],
+                None => eprintln!("{} has no {}", query::label(self.current()), name),
+            },
+            Command::Set(name, value) => {
+                let _ = self.current().set(&name, &value);
+            }
+            Command::Actions => {
+                for action in self.current().action_names() {
+                    println!["{}", action];
+                }
+            }
+            Command::Action(action) => {
+                let _ = self.current().perform(&action);
+            }
+            Command::Find(selector) => {
+                for (indices, path) in self.find(&selector) {
+                    println![
+                        "{:>8}  {}",
+                        join_indices(&indices),
+                        query::label(path.last().unwrap())
+                    ];
+                }
+            }
+            Command::Tree(depth) => print!["{}", tree(self.current(), depth)],
+            Command::Help => println!["{}", HELP],
+            Command::Exit => return false,
+        }
+
+        true
+    }
+
+    pub fn pwd(&self) -> String {
+        self.path
+            .iter()
+            .map(query::label)
+            .collect::<Vec<_>>()
+            .join(" > ")
+    }
+
+    fn cd(&mut self, target: CdTarget) {
+        match target {
+            CdTarget::Parent if self.path.len() > 1 => {
+                self.path.pop();
+            }
+            CdTarget::Parent => eprintln!("already at the root"),
+            CdTarget::Root => self.path.truncate(1),
+            CdTarget::Indices(indices) => {
+                let mut path = Vec::new();
+
+                for (level, index) in indices.iter().enumerate() {
+                    let parent = path.last().unwrap_or_else(|| self.current());
+
+                    match parent.children().into_iter().nth(*index) {
+                        Some(child) => path.push(child),
+                        None => {
+                            eprintln!("no child {}", join_indices(&indices[..=level]));
+                            return;
+                        }
+                    }
+                }
+
+                self.path.extend(path);
+            }
+            CdTarget::Selector(selector) => match self.find_first(&selector) {
+                Some((_, path)) => self.path.extend(path.into_iter().skip(1)),
+                None => eprintln!("nothing matches {}", selector),
+            },
+        }
+    }
+
+    /// Descendants of the current element that match, in pre-order, with
+    /// their child indices and their path from the current element. The
+    /// selector sees the whole path from the root, so its ancestor steps can
+    /// match above the current element.
+    pub fn find(&self, selector: &Selector) -> Vec<(Vec<usize>, Vec<N>)> {
+        self.find_up_to(selector, usize::MAX)
+    }
+
+    /// The first match [`Session::find`] would return, without searching
+    /// the rest of the tree.
+    pub fn find_first(&self, selector: &Selector) -> Option<(Vec<usize>, Vec<N>)> {
+        self.find_up_to(selector, 1).pop()
+    }
+
+    fn find_up_to(&self, selector: &Selector, limit: usize) -> Vec<(Vec<usize>, Vec<N>)> {
+        let mut found = Vec::new();
+        let current = self.path.len() - 1;
+
+        search(
+            selector,
+            limit,
+            &mut self.path.clone(),
+            &mut Vec::new(),
+            &mut found,
+        );
+        found
+            .into_iter()
+            .map(|(indices, mut path)| (indices, path.split_off(current)))
+            .collect()
+    }
+}
+
+fn join_indices(indices: &[usize]) -> String {
+    indices
+        .iter()
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn search<N: Node>(
+    selector: &Selector,
+    limit: usize,
+    path: &mut Vec<N>,
+    indices: &mut Vec<usize>,
+    found: &mut Vec<(Vec<usize>, Vec<N>)>,
+) {
+    if indices.len() >= MAX_FIND_DEPTH {
+        return;
+    }
+
+    for (i, child) in path.last().unwrap().children().into_iter().enumerate() {
+        if found.len() >= limit {
+            return;
+        }
+
+        path.push(child);
+        indices.push(i);
+
+        if selector.matches_path(path) {
+            found.push((indices.clone(), path.clone()));
+        }
+
+        search(selector, limit, path, indices, found);
+        path.pop();
+        indices.pop();
+    }
+}
+
+/// An outline of labels, `depth` levels below `node`.
+pub fn tree<N: Node>(node: &N, depth: usize) -> String {
+    let mut out = String::new();
+
+    tree_one(node, depth, 0, &mut out);
+    out
+}
+
+fn tree_one<N: Node>(node: &N, depth: usize, level: usize, out: &mut String) {
+    out.push_str(&format!("{}{}\n", "    ".repeat(level), query::label(node)));
+
+    if level < depth {
+        for child in node.children() {
+            tree_one(&child, depth, level + 1, out);
+        }
+    }
+}
+
+/// Completes command names, then attribute names after `get` and `set` and
+/// action names after `action`, from the current element.
+#[derive(Default)]
+pub struct Completions {
+    pub attributes: Vec<String>,
+    pub actions: Vec<String>,
+}
+
+impl Completions {
+    fn for_node<N: Node>(node: &N) -> Self {
+        Self {
+            attributes: node.attribute_names(),
+            actions: node.action_names(),
+        }
+    }
+
+    /// The start of the word before `pos` and the candidates for it.
+    pub fn candidates(&self, line: &str, pos: usize) -> (usize, Vec<String>) {
+        let line = &line[..pos];
+        let start = line.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let word = &line[start..];
+        let names: Vec<&str> = match line[..start].split_whitespace().collect::<Vec<_>>()[..] {
+            [] => COMMANDS.to_vec(),
+            ["get"] | ["set"] => self.attributes.iter().map(|s| s.as_str()).collect(),
+            ["action"] => self.actions.iter().map(|s| s.as_str()).collect(),
+            _ => Vec::new(),
+        };
+
+        (
+            start,
+            names
+                .into_iter()
+                .filter(|name| name.starts_with(word))
+                .map(|name| name.to_owned())
+                .collect(),
+        )
+    }
+}
+
+impl Completer for Completions {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(self.candidates(line, pos))
+    }
+}
+
+impl Hinter for Completions {
+    type Hint = String;
+}
+
+impl Highlighter for Completions {}
+
+impl Validator for Completions {}
+
+impl Helper for Completions {}
+
+fn history_path() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".aq_history"))
+}
+
+pub fn run<N: Node>(root: N) -> Result<(), i32> {
+    let mut editor = Editor::<Completions, DefaultHistory>::new().map_err(|e| {
+        eprintln!("can't start the line editor: {}", e);
+        1
+    })?;
+    let history = history_path();
+    let mut session = Session::new(root);
+
+    if let Some(history) = &history {
+        let _ = editor.load_history(history);
+    }
+
+    loop {
+        editor.set_helper(Some(Completions::for_node(session.current())));
+
+        let line = match editor.readline(&session.prompt()) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => {
+                eprintln!("{}", e);
+                break;
+            }
+        };
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let _ = editor.add_history_entry(line.as_str());
+
+        match line.parse() {
+            Ok(command) => {
+                if !session.execute(command) {
+                    break;
+                }
+            }
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+
+    if let Some(history) = &history {
+        let _ = editor.save_history(history);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(role: &str, children: Vec<SnapshotNode>) -> SnapshotNode {
+        SnapshotNode {
+            role: role.to_owned(),
+            children,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn find_first_stops_at_the_first_match() {
+        let root = node(
+            "AXWindow",
+            vec![
+                node("AXGroup", vec![node("AXButton", vec![])]),
+                node("AXButton", vec![]),
+            ],
+        );
+        let session = Session::new(&root);
+        let selector: Selector = "AXButton".parse().unwrap();
+        let indices: Vec<_> = session
+            .find(&selector)
+            .into_iter()
+            .map(|(indices, _)| indices)
+            .collect();
+
+        assert_eq!(indices, [vec![0, 0], vec![1]]);
+
+        let (indices, path) = session.find_first(&selector).unwrap();
+
+        assert_eq!(indices, [0, 0]);
+        assert_eq!(
+            path.iter()
+                .map(|node| node.role.as_str())
+                .collect::<Vec<_>>(),
+            ["AXWindow", "AXGroup", "AXButton"]
+        );
+        assert!(session.find_first(&"AXSheet".parse().unwrap()).is_none());
+    }
+
+    #[test]
+    fn find_matches_ancestors_above_the_current_element() {
+        let root = node(
+            "AXApplication",
+            vec![node(
+                "AXWindow",
+                vec![node("AXGroup", vec![node("AXButton", vec![])])],
+            )],
+        );
+        let mut session = Session::new(&root);
+
+        session.cd(CdTarget::Indices(vec![0, 0]));
+        assert_eq!(session.current().role, "AXGroup");
+
+        let (indices, path) = session
+            .find_first(&"AXWindow AXButton".parse().unwrap())
+            .unwrap();
+
+        assert_eq!(indices, [0]);
+        assert_eq!(
+            path.iter()
+                .map(|node| node.role.as_str())
+                .collect::<Vec<_>>(),
+            ["AXGroup", "AXButton"]
+        );
+
+        session.cd(CdTarget::Selector(
+            "AXWindow > AXGroup > AXButton".parse().unwrap(),
+        ));
+        assert_eq!(session.current().role, "AXButton");
+        assert_eq!(session.path.len(), 4);
+    }
+
+    #[test]
+    fn parses_commands() {
+        assert!(matches!(
+            "set AXValue  hello world ".parse(),
+            Ok(Command::Set(name, value)) if name == "AXValue" && value == "hello world"
+        ));
+        assert!(matches!("tree".parse(), Ok(Command::Tree(2))));
+        assert!(matches!("tree -d 5".parse(), Ok(Command::Tree(5))));
+        assert!(matches!("press".parse(), Ok(Command::Action(a)) if a == kAXPressAction));
+        assert!(matches!(
+            "cd 0/2/1".parse(),
+            Ok(Command::Cd(CdTarget::Indices(i))) if i == [0, 2, 1]
+        ));
+        assert!(matches!("..".parse(), Ok(Command::Cd(CdTarget::Parent))));
+        assert!(matches!(
+            "cd AXButton".parse(),
+            Ok(Command::Cd(CdTarget::Selector(_)))
+        ));
+    }
+
+    #[test]
+    fn rejects_bad_commands() {
+        let error = |line: &str| line.parse::<Command>().unwrap_err();
+
+        assert_eq!(error("set AXValue"), "usage: set <attribute> <value>");
+        assert_eq!(error("get"), "usage: get <attribute>");
+        assert_eq!(error("tree -d x"), "invalid depth \"x\"");
+        assert_eq!(error("tree 3"), "usage: tree [-d <depth>]");
+        assert_eq!(error("cd 0/x"), "invalid index path \"0/x\"");
+        assert_eq!(error("cd 1//2"), "invalid index path \"1//2\"");
+        assert_eq!(
+            error("frobnicate"),
+            "unknown command \"frobnicate\", try help"
+        );
+    }
+
+    #[test]
+    fn completes_commands_attributes_and_actions() {
+        let completions = Completions {
+            attributes: vec!["AXTitle".to_owned(), "AXValue".to_owned()],
+            actions: vec!["AXPress".to_owned(), "AXShowMenu".to_owned()],
+        };
+        let candidates = |line: &str| completions.candidates(line, line.len());
+
+        assert_eq!(
+            candidates("a"),
+            (
+                0,
+                vec![
+                    "attrs".to_owned(),
+                    "actions".to_owned(),
+                    "action".to_owned()
+                ]
+            )
+        );
+        assert_eq!(candidates("get AXV"), (4, vec!["AXValue".to_owned()]));
+        assert_eq!(candidates("set "), (4, completions.attributes.clone()));
+        assert_eq!(candidates("action AXS"), (7, vec!["AXShowMenu".to_owned()]));
+        assert_eq!(candidates("get AXValue x"), (12, vec![]));
+        assert_eq!(
+            completions.candidates("get AXValue", 5),
+            (4, vec!["AXTitle".to_owned(), "AXValue".to_owned()])
+        );
+    }
+}
@@ -0,0 +1,377 @@
+use accessibility::{AXUIElementAttributes, AXUIElementMenus, MenuItem, Shortcut};
+use serde::Serialize;
+use std::{collections::BTreeMap, path::Path, str::FromStr};
+
+use crate::{snapshot::load_snapshot, target::TargetOpt};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Text,
+    Csv,
+    Json,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(ReportFormat::Text),
+            "csv" => Ok(ReportFormat::Csv),
+            "json" => Ok(ReportFormat::Json),
+            _ => Err(format!("unknown format {:?}", s)),
+        }
+    }
+}
+
+/// A menu command: an item that does something when chosen, as opposed to a
+/// submenu or separator.
+#[derive(Clone, Debug, Serialize)]
+pub struct Command {
+    pub path: Vec<String>,
+    pub enabled: bool,
+    pub shortcut: Option<Shortcut>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ShortcutReport {
+    pub commands: Vec<Command>,
+    /// Shortcuts bound to more than one command, with the paths using them.
+    pub duplicates: BTreeMap<String, Vec<Vec<String>>>,
+    /// Paths of commands without a shortcut.
+    pub missing: Vec<Vec<String>>,
+}
+
+impl ShortcutReport {
+    pub fn new(menu_bar: &[MenuItem]) -> Self {
+        let mut report = ShortcutReport::default();
+        let mut path = Vec::new();
+
+        collect(menu_bar, &mut path, &mut report.commands);
+
+        let mut by_shortcut: BTreeMap<String, Vec<Vec<String>>> = BTreeMap::new();
+
+        for command in &report.commands {
+            match &command.shortcut {
+                Some(shortcut) => by_shortcut
+                    .entry(shortcut.to_string())
+                    .or_default()
+                    .push(command.path.clone()),
+                None => report.missing.push(command.path.clone()),
+            }
+        }
+
+        report.duplicates = by_shortcut
+            .into_iter()
+            .filter(|(_, paths)| paths.len() > 1)
+            .collect();
+        report
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+
+        for command in &self.commands {
+            let shortcut = command
+                .shortcut
+                .as_ref()
+                .map(|s| s.to_string())
+                .unwrap_or_default();
+            let disabled = if command.enabled { "" } else { " (disabled)" };
+
+            out.push_str(&format!(
+                "{:<8} {}{}\n",
+                shortcut,
+                command.path.join(" > "),
+                disabled
+            ));
+        }
+
+        if !self.duplicates.is_empty() {
+            out.push_str("\nduplicate shortcuts:\n");
+
+            for (shortcut, paths) in &self.duplicates {
+                out.push_str(&format!("  {}\n", shortcut));
+
+                for path in paths {
+                    out.push_str(&format!("    {}\n", path.join(" > ")));
+                }
+            }
+        }
+
+        out.push_str(&format!(
+            "\n{} commands, {} without a shortcut, {} duplicate shortcuts\n",
+            self.commands.len(),
+            self.missing.len(),
+            self.duplicates.len()
+        ));
+        out
+    }
+
+    /// One row per command: path, shortcut, spelled-out shortcut, enabled,
+    /// duplicate.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("path,shortcut,keys,enabled,duplicate\n");
+
+        for command in &self.commands {
+            let shortcut = command.shortcut.as_ref();
+            let duplicate = shortcut
+                .map(|s| self.duplicates.contains_key(&s.to_string()))
+                .unwrap_or(false);
+            let fields = [
+                command.path.join(" > "),
+                shortcut.map(|s| s.to_string()).unwrap_or_default(),
+                shortcut.map(|s| format!("{:#}", s)).unwrap_or_default(),
+                command.enabled.to_string(),
+                duplicate.to_string(),
+            ];
+            let fields: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+
+            out.push_str(&fields.join(","));
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+fn collect(items: &[MenuItem], path: &mut Vec<String>, commands: &mut Vec<Command>) {
+    for item in items {
+        if item.title.is_empty() {
+            continue;
+        }
+
+        path.push(item.title.clone());
+
+        if item.children.is_empty() {
+            // Top-level menu bar titles aren't commands, even when their menu
+            // couldn't be read.
+            if path.len() > 1 {
+                commands.push(Command {
+                    path: path.clone(),
+                    enabled: item.enabled,
+                    shortcut: item.shortcut.clone(),
+                });
+            }
+        } else {
+            collect(&item.children, path, commands);
+        }
+
+        path.pop();
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains(&[',', '"', '\n'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+fn live_menu_bar(target: &TargetOpt) -> Result<Vec<MenuItem>, i32> {
+    let items = target
+        .element()?
+        .menu_bar()
+        .and_then(|menu_bar| menu_bar.list_menu_items());
+
+    items.map_err(|e| {
+        eprintln!("{}", e);
+        1
+    })
+}
+
+pub fn run(target: &TargetOpt, snapshot: Option<&Path>, format: ReportFormat) -> Result<(), i32> {
+    let menu_bar = match snapshot {
+        Some(_) if target.is_given() => {
+            eprintln!("--snapshot can't be combined with a target");
+            return Err(2);
+        }
+        Some(path) => MenuItem::from_snapshot(&load_snapshot(path)?),
+        None => live_menu_bar(target)?,
+    };
+    let report = ShortcutReport::new(&menu_bar);
+
+    match format {
+        ReportFormat::Text => print!["{}", report.to_text()],
+        ReportFormat::Csv => print!["{}", report.to_csv()],
+        ReportFormat::Json => println!["{}", serde_json::to_string_pretty(&report).unwrap()],
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use accessibility::{AXAny, Number, SnapshotNode};
+    use accessibility_sys::{
+        kAXEnabledAttribute, kAXMenuBarItemRole, kAXMenuBarRole, kAXMenuItemCmdCharAttribute,
+        kAXMenuItemCmdModifiersAttribute, kAXMenuItemModifierShift, kAXMenuItemRole, kAXMenuRole,
+    };
+
+    fn node(role: &str, title: &str, children: Vec<SnapshotNode>) -> SnapshotNode {
+        SnapshotNode {
+            role: role.to_owned(),
+            title: Some(title.to_owned()),
+            children,
+            ..Default::default()
+        }
+    }
+
+    fn item(title: &str, enabled: bool, shortcut: Option<(&str, u32)>) -> SnapshotNode {
+        let mut node = node(kAXMenuItemRole, title, vec![]);
+
+        node.attributes
+            .insert(kAXEnabledAttribute.to_owned(), AXAny::Bool(enabled));
+        if let Some((c, modifiers)) = shortcut {
+            node.attributes
+                .insert(kAXMenuItemCmdCharAttribute.to_owned(), AXAny::from(c));
+            node.attributes.insert(
+                kAXMenuItemCmdModifiersAttribute.to_owned(),
+                AXAny::Number(Number::Integer(modifiers as i64)),
+            );
+        }
+
+        node
+    }
+
+    fn menu(role: &str, title: &str, items: Vec<SnapshotNode>) -> SnapshotNode {
+        node(role, title, vec![node(kAXMenuRole, "", items)])
+    }
+
+    fn report() -> ShortcutReport {
+        let bar = node(
+            kAXMenuBarRole,
+            "",
+            vec![
+                menu(
+                    kAXMenuBarItemRole,
+                    "File",
+                    vec![
+                        item("New", true, Some(("n", 0))),
+                        item("Save, Copy", true, Some(("s", 0))),
+                        menu(
+                            kAXMenuItemRole,
+                            "Export",
+                            vec![
+                                item("PDF", true, Some(("e", kAXMenuItemModifierShift))),
+                                item("Say \"Hi\"", true, None),
+                            ],
+                        ),
+                        item("", false, None),
+                        item("Close", false, None),
+                    ],
+                ),
+                menu(
+                    kAXMenuBarItemRole,
+                    "Edit",
+                    vec![
+                        item("Keep", true, Some(("s", 0))),
+                        item("Line\nTwo", true, Some(("f", 0))),
+                    ],
+                ),
+                node(kAXMenuBarItemRole, "Help", vec![]),
+            ],
+        );
+
+        ShortcutReport::new(&MenuItem::from_snapshot(&bar))
+    }
+
+    fn path(segments: &[&str]) -> Vec<String> {
+        segments.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn collects_commands() {
+        let report = report();
+        let paths: Vec<String> = report.commands.iter().map(|c| c.path.join(" > ")).collect();
+
+        // Separators and menu bar titles without a menu aren't commands.
+        assert_eq!(
+            paths,
+            [
+                "File > New",
+                "File > Save, Copy",
+                "File > Export > PDF",
+                "File > Export > Say \"Hi\"",
+                "File > Close",
+                "Edit > Keep",
+                "Edit > Line\nTwo",
+            ]
+        );
+        assert_eq!(
+            report.missing,
+            [
+                path(&["File", "Export", "Say \"Hi\""]),
+                path(&["File", "Close"]),
+            ]
+        );
+        assert_eq!(report.duplicates.len(), 1);
+        assert_eq!(
+            report.duplicates["⌘S"],
+            [path(&["File", "Save, Copy"]), path(&["Edit", "Keep"])]
+        );
+    }
+
+    #[test]
+    fn no_commands() {
+        let report = ShortcutReport::new(&[]);
+
+        assert!(report.commands.is_empty());
+        assert_eq!(
+            report.to_text(),
+            "\n0 commands, 0 without a shortcut, 0 duplicate shortcuts\n"
+        );
+        assert_eq!(report.to_csv(), "path,shortcut,keys,enabled,duplicate\n");
+    }
+
+    #[test]
+    fn text() {
+        let expected = "\
+⌘N       File > New
+⌘S       File > Save, Copy
+⇧⌘E      File > Export > PDF
+         File > Export > Say \"Hi\"
+         File > Close (disabled)
+⌘S       Edit > Keep
+⌘F       Edit > Line
+Two
+
+duplicate shortcuts:
+  ⌘S
+    File > Save, Copy
+    Edit > Keep
+
+7 commands, 2 without a shortcut, 1 duplicate shortcuts
+";
+
+        assert_eq!(report().to_text(), expected);
+    }
+
+    #[test]
+    fn csv() {
+        let expected = "\
+path,shortcut,keys,enabled,duplicate
+File > New,⌘N,Cmd+N,true,false
+\"File > Save, Copy\",⌘S,Cmd+S,true,true
+File > Export > PDF,⇧⌘E,Cmd+Shift+E,true,false
+\"File > Export > Say \"\"Hi\"\"\",,,true,false
+File > Close,,,false,false
+Edit > Keep,⌘S,Cmd+S,true,true
+\"Edit > Line
+Two\",⌘F,Cmd+F,true,false
+";
+
+        assert_eq!(report().to_csv(), expected);
+    }
+
+    #[test]
+    fn csv_fields() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field(""), "");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("a\nb"), "\"a\nb\"");
+    }
+}
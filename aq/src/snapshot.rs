@@ -0,0 +1,49 @@
+//! `aq snapshot`, and loading snapshot files for the commands that accept
+//! `--snapshot` in place of a live target.
+
+use accessibility::{SnapshotFile, SnapshotNode};
+use std::{fs, path::Path};
+
+use crate::target::TargetOpt;
+
+/// Loads a snapshot file, as written by `aq snapshot`, or a bare tree, as
+/// printed by `aq dump --format json`.
+pub fn load_snapshot(path: &Path) -> Result<SnapshotNode, i32> {
+    let data = fs::read_to_string(path).map_err(|e| {
+        eprintln!("{}: {}", path.display(), e);
+        2
+    })?;
+
+    if let Ok(file) = serde_json::from_str::<SnapshotFile>(&data) {
+        return Ok(file.root);
+    }
+
+    serde_json::from_str::<SnapshotNode>(&data).map_err(|e| {
+        eprintln!("{}: not a snapshot file: {}", path.display(), e);
+        2
+    })
+}
+
+/// Writes a snapshot of the target to `output`, or to stdout without one.
+pub fn run(target: &TargetOpt, output: Option<&Path>) -> Result<(), i32> {
+    let file = SnapshotFile::capture(&target.element()?);
+    let json = serde_json::to_string_pretty(&file).unwrap() + "\n";
+
+    match output {
+        Some(path) => {
+            fs::write(path, json).map_err(|e| {
+                eprintln!("{}: {}", path.display(), e);
+                1
+            })?;
+            eprintln!(
+                "captured {} elements in {:.2}s to {}",
+                file.node_count,
+                file.duration,
+                path.display()
+            );
+        }
+        None => print!["{}", json],
+    }
+
+    Ok(())
+}
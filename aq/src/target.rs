@@ -0,0 +1,395 @@
+use accessibility::{AXUIElement, AXUIElementAttributes, RunningApplication};
+use std::fmt;
+use structopt::StructOpt;
+
+/// Which element a command operates on. Exactly one of these must be given.
+#[derive(Clone, Debug, Default, StructOpt)]
+pub struct TargetOpt {
+    /// Process ID of the application
+    pub pid: Option<i32>,
+    #[structopt(flatten)]
+    pub flags: TargetFlags,
+}
+
+/// The targets given by flag, for commands whose positional arguments don't
+/// fit a leading pid.
+#[derive(Clone, Debug, Default, StructOpt)]
+pub struct TargetFlags {
+    /// Application with this bundle identifier, e.g. com.apple.Safari
+    #[structopt(long)]
+    pub bundle: Option<String>,
+    /// Application with this name, ignoring case, e.g. Safari
+    #[structopt(long)]
+    pub name: Option<String>,
+    /// The application that has keyboard focus
+    #[structopt(long)]
+    pub frontmost: bool,
+    /// The system-wide element, which spans every application
+    #[structopt(long)]
+    pub system_wide: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Target {
+    Pid(i32),
+    Bundle(String),
+    Name(String),
+    Frontmost,
+    SystemWide,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Resolved {
+    Application(i32),
+    SystemWide,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TargetError {
+    Missing,
+    Conflicting(Vec<&'static str>),
+    NotFound(Target),
+    Ambiguous(Target, Vec<RunningApplication>),
+    NoFrontmost(String),
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Target::Pid(pid) => write!(f, "pid {}", pid),
+            Target::Bundle(bundle) => write!(f, "bundle {}", bundle),
+            Target::Name(name) => write!(f, "application named {:?}", name),
+            Target::Frontmost => write!(f, "frontmost application"),
+            Target::SystemWide => write!(f, "system-wide element"),
+        }
+    }
+}
+
+impl fmt::Display for TargetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TargetError::Missing => write!(
+                f,
+                "no target given: pass a pid, --bundle, --name, --frontmost or --system-wide"
+            ),
+            TargetError::Conflicting(flags) => {
+                write!(f, "only one target may be given, got {}", flags.join(", "))
+            }
+            TargetError::NotFound(target) => write!(f, "no running {}", target),
+            TargetError::Ambiguous(target, matches) => {
+                write!(f, "{} matches {} applications:", target, matches.len())?;
+
+                for app in matches {
+                    write!(
+                        f,
+                        "\n  {} {} ({})",
+                        app.pid,
+                        app.name.as_deref().unwrap_or("?"),
+                        app.bundle_id.as_deref().unwrap_or("no bundle")
+                    )?;
+                }
+
+                write!(f, "\nuse a pid to pick one")
+            }
+            TargetError::NoFrontmost(reason) => {
+                write!(f, "can't find the frontmost application: {}", reason)
+            }
+        }
+    }
+}
+
+/// Where targets are looked up, so resolution can run against a fake
+/// process list.
+pub trait Processes {
+    fn running(&self) -> Vec<RunningApplication>;
+    fn frontmost(&self) -> Result<i32, String>;
+}
+
+/// The processes of the current session.
+pub struct LiveProcesses;
+
+impl Processes for LiveProcesses {
+    fn running(&self) -> Vec<RunningApplication> {
+        AXUIElement::running_applications()
+    }
+
+    fn frontmost(&self) -> Result<i32, String> {
+        AXUIElement::system_wide()
+            .focused_application()
+            .and_then(|app| app.pid())
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Splits `[pid] <args...>` when a command takes `count` arguments after an
+/// optional pid. A pid is only taken when all `count + 1` are present, so
+/// the other arguments never need to be told apart from pids.
+pub fn split_pid(args: &[String], count: usize) -> Result<(Option<i32>, &[String]), String> {
+    if args.len() == count {
+        Ok((None, args))
+    } else if args.len() == count + 1 {
+        match args[0].parse() {
+            Ok(pid) => Ok((Some(pid), &args[1..])),
+            Err(_) => Err(format!("invalid pid {:?}", args[0])),
+        }
+    } else {
+        Err(format!(
+            "expected {} or {} arguments, got {}",
+            count,
+            count + 1,
+            args.len()
+        ))
+    }
+}
+
+impl TargetFlags {
+    pub fn with_pid(&self, pid: Option<i32>) -> TargetOpt {
+        TargetOpt {
+            pid,
+            flags: self.clone(),
+        }
+    }
+}
+
+impl TargetOpt {
+    pub fn is_given(&self) -> bool {
+        !self.given().is_empty()
+    }
+
+    fn given(&self) -> Vec<&'static str> {
+        let given = [
+            ("pid", self.pid.is_some()),
+            ("--bundle", self.flags.bundle.is_some()),
+            ("--name", self.flags.name.is_some()),
+            ("--frontmost", self.flags.frontmost),
+            ("--system-wide", self.flags.system_wide),
+        ];
+
+        given
+            .iter()
+            .filter(|(_, given)| *given)
+            .map(|(flag, _)| *flag)
+            .collect()
+    }
+
+    pub fn target(&self) -> Result<Target, TargetError> {
+        let given = self.given();
+
+        if given.len() > 1 {
+            return Err(TargetError::Conflicting(given));
+        }
+
+        if let Some(pid) = self.pid {
+            Ok(Target::Pid(pid))
+        } else if let Some(bundle) = &self.flags.bundle {
+            Ok(Target::Bundle(bundle.clone()))
+        } else if let Some(name) = &self.flags.name {
+            Ok(Target::Name(name.clone()))
+        } else if self.flags.frontmost {
+            Ok(Target::Frontmost)
+        } else if self.flags.system_wide {
+            Ok(Target::SystemWide)
+        } else {
+            Err(TargetError::Missing)
+        }
+    }
+
+    /// Resolves the target against the live system and prints any error,
+    /// returning the exit code to use.
+    pub fn element(&self) -> Result<AXUIElement, i32> {
+        let resolved = self
+            .target()
+            .and_then(|target| target.resolve(&LiveProcesses));
+
+        match resolved {
+            Ok(Resolved::Application(pid)) => Ok(AXUIElement::application(pid)),
+            Ok(Resolved::SystemWide) => Ok(AXUIElement::system_wide()),
+            Err(e) => {
+                eprintln!("{}", e);
+                Err(2)
+            }
+        }
+    }
+}
+
+impl Target {
+    pub fn resolve(&self, processes: &dyn Processes) -> Result<Resolved, TargetError> {
+        let matches: Vec<RunningApplication> = match self {
+            Target::Pid(pid) => return Ok(Resolved::Application(*pid)),
+            Target::SystemWide => return Ok(Resolved::SystemWide),
+            Target::Frontmost => {
+                return processes
+                    .frontmost()
+                    .map(Resolved::Application)
+                    .map_err(TargetError::NoFrontmost)
+            }
+            Target::Bundle(bundle) => processes
+                .running()
+                .into_iter()
+                .filter(|app| app.bundle_id.as_deref() == Some(bundle.as_str()))
+                .collect(),
+            Target::Name(name) => processes
+                .running()
+                .into_iter()
+                .filter(|app| {
+                    app.name
+                        .as_deref()
+                        .is_some_and(|n| n.to_lowercase() == name.to_lowercase())
+                })
+                .collect(),
+        };
+
+        match matches.len() {
+            0 => Err(TargetError::NotFound(self.clone())),
+            1 => Ok(Resolved::Application(matches[0].pid)),
+            _ => Err(TargetError::Ambiguous(self.clone(), matches)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeProcesses {
+        running: Vec<RunningApplication>,
+        frontmost: Result<i32, String>,
+    }
+
+    impl Processes for FakeProcesses {
+        fn running(&self) -> Vec<RunningApplication> {
+            self.running.clone()
+        }
+
+        fn frontmost(&self) -> Result<i32, String> {
+            self.frontmost.clone()
+        }
+    }
+
+    fn app(pid: i32, name: &str, bundle_id: &str) -> RunningApplication {
+        RunningApplication {
+            pid,
+            name: Some(name.to_owned()),
+            bundle_id: Some(bundle_id.to_owned()),
+        }
+    }
+
+    fn processes() -> FakeProcesses {
+        FakeProcesses {
+            running: vec![
+                app(10, "Safari", "com.apple.Safari"),
+                app(20, "Terminal", "com.apple.Terminal"),
+                app(30, "Helper", "com.example.Helper"),
+                app(31, "Helper", "com.example.Helper"),
+            ],
+            frontmost: Ok(20),
+        }
+    }
+
+    fn flags(f: impl FnOnce(&mut TargetFlags)) -> TargetOpt {
+        let mut flags = TargetFlags::default();
+
+        f(&mut flags);
+        flags.with_pid(None)
+    }
+
+    #[test]
+    fn missing() {
+        assert_eq!(TargetOpt::default().target(), Err(TargetError::Missing));
+    }
+
+    #[test]
+    fn conflicting() {
+        let mut opt = flags(|f| {
+            f.name = Some("Safari".to_owned());
+            f.frontmost = true;
+        });
+
+        opt.pid = Some(1);
+        assert_eq!(
+            opt.target(),
+            Err(TargetError::Conflicting(vec![
+                "pid",
+                "--name",
+                "--frontmost"
+            ]))
+        );
+    }
+
+    #[test]
+    fn bundle() {
+        let target = flags(|f| f.bundle = Some("com.apple.Safari".to_owned()))
+            .target()
+            .unwrap();
+
+        assert_eq!(target.resolve(&processes()), Ok(Resolved::Application(10)));
+    }
+
+    #[test]
+    fn name_ignores_case() {
+        let target = flags(|f| f.name = Some("terminal".to_owned()))
+            .target()
+            .unwrap();
+
+        assert_eq!(target.resolve(&processes()), Ok(Resolved::Application(20)));
+    }
+
+    #[test]
+    fn not_found() {
+        let target = Target::Name("Finder".to_owned());
+
+        assert_eq!(
+            target.resolve(&processes()),
+            Err(TargetError::NotFound(target))
+        );
+    }
+
+    #[test]
+    fn ambiguous() {
+        let target = Target::Bundle("com.example.Helper".to_owned());
+
+        assert_eq!(
+            target.resolve(&processes()),
+            Err(TargetError::Ambiguous(
+                target.clone(),
+                vec![
+                    app(30, "Helper", "com.example.Helper"),
+                    app(31, "Helper", "com.example.Helper"),
+                ]
+            ))
+        );
+    }
+
+    #[test]
+    fn frontmost() {
+        let mut processes = processes();
+
+        assert_eq!(
+            Target::Frontmost.resolve(&processes),
+            Ok(Resolved::Application(20))
+        );
+
+        processes.frontmost = Err("cannot complete".to_owned());
+        assert_eq!(
+            Target::Frontmost.resolve(&processes),
+            Err(TargetError::NoFrontmost("cannot complete".to_owned()))
+        );
+    }
+
+    #[test]
+    fn pid_and_system_wide_need_no_lookup() {
+        let processes = FakeProcesses {
+            running: Vec::new(),
+            frontmost: Err("none".to_owned()),
+        };
+
+        assert_eq!(
+            Target::Pid(42).resolve(&processes),
+            Ok(Resolved::Application(42))
+        );
+        assert_eq!(
+            Target::SystemWide.resolve(&processes),
+            Ok(Resolved::SystemWide)
+        );
+    }
+}
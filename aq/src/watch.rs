@@ -0,0 +1,249 @@
+//! `aq watch`: prints a line per accessibility notification.
+//!
+//! Events are formatted from the path of their element, so the formatting
+//! works the same for live elements and for synthetic events built from
+//! snapshot nodes.
+
+use accessibility::{AXAny, AXUIElement, Observer, ObserverEvent, Selectable, Selector};
+use core_foundation::runloop::{kCFRunLoopDefaultMode, CFRunLoop};
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+use crate::{
+    interact, query,
+    target::{LiveProcesses, Resolved, TargetOpt},
+};
+
+pub struct WatchOptions {
+    pub notifications: Vec<String>,
+    /// Only report events whose element matches.
+    pub selector: Option<Selector>,
+    /// Stop after this long instead of running until interrupted.
+    pub duration: Option<Duration>,
+    pub json: bool,
+}
+
+/// One reported event. `time` is in seconds since the watch started.
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub time: f64,
+    pub notification: String,
+    pub role: Option<String>,
+    pub title: Option<String>,
+    /// Labels from the application down to the element.
+    pub path: Vec<String>,
+}
+
+/// A non-empty string attribute.
+pub fn text<T: Selectable>(target: &T, name: &str) -> Option<String> {
+    match target.selector_attribute(name) {
+        Some(AXAny::String(s)) if !s.is_empty() => Some(s),
+        _ => None,
+    }
+}
+
+impl Record {
+    /// Describes an event whose element is given as its path, from the root
+    /// down to the element itself.
+    pub fn new<T: Selectable>(event: &ObserverEvent<Vec<T>>, start: Instant) -> Self {
+        let element = event.element.last();
+
+        Self {
+            time: event
+                .timestamp
+                .saturating_duration_since(start)
+                .as_secs_f64(),
+            notification: event.notification.clone(),
+            role: element.and_then(|e| text(e, "AXRole")),
+            title: element.and_then(|e| text(e, "AXTitle")),
+            path: event.element.iter().map(query::label).collect(),
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut line = format!(
+            "{:>10.3} {} {}",
+            self.time,
+            self.notification,
+            self.role.as_deref().unwrap_or("?")
+        );
+
+        if let Some(title) = &self.title {
+            line.push_str(&format!(" {:?}", title));
+        }
+
+        line.push_str(&format!("  [{}]", self.path.join(" > ")));
+        line
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+/// The line to print for `event`, or `None` if the selector filters it out.
+pub fn format_event<T: Selectable>(
+    event: &ObserverEvent<Vec<T>>,
+    start: Instant,
+    options: &WatchOptions,
+) -> Option<String> {
+    if let Some(selector) = &options.selector {
+        if !selector.matches_path(&event.element) {
+            return None;
+        }
+    }
+
+    let record = Record::new(event, start);
+
+    Some(if options.json {
+        record.to_json()
+    } else {
+        record.to_text()
+    })
+}
+
+fn with_path(event: ObserverEvent) -> ObserverEvent<Vec<AXUIElement>> {
+    ObserverEvent::new(
+        event.element.ancestry(),
+        event.notification,
+        event.timestamp,
+    )
+}
+
+pub fn run(target: &TargetOpt, options: WatchOptions) -> Result<(), i32> {
+    let resolved = target
+        .target()
+        .and_then(|target| target.resolve(&LiveProcesses));
+    let pid = match resolved {
+        Ok(Resolved::Application(pid)) => pid,
+        Ok(Resolved::SystemWide) => {
+            eprintln!("notifications can only be watched for an application");
+            return Err(2);
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            return Err(2);
+        }
+    };
+    let application = AXUIElement::application(pid);
+    let start = Instant::now();
+    let notifications = options.notifications.clone();
+    let duration = options.duration;
+    let observer = Observer::new(pid, move |event| {
+        if let Some(line) = format_event(&with_path(event), start, &options) {
+            println!["{}", line];
+        }
+    })
+    .map_err(|e| {
+        eprintln!("can't observe pid {}: {}", pid, e);
+        interact::exit_code(&e)
+    })?;
+
+    for notification in &notifications {
+        observer
+            .add_notification(&application, notification)
+            .map_err(|e| {
+                eprintln!("can't watch {}: {}", notification, e);
+                interact::exit_code(&e)
+            })?;
+    }
+
+    observer.schedule();
+
+    loop {
+        let step = match duration {
+            Some(duration) => match duration.checked_sub(start.elapsed()) {
+                Some(left) if !left.is_zero() => left.min(Duration::from_secs(1)),
+                _ => break,
+            },
+            None => Duration::from_secs(1),
+        };
+
+        CFRunLoop::run_in_mode(unsafe { kCFRunLoopDefaultMode }, step, false);
+    }
+
+    observer.unschedule();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use accessibility::SnapshotNode;
+    use serde_json::{json, Value};
+
+    fn node(role: &str, title: &str) -> SnapshotNode {
+        SnapshotNode {
+            role: role.to_owned(),
+            title: Some(title.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    fn event(start: Instant) -> ObserverEvent<Vec<SnapshotNode>> {
+        ObserverEvent::new(
+            vec![
+                node("AXApplication", "Finder"),
+                node("AXWindow", "Documents"),
+                node("AXButton", "OK"),
+            ],
+            "AXTitleChanged",
+            start + Duration::from_millis(1500),
+        )
+    }
+
+    fn options(selector: Option<&str>, json: bool) -> WatchOptions {
+        WatchOptions {
+            notifications: Vec::new(),
+            selector: selector.map(|s| s.parse().unwrap()),
+            duration: None,
+            json,
+        }
+    }
+
+    #[test]
+    fn text_output() {
+        let start = Instant::now();
+
+        assert_eq!(
+            format_event(&event(start), start, &options(None, false)).unwrap(),
+            "     1.500 AXTitleChanged AXButton \"OK\"  \
+             [AXApplication \"Finder\" > AXWindow \"Documents\" > AXButton \"OK\"]"
+        );
+    }
+
+    #[test]
+    fn json_output() {
+        let start = Instant::now();
+        let line = format_event(&event(start), start, &options(None, true)).unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<Value>(&line).unwrap(),
+            json!({
+                "time": 1.5,
+                "notification": "AXTitleChanged",
+                "role": "AXButton",
+                "title": "OK",
+                "path": [
+                    "AXApplication \"Finder\"",
+                    "AXWindow \"Documents\"",
+                    "AXButton \"OK\"",
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn selector_filters_on_the_path() {
+        let start = Instant::now();
+        let event = event(start);
+        let matches = |selector| format_event(&event, start, &options(Some(selector), false));
+
+        assert!(matches("AXButton").is_some());
+        assert!(matches("AXWindow > AXButton").is_some());
+        assert!(matches("AXApplication AXButton").is_some());
+        assert!(matches("AXApplication > AXButton").is_none());
+        assert!(matches("AXSheet AXButton").is_none());
+        assert!(matches("AXWindow").is_none());
+    }
+}
@@ -0,0 +1,331 @@
+//! Regenerates the files derived from `spec/accessibility.toml`.
+//!
+//! ```text
+//! cargo run -p codegen            # rewrite the generated files
+//! cargo run -p codegen -- --check # exit 1 if any of them is out of date
+//! ```
+
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+mod spec;
+
+use spec::{Attribute, Role, Spec};
+
+const HEADER: &str =
+    "// Generated by `cargo run -p codegen` from spec/accessibility.toml. Do not edit.\n";
+
+fn main() {
+    let check = env::args().skip(1).any(|arg| arg == "--check");
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("codegen lives inside the workspace")
+        .to_owned();
+    let spec = Spec::load(&root.join("spec/accessibility.toml")).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(2);
+    });
+    let outputs = [
+        (
+            "accessibility-sys/src/attribute_constants.rs",
+            attribute_constants(&spec),
+        ),
+        (
+            "accessibility-sys/src/role_constants.rs",
+            role_constants(&spec),
+        ),
+        (
+            "accessibility-sys/src/notification_constants.rs",
+            notification_constants(&spec),
+        ),
+        (
+            "accessibility/src/generated/attributes.rs",
+            attributes(&spec),
+        ),
+        ("accessibility/src/generated/roles.rs", roles(&spec)),
+        ("docs/attributes.md", markdown(&spec)),
+    ];
+    let mut stale = 0;
+
+    for (path, contents) in outputs.iter() {
+        let path: PathBuf = root.join(path);
+        let current = fs::read_to_string(&path).unwrap_or_default();
+
+        if current == *contents {
+            continue;
+        }
+
+        if check {
+            eprintln!("{} is out of date", path.display());
+            stale += 1;
+        } else {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).expect("create output directory");
+            }
+
+            fs::write(&path, contents).unwrap_or_else(|e| {
+                eprintln!("{}: {}", path.display(), e);
+                process::exit(2);
+            });
+            println!("wrote {}", path.display());
+        }
+    }
+
+    if stale > 0 {
+        eprintln!("run `cargo run -p codegen` to regenerate");
+        process::exit(1);
+    }
+}
+
+fn constant(out: &mut String, doc: Option<&str>, name: &str, value: &str) {
+    if let Some(doc) = doc {
+        writeln!(out, "/// {}", doc).unwrap();
+    }
+
+    writeln!(out, "pub const {}: &str = {:?};", name, value).unwrap();
+}
+
+fn attribute_constants(spec: &Spec) -> String {
+    let mut out = format!("{}#![allow(non_upper_case_globals)]\n\n", HEADER);
+
+    for attribute in &spec.attribute {
+        let primary = attribute.constant();
+
+        constant(&mut out, Some(&attribute.doc), &primary, &attribute.name);
+
+        for alias in &attribute.aliases {
+            let doc = format!("Alternate name of [`{}`].", primary);
+
+            constant(&mut out, Some(&doc), alias, &attribute.name);
+        }
+    }
+
+    out
+}
+
+fn role_constants(spec: &Spec) -> String {
+    let mut out = format!("{}#![allow(non_upper_case_globals)]\n\n", HEADER);
+
+    for (roles, kind) in [(&spec.role, "Role"), (&spec.subrole, "Subrole")].iter() {
+        for role in roles.iter() {
+            constant(
+                &mut out,
+                role.doc.as_deref(),
+                &role.constant(kind),
+                &role.name,
+            );
+        }
+    }
+
+    out
+}
+
+fn notification_constants(spec: &Spec) -> String {
+    let mut out = format!("{}#![allow(non_upper_case_globals)]\n\n", HEADER);
+
+    for notification in &spec.notification {
+        constant(
+            &mut out,
+            notification.doc.as_deref(),
+            &notification.constant(),
+            &notification.name,
+        );
+    }
+
+    out
+}
+
+fn imports(out: &mut String, mut names: Vec<String>) {
+    names.sort();
+    out.push_str("use accessibility_sys::{\n");
+
+    for name in names {
+        writeln!(out, "    {},", name).unwrap();
+    }
+
+    out.push_str("};\n");
+}
+
+fn role_list(attribute: &Attribute) -> String {
+    attribute
+        .roles
+        .iter()
+        .map(|r| format!("`{}`", r))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn attribute_doc(out: &mut String, attribute: &Attribute) {
+    writeln!(out, "    /// {}", attribute.doc).unwrap();
+
+    if !attribute.roles.is_empty() {
+        writeln!(out, "    ///\n    /// Applies to {}.", role_list(attribute)).unwrap();
+    }
+}
+
+fn attributes(spec: &Spec) -> String {
+    let mut out = format!("{}\n", HEADER);
+    let mut sorted: Vec<&Attribute> = spec.attribute.iter().collect();
+
+    sorted.sort_by_key(|a| a.accessor());
+    imports(&mut out, sorted.iter().map(|a| a.constant()).collect());
+    out.push_str("\ndefine_attributes![\n");
+
+    for attribute in sorted.iter().filter(|a| a.parameter.is_none()) {
+        attribute_doc(&mut out, attribute);
+        write!(
+            out,
+            "    ({}, {}, {}",
+            attribute.accessor(),
+            attribute.value_type,
+            attribute.constant()
+        )
+        .unwrap();
+
+        if let Some(setter) = attribute.setter() {
+            write!(out, ", {}", setter).unwrap();
+        }
+
+        out.push_str("),\n");
+    }
+
+    out.push_str("];\n\ndefine_parameterized_attributes![\n");
+
+    for attribute in &sorted {
+        if let Some(parameter) = &attribute.parameter {
+            attribute_doc(&mut out, attribute);
+            writeln!(
+                out,
+                "    ({}, {}, {}, {}),",
+                attribute.accessor(),
+                attribute.value_type,
+                parameter,
+                attribute.constant()
+            )
+            .unwrap();
+        }
+    }
+
+    out.push_str("];\n");
+    out
+}
+
+fn define_roles(out: &mut String, doc: &str, kind: &str, roles: &[Role]) {
+    writeln!(out, "\ndefine_roles![\n    /// {}\n    {},", doc, kind).unwrap();
+
+    for role in roles {
+        writeln!(out, "    /// `{}`", role.name).unwrap();
+
+        if let Some(doc) = &role.doc {
+            writeln!(out, "    ///\n    /// {}", doc).unwrap();
+        }
+
+        writeln!(out, "    ({}, {}),", role.variant(), role.constant(kind)).unwrap();
+    }
+
+    out.push_str("];\n");
+}
+
+fn roles(spec: &Spec) -> String {
+    let mut out = format!("{}\n", HEADER);
+    let constants = spec
+        .role
+        .iter()
+        .map(|r| r.constant("Role"))
+        .chain(spec.subrole.iter().map(|r| r.constant("Subrole")))
+        .collect();
+
+    imports(&mut out, constants);
+    define_roles(&mut out, "An element's `AXRole`.", "Role", &spec.role);
+    define_roles(
+        &mut out,
+        "An element's `AXSubrole`.",
+        "Subrole",
+        &spec.subrole,
+    );
+    out
+}
+
+fn markdown(spec: &Spec) -> String {
+    let mut out = String::from(
+        "<!-- Generated by `cargo run -p codegen` from spec/accessibility.toml. Do not edit. -->\n\n\
+         # Accessibility reference\n\n## Attributes\n\n\
+         | Attribute | Accessor | Type | Settable | Roles | Description |\n\
+         | --- | --- | --- | --- | --- | --- |\n",
+    );
+
+    for attribute in spec.attribute.iter().filter(|a| a.parameter.is_none()) {
+        writeln!(
+            out,
+            "| `{}` | `{}` | `{}` | {} | {} | {} |",
+            attribute.name,
+            attribute.accessor(),
+            attribute.value_type,
+            if attribute.settable { "yes" } else { "" },
+            role_list(attribute),
+            attribute.doc
+        )
+        .unwrap();
+    }
+
+    out.push_str(
+        "\n## Parameterized attributes\n\n\
+         | Attribute | Accessor | Parameter | Type | Roles | Description |\n\
+         | --- | --- | --- | --- | --- | --- |\n",
+    );
+
+    for attribute in &spec.attribute {
+        if let Some(parameter) = &attribute.parameter {
+            writeln!(
+                out,
+                "| `{}` | `{}` | `{}` | `{}` | {} | {} |",
+                attribute.name,
+                attribute.accessor(),
+                parameter,
+                attribute.value_type,
+                role_list(attribute),
+                attribute.doc
+            )
+            .unwrap();
+        }
+    }
+
+    let lists = [
+        (
+            "Roles",
+            spec.role
+                .iter()
+                .map(|r| (&r.name, &r.doc))
+                .collect::<Vec<_>>(),
+        ),
+        (
+            "Subroles",
+            spec.subrole.iter().map(|r| (&r.name, &r.doc)).collect(),
+        ),
+        (
+            "Notifications",
+            spec.notification
+                .iter()
+                .map(|n| (&n.name, &n.doc))
+                .collect(),
+        ),
+    ];
+
+    for (title, names) in lists.iter() {
+        writeln!(out, "\n## {}\n", title).unwrap();
+
+        for (name, doc) in names {
+            match doc {
+                Some(doc) => writeln!(out, "- `{}`: {}", name, doc).unwrap(),
+                None => writeln!(out, "- `{}`", name).unwrap(),
+            }
+        }
+    }
+
+    out
+}
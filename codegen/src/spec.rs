@@ -0,0 +1,179 @@
+use serde::Deserialize;
+use std::{collections::BTreeSet, fs, path::Path};
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Spec {
+    #[serde(default)]
+    pub attribute: Vec<Attribute>,
+    #[serde(default)]
+    pub role: Vec<Role>,
+    #[serde(default)]
+    pub subrole: Vec<Role>,
+    #[serde(default)]
+    pub notification: Vec<Notification>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Attribute {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub value_type: String,
+    #[serde(default)]
+    pub settable: bool,
+    pub parameter: Option<String>,
+    #[serde(default)]
+    pub roles: Vec<String>,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    pub doc: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Role {
+    pub name: String,
+    /// Overrides the enum variant, which is otherwise the name without `AX`.
+    pub variant: Option<String>,
+    pub doc: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Notification {
+    pub name: String,
+    pub doc: Option<String>,
+}
+
+impl Spec {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let spec: Spec = toml::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        spec.validate()
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(spec)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let mut constants = BTreeSet::new();
+        let names = self
+            .attribute
+            .iter()
+            .map(|a| (a.name.as_str(), a.constant()))
+            .chain(
+                self.role
+                    .iter()
+                    .map(|r| (r.name.as_str(), r.constant("Role"))),
+            )
+            .chain(
+                self.subrole
+                    .iter()
+                    .map(|r| (r.name.as_str(), r.constant("Subrole"))),
+            )
+            .chain(
+                self.notification
+                    .iter()
+                    .map(|n| (n.name.as_str(), n.constant())),
+            )
+            .chain(
+                self.attribute
+                    .iter()
+                    .flat_map(|a| a.aliases.iter().map(move |c| (a.name.as_str(), c.clone()))),
+            );
+
+        for (name, constant) in names {
+            if !name.starts_with("AX") {
+                return Err(format!("{:?} does not start with AX", name));
+            }
+
+            if !constants.insert(constant.clone()) {
+                return Err(format!("{} is defined twice", constant));
+            }
+        }
+
+        let mut accessors = BTreeSet::new();
+
+        for attribute in &self.attribute {
+            if attribute.settable && attribute.parameter.is_some() {
+                return Err(format!(
+                    "{} can't be both settable and parameterized",
+                    attribute.name
+                ));
+            }
+
+            if !accessors.insert(attribute.accessor()) {
+                return Err(format!(
+                    "accessor {} is defined twice",
+                    attribute.accessor()
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Attribute {
+    pub fn constant(&self) -> String {
+        let suffix = match self.parameter {
+            Some(_) => "ParameterizedAttribute",
+            None => "Attribute",
+        };
+
+        format!("kAX{}{}", &self.name[2..], suffix)
+    }
+
+    pub fn accessor(&self) -> String {
+        snake_case(&self.name[2..])
+    }
+
+    pub fn setter(&self) -> Option<String> {
+        if self.settable {
+            Some(format!("set_{}", self.accessor()))
+        } else {
+            None
+        }
+    }
+}
+
+impl Role {
+    pub fn constant(&self, kind: &str) -> String {
+        format!("kAX{}{}", &self.name[2..], kind)
+    }
+
+    pub fn variant(&self) -> String {
+        self.variant
+            .clone()
+            .unwrap_or_else(|| self.name[2..].to_owned())
+    }
+}
+
+impl Notification {
+    pub fn constant(&self) -> String {
+        format!("kAX{}Notification", &self.name[2..])
+    }
+}
+
+/// `TopLevelUIElement` -> `top_level_ui_element`, `AMPMField` ->
+/// `ampm_field`.
+pub fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower) {
+                out.push('_');
+            }
+        }
+
+        out.extend(c.to_lowercase());
+    }
+
+    out
+}